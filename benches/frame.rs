@@ -0,0 +1,117 @@
+//! Times a frame of the game, and the collision checks in it with the
+//! broadphase grid and without it, with more and more enemies and bullets
+//! on a level full of walls. Run with `cargo bench`.
+
+use std::{
+    f32::consts::TAU,
+    time::{Duration, Instant},
+};
+
+use breach::{
+    entity::projectiles,
+    game::TICK,
+    geometry::Pos,
+    map::Map,
+    rng::Rng,
+    state::State,
+    systems::collision::{self, Broadphase},
+};
+
+const WIDTH: usize = 160;
+const HEIGHT: usize = 60;
+const FRAMES: u32 = 500;
+
+/// A walled in arena with pillars every few cells and `enemies` Goblos spread over it.
+fn level(enemies: usize) -> String {
+    let mut rows = vec![vec![' '; WIDTH]; HEIGHT];
+
+    for (y, row) in rows.iter_mut().enumerate() {
+        for (x, cell) in row.iter_mut().enumerate() {
+            let border = x == 0 || y == 0 || x == WIDTH - 1 || y == HEIGHT - 1;
+            let pillar = x % 12 == 6 && y % 8 == 4;
+            if border || pillar {
+                *cell = '#';
+            }
+        }
+    }
+
+    let free: Vec<(usize, usize)> = (2..HEIGHT - 2)
+        .step_by(2)
+        .flat_map(|y| (10..WIDTH - 2).step_by(3).map(move |x| (x, y)))
+        .filter(|&(x, y)| rows[y][x] == ' ')
+        .collect();
+    for &(x, y) in free
+        .iter()
+        .step_by((free.len() / enemies).max(1))
+        .take(enemies)
+    {
+        rows[y][x] = 'G';
+    }
+    rows[HEIGHT / 2][2] = '@';
+
+    rows.into_iter()
+        .map(|row| row.into_iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Fires bullets in every direction from all over the arena until `count` are flying.
+fn top_up(state: &mut State, rng: &mut Rng, count: usize) {
+    while state.world.projectiles.len() < count {
+        let center = Pos(
+            rng.range(2., WIDTH as f32 - 2.),
+            rng.range(2., HEIGHT as f32 - 2.),
+        );
+        if !state.map.is_wall(&center) {
+            state
+                .world
+                .spawn(projectiles::bullet(center, rng.range(0., TAU)));
+        }
+    }
+}
+
+/// How long finding the collisions of the world takes with `broadphase`.
+fn detect(state: &State, broadphase: &mut Broadphase) -> Duration {
+    let started = Instant::now();
+    broadphase.update(&state.world);
+    std::hint::black_box(collision::detect(&state.world, broadphase));
+    started.elapsed()
+}
+
+fn main() {
+    println!("frame budget {TICK:?}");
+
+    for (enemies, bullets) in [(50, 100), (100, 200), (200, 400), (400, 600), (800, 800)] {
+        let map = Map::try_from(level(enemies).as_str()).unwrap();
+        let mut state = State::new(map.clone(), map.bounds.clone(), 0);
+        state.add_player(0);
+        // Keeps the frames coming once every player is dead or every enemy is.
+        state.networked = true;
+        let mut grid = Broadphase::new(&map);
+        let mut brute_force = Broadphase::brute_force(&map);
+        let mut rng = Rng::new(0);
+
+        let (mut total, mut worst) = (Duration::ZERO, Duration::ZERO);
+        let (mut with_grid, mut without) = (Duration::ZERO, Duration::ZERO);
+        let mut entities = 0;
+        for _ in 0..FRAMES {
+            top_up(&mut state, &mut rng, bullets);
+            entities = entities.max(state.world.ais.len() + state.world.projectiles.len());
+            with_grid += detect(&state, &mut grid);
+            without += detect(&state, &mut brute_force);
+
+            let started = Instant::now();
+            state.frame();
+            let elapsed = started.elapsed();
+            total += elapsed;
+            worst = worst.max(elapsed);
+        }
+
+        println!(
+            "{enemies:>4} enemies, {bullets:>4} bullets, up to {entities:>4} entities: frame mean {:?}, worst {worst:?}; collisions with the grid {:?}, brute force {:?}",
+            total / FRAMES,
+            with_grid / FRAMES,
+            without / FRAMES,
+        );
+    }
+}
@@ -0,0 +1,17 @@
+use std::error::Error;
+
+use breach::net::server::Server;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let addr = match std::env::args().nth(1) {
+        Some(arg) if arg.starts_with('-') => return Err("usage: breach-server [ADDR]".into()),
+        Some(addr) => addr,
+        None => "0.0.0.0:7878".to_string(),
+    };
+
+    let server = Server::bind(addr)?;
+    println!("breach-server listening on {}", server.local_addr()?);
+    server.run();
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+
+use super::{path, Bot, View};
+use crate::{event::PlayerEvent, geometry::Pos};
+
+/// Ticks between two moves, roughly how fast a held down key repeats.
+const MOVE_EVERY: u64 = 8;
+
+/// Ticks between two shots.
+const SHOOT_EVERY: u64 = 10;
+
+/// Enemies closer than this are backed away from.
+const KEEP_AWAY: f32 = 6.;
+
+/// The level is split into areas of this many cells, which are explored one by one.
+const AREA: (f32, f32) = (8., 4.);
+
+/// Shoots the closest enemy in sight while closing in on it, keeps its
+/// distance when enemies get close, and walks around walls towards the last
+/// enemy it saw when none are in sight. When it has not seen any enemies it
+/// heads for the closest area of the level it has not been to yet.
+#[derive(Debug, Default)]
+pub struct Hunter {
+    last_seen: Option<Pos>,
+    explored: HashSet<(i32, i32)>,
+}
+
+impl Bot for Hunter {
+    fn name(&self) -> &'static str {
+        "hunter"
+    }
+
+    fn act(&mut self, view: &View) -> Vec<PlayerEvent> {
+        let mut events = vec![];
+        let me = view.player.center();
+        self.explore(me);
+
+        if let Some(enemy) = view.closest_enemy() {
+            let target = enemy.hitbox.center();
+            self.last_seen = Some(target);
+
+            // Aim is measured from the corner of the player, but shots leave from its center.
+            let offset = view.player.hitbox.pos - me;
+            events.push(PlayerEvent::Aim(target + offset));
+
+            if view.tick.is_multiple_of(SHOOT_EVERY) {
+                events.push(PlayerEvent::Shoot);
+            }
+        }
+
+        if !view.tick.is_multiple_of(MOVE_EVERY) {
+            return events;
+        }
+
+        let in_sight = !view.enemies.is_empty();
+        if let Some(target) = self.last_seen {
+            if in_sight && me.distance(&target) < KEEP_AWAY {
+                let angle = me.angle(&target);
+                let Pos(x, y) = Pos(angle.cos().round(), angle.sin().round()).to_cells();
+                events.push(PlayerEvent::Move(-x, -y));
+                return events;
+            }
+            if !in_sight && me.distance(&target) < 2. {
+                self.last_seen = None;
+            }
+        }
+
+        if let Some(Pos(x, y)) = self.next_step(view) {
+            events.push(PlayerEvent::Move(x, y));
+        } else if !in_sight {
+            self.last_seen = None;
+        }
+
+        events
+    }
+}
+
+impl Hunter {
+    fn area(Pos(x, y): Pos) -> (i32, i32) {
+        ((x / AREA.0).floor() as i32, (y / AREA.1).floor() as i32)
+    }
+
+    fn explore(&mut self, me: Pos) {
+        let (x, y) = Self::area(me);
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                self.explored.insert((x + dx, y + dy));
+            }
+        }
+    }
+
+    /// Towards the last enemy seen if there is one, otherwise towards the closest unexplored area.
+    fn next_step(&self, view: &View) -> Option<Pos> {
+        let hitbox = &view.player.hitbox;
+        let center = Pos(hitbox.w / 2., hitbox.h / 2.);
+
+        match self.last_seen {
+            Some(target) => {
+                let close = if view.enemies.is_empty() {
+                    2.
+                } else {
+                    KEEP_AWAY
+                };
+                path::first_step(view.map, hitbox, |pos| {
+                    (pos + center).distance(&target) < close
+                })
+            }
+            None => path::first_step(view.map, hitbox, |pos| {
+                !self.explored.contains(&Self::area(pos + center))
+            }),
+        }
+    }
+}
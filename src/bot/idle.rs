@@ -0,0 +1,16 @@
+use super::{Bot, View};
+use crate::event::PlayerEvent;
+
+/// Stands still and does nothing, to see how dangerous a level is on its own.
+#[derive(Debug)]
+pub struct Idle;
+
+impl Bot for Idle {
+    fn name(&self) -> &'static str {
+        "idle"
+    }
+
+    fn act(&mut self, _: &View) -> Vec<PlayerEvent> {
+        vec![]
+    }
+}
@@ -0,0 +1,72 @@
+pub mod hunter;
+pub mod idle;
+pub mod path;
+pub mod runner;
+
+use crate::{
+    event::PlayerEvent,
+    game::{EntityView, Observation, PlayerView},
+    map::Map,
+};
+
+/// How far, in cells, a bot can see enemies that are not behind walls.
+pub const SIGHT: f32 = 30.;
+
+/// What a bot gets to know about the game before deciding what to do.
+#[derive(Debug)]
+pub struct View<'a> {
+    pub tick: u64,
+    pub player: &'a PlayerView,
+    pub enemies: Vec<&'a EntityView>,
+    pub map: &'a Map,
+}
+
+impl<'a> View<'a> {
+    /// The view of `player`, where only the enemies in sight of it are visible.
+    #[must_use]
+    pub fn new(observation: &'a Observation, player: &'a PlayerView, map: &'a Map) -> Self {
+        let eye = player.center();
+        let enemies = observation
+            .enemies
+            .iter()
+            .filter(|enemy| {
+                let center = enemy.hitbox.center();
+                eye.distance(&center) <= SIGHT && map.line_of_sight(&eye, &center)
+            })
+            .collect();
+
+        Self {
+            tick: observation.tick,
+            player,
+            enemies,
+            map,
+        }
+    }
+
+    #[must_use]
+    pub fn closest_enemy(&self) -> Option<&'a EntityView> {
+        let eye = self.player.center();
+        self.enemies.iter().copied().min_by(|a, b| {
+            let a = a.hitbox.center().distance(&eye);
+            let b = b.hitbox.center().distance(&eye);
+            a.total_cmp(&b)
+        })
+    }
+}
+
+/// Plays the game in place of a person, one tick at a time.
+pub trait Bot {
+    fn name(&self) -> &'static str;
+    fn act(&mut self, view: &View) -> Vec<PlayerEvent>;
+}
+
+pub const BOTS: [&str; 2] = ["idle", "hunter"];
+
+#[must_use]
+pub fn by_name(name: &str) -> Option<Box<dyn Bot>> {
+    match name {
+        "idle" => Some(Box::new(idle::Idle)),
+        "hunter" => Some(Box::new(hunter::Hunter::default())),
+        _ => None,
+    }
+}
@@ -0,0 +1,56 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::{
+    geometry::{Pos, Rect},
+    map::Map,
+};
+
+/// What a single key press moves a player: two cells sideways, or one row up or down.
+pub const STEPS: [Pos; 4] = [Pos(2., 0.), Pos(-2., 0.), Pos(0., 1.), Pos(0., -1.)];
+
+/// The first step of the shortest walk for `rect` to somewhere its corner
+/// makes `arrived` hold, going around walls. `None` if it is already there
+/// or cannot get there.
+#[must_use]
+pub fn first_step(map: &Map, rect: &Rect, arrived: impl Fn(Pos) -> bool) -> Option<Pos> {
+    // Steps are whole cells, so the walk is planned on whole cells too, for
+    // a rect covering every cell this one touches.
+    let start = Pos(rect.pos.0.floor(), rect.pos.1.floor());
+    let w = (rect.pos.0 + rect.w).ceil() - start.0;
+    let h = (rect.pos.1 + rect.h).ceil() - start.1;
+    if arrived(rect.pos) {
+        return None;
+    }
+
+    let key = |Pos(x, y): Pos| (x as i32, y as i32);
+    let free = |pos: Pos| {
+        let rect = Rect::new(&pos, w, h);
+        pos.0 >= 0.
+            && pos.1 >= 0.
+            && pos.0 + w <= map.bounds.w
+            && pos.1 + h <= map.bounds.h
+            && !map.blocks(&rect)
+    };
+
+    let mut first: HashMap<(i32, i32), Pos> = HashMap::new();
+    let mut queue = VecDeque::new();
+    first.insert(key(start), Pos::default());
+    queue.push_back(start);
+
+    while let Some(pos) = queue.pop_front() {
+        for step in STEPS {
+            let next = pos + step;
+            if first.contains_key(&key(next)) || !free(next) {
+                continue;
+            }
+            let step = if pos == start { step } else { first[&key(pos)] };
+            if arrived(next + rect.pos - start) {
+                return Some(step);
+            }
+            first.insert(key(next), step);
+            queue.push_back(next);
+        }
+    }
+
+    None
+}
@@ -0,0 +1,148 @@
+use std::fmt::Display;
+
+use super::{Bot, View};
+use crate::{entity::player::HOST_ID, event::Event, game::TICK, map::MapError, Game};
+
+/// Bots give up on a level after five minutes of play.
+pub const MAX_TICKS: u64 = 75_000;
+
+/// How a single game played by a bot ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outcome {
+    pub cleared: bool,
+    pub ticks: u64,
+    pub score: u32,
+    pub damage_taken: u32,
+}
+
+/// How a bot fared over a number of games of a level.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub bot: String,
+    pub level: String,
+    pub outcomes: Vec<Outcome>,
+}
+
+/// Plays a single game of `level` until it is cleared, lost or `max_ticks` have passed.
+///
+/// # Errors
+///
+/// If `level` cannot be read as a map.
+pub fn play(
+    bot: &mut dyn Bot,
+    level: &str,
+    seed: u64,
+    max_ticks: u64,
+) -> Result<Outcome, MapError> {
+    let mut game = Game::new(level, seed)?;
+
+    while !game.is_cleared() && !game.is_lost() && game.tick() < max_ticks {
+        let observation = game.observe();
+        let events = match observation.player(HOST_ID) {
+            Some(player) => bot.act(&View::new(&observation, player, game.map())),
+            None => vec![],
+        };
+
+        game.step(
+            events
+                .into_iter()
+                .map(|event| Event::Player(HOST_ID, event)),
+        );
+    }
+
+    let stats = game.state().stats;
+
+    Ok(Outcome {
+        cleared: game.is_cleared(),
+        ticks: game.tick(),
+        score: stats.score,
+        damage_taken: stats.damage_taken,
+    })
+}
+
+/// Plays `games` games of a level with a fresh bot for each, seeding game `n` with `n`.
+///
+/// # Errors
+///
+/// If `level` cannot be read as a map.
+pub fn run(
+    make_bot: impl Fn() -> Box<dyn Bot>,
+    (name, level): (&str, &str),
+    games: u32,
+    max_ticks: u64,
+) -> Result<Report, MapError> {
+    let outcomes = (0..games)
+        .map(|seed| play(make_bot().as_mut(), level, u64::from(seed), max_ticks))
+        .collect::<Result<_, _>>()?;
+
+    Ok(Report {
+        bot: make_bot().name().to_string(),
+        level: name.to_string(),
+        outcomes,
+    })
+}
+
+impl Report {
+    #[must_use]
+    pub fn clear_rate(&self) -> f32 {
+        if self.outcomes.is_empty() {
+            return 0.;
+        }
+        self.cleared().count() as f32 / self.outcomes.len() as f32
+    }
+
+    /// Average time it took to clear the level, in games that were cleared.
+    #[must_use]
+    pub fn mean_time_to_clear(&self) -> Option<f32> {
+        let ticks: Vec<u64> = self.cleared().map(|outcome| outcome.ticks).collect();
+        if ticks.is_empty() {
+            return None;
+        }
+        let mean = ticks.iter().sum::<u64>() as f32 / ticks.len() as f32;
+
+        Some(mean * TICK.as_secs_f32())
+    }
+
+    #[must_use]
+    pub fn mean_score(&self) -> f32 {
+        self.mean(|outcome| outcome.score)
+    }
+
+    #[must_use]
+    pub fn mean_damage_taken(&self) -> f32 {
+        self.mean(|outcome| outcome.damage_taken)
+    }
+
+    fn mean(&self, of: impl Fn(&Outcome) -> u32) -> f32 {
+        if self.outcomes.is_empty() {
+            return 0.;
+        }
+        let total: u32 = self.outcomes.iter().map(of).sum();
+
+        total as f32 / self.outcomes.len() as f32
+    }
+
+    fn cleared(&self) -> impl Iterator<Item = &Outcome> {
+        self.outcomes.iter().filter(|outcome| outcome.cleared)
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let time = self
+            .mean_time_to_clear()
+            .map_or_else(|| "-".to_string(), |secs| format!("{secs:.1}s"));
+
+        write!(
+            f,
+            "{} on {}: cleared {}/{} ({:.0}%), time to clear {time}, score {:.0}, damage taken {:.1}",
+            self.bot,
+            self.level,
+            self.cleared().count(),
+            self.outcomes.len(),
+            self.clear_rate() * 100.,
+            self.mean_score(),
+            self.mean_damage_taken(),
+        )
+    }
+}
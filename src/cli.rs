@@ -0,0 +1,321 @@
+//! The command line of the `breach` binary: a command, with options of its
+//! own, and options that go for every command. Options can also be read from
+//! a config file, with the command line taking precedence.
+
+use std::{fmt::Display, path::PathBuf};
+
+use crate::theme::Theme;
+
+pub const USAGE: &str = "\
+breach, a game where you clear rooms, played in your terminal
+
+Usage: breach [OPTIONS] [COMMAND]
+
+Commands:
+  play [--level PATH] [--seed N] [--record FILE]
+                          Play a level, the first built in one by default
+  replay FILE             Watch a recorded game
+  host ADDR               Host a game for others to join
+  join ADDR               Join a hosted game
+  connect ADDR            Connect to a dedicated server
+  bot NAME [--games N]    Let a bot play every level and report how it did
+  edit PATH               Edit a level, or make a new one at PATH
+  validate [PATH...]      Check that levels can be played, the built in ones
+                          if no paths are given
+  list-levels             List the built in levels
+  scores [--history]      Print the best runs of each level, or every run
+  help                    Print this help
+
+The commands host, join, connect and bot can also be given as --host ADDR,
+--join ADDR, --connect ADDR and --bot NAME [--games N].
+
+Without a command, the game opens on the main menu.
+
+Options:
+  --fps                   Show the frame rate in the HUD
+  --no-mouse              Leave the mouse to the terminal
+  --theme NAME            Use the theme NAME: classic, dusk or colorblind
+  --name NAME             Put runs on the scoreboard as NAME
+  --scores PATH           Keep the scoreboard in PATH instead of the data
+                          directory of the user
+  --config PATH           Read options from PATH
+  -h, --help              Print this help
+  -V, --version           Print the version
+
+A config file has one option per line, like `theme = dusk`. It can set fps,
+mouse, theme, name, scores, screen_shake and fov_shading, where the flags
+take true or false. Lines starting with # are left out.
+
+Exit codes: 0 on success, 1 when the command fails, 2 on invalid usage.";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// The main menu, when no command is given.
+    Menu,
+    Play {
+        /// A level file to play instead of the first built in level.
+        level: Option<PathBuf>,
+        seed: Option<u64>,
+        /// Where to save a replay of the game.
+        record: Option<PathBuf>,
+    },
+    Replay(PathBuf),
+    Host(String),
+    Join(String),
+    Connect(String),
+    Bot {
+        name: String,
+        games: u32,
+    },
+    /// A level file to edit, which is made if it does not exist.
+    Edit(PathBuf),
+    /// Level files to check, or none to check the built in levels.
+    Validate(Vec<PathBuf>),
+    ListLevels,
+    /// The leaderboard, or every run played if `history` is set.
+    Scores {
+        history: bool,
+    },
+    Help,
+    Version,
+}
+
+/// Options that go for every command. Options that are not given are left
+/// to the config file, and then to the defaults of the game.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Options {
+    pub fps: Option<bool>,
+    pub mouse: Option<bool>,
+    pub theme: Option<Theme>,
+    /// The name runs are put on the scoreboard with.
+    pub name: Option<String>,
+    /// Where the scoreboard is kept.
+    pub scores: Option<PathBuf>,
+    pub screen_shake: Option<bool>,
+    pub fov_shading: Option<bool>,
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cli {
+    pub command: Command,
+    pub options: Options,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CliError {
+    UnknownCommand(String),
+    /// An argument the command does not take.
+    Unexpected(String),
+    /// An option or command given without the value it needs.
+    MissingValue(&'static str),
+    InvalidValue {
+        name: String,
+        value: String,
+    },
+    UnknownTheme(String),
+    /// A line of a config file that could not be read, by its line number.
+    InvalidConfig(usize),
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::UnknownCommand(command) => write!(f, "unknown command {command}"),
+            CliError::Unexpected(arg) => write!(f, "unexpected argument {arg}"),
+            CliError::MissingValue(name) => write!(f, "{name} needs a value"),
+            CliError::InvalidValue { name, value } => write!(f, "invalid {name} {value}"),
+            CliError::UnknownTheme(name) => {
+                let themes: Vec<_> = Theme::ALL.iter().map(|theme| theme.name).collect();
+                write!(f, "unknown theme {name}, try one of {}", themes.join(", "))
+            }
+            CliError::InvalidConfig(line) => write!(f, "invalid config on line {line}"),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// The value that follows the option or command `name`.
+fn value(args: &mut impl Iterator<Item = String>, name: &'static str) -> Result<String, CliError> {
+    args.next()
+        .filter(|value| !value.starts_with('-'))
+        .ok_or(CliError::MissingValue(name))
+}
+
+fn parse<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, CliError> {
+    value.parse().map_err(|_| CliError::InvalidValue {
+        name: name.to_string(),
+        value: value.to_string(),
+    })
+}
+
+fn theme(name: &str) -> Result<Theme, CliError> {
+    Theme::get(name).ok_or_else(|| CliError::UnknownTheme(name.to_string()))
+}
+
+impl Cli {
+    /// Reads the command line, without the name of the binary.
+    ///
+    /// # Errors
+    ///
+    /// If a command or option is unknown, or is missing its value.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, CliError> {
+        let mut options = Options::default();
+        let mut rest = vec![];
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let command = match arg.as_str() {
+                "-h" | "--help" => Some(Command::Help),
+                "-V" | "--version" => Some(Command::Version),
+                "--fps" => {
+                    options.fps = Some(true);
+                    None
+                }
+                "--no-mouse" => {
+                    options.mouse = Some(false);
+                    None
+                }
+                "--theme" => {
+                    options.theme = Some(theme(&value(&mut args, "--theme")?)?);
+                    None
+                }
+                "--name" => {
+                    options.name = Some(value(&mut args, "--name")?);
+                    None
+                }
+                "--scores" => {
+                    options.scores = Some(value(&mut args, "--scores")?.into());
+                    None
+                }
+                "--config" => {
+                    options.config = Some(value(&mut args, "--config")?.into());
+                    None
+                }
+                "--host" | "--join" | "--connect" | "--bot" => {
+                    rest.push(arg.trim_start_matches('-').to_string());
+                    None
+                }
+                _ => {
+                    rest.push(arg);
+                    None
+                }
+            };
+            if let Some(command) = command {
+                return Ok(Self { command, options });
+            }
+        }
+
+        Ok(Self {
+            command: command(rest)?,
+            options,
+        })
+    }
+}
+
+fn command(args: Vec<String>) -> Result<Command, CliError> {
+    let mut args = args.into_iter();
+    let Some(name) = args.next() else {
+        return Ok(Command::Menu);
+    };
+
+    let command = match name.as_str() {
+        "play" => {
+            let (mut level, mut seed, mut record) = (None, None, None);
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--level" => level = Some(value(&mut args, "--level")?.into()),
+                    "--seed" => seed = Some(parse("--seed", &value(&mut args, "--seed")?)?),
+                    "--record" => record = Some(value(&mut args, "--record")?.into()),
+                    _ => return Err(CliError::Unexpected(arg)),
+                }
+            }
+            Command::Play {
+                level,
+                seed,
+                record,
+            }
+        }
+        "replay" => Command::Replay(value(&mut args, "replay")?.into()),
+        "host" => Command::Host(value(&mut args, "host")?),
+        "join" => Command::Join(value(&mut args, "join")?),
+        "connect" => Command::Connect(value(&mut args, "connect")?),
+        "bot" => {
+            let name = value(&mut args, "bot")?;
+            let games = match args.next().as_deref() {
+                Some("--games") => parse("--games", &value(&mut args, "--games")?)?,
+                Some(arg) => return Err(CliError::Unexpected(arg.to_string())),
+                None => 1,
+            };
+            Command::Bot { name, games }
+        }
+        "edit" => Command::Edit(value(&mut args, "edit")?.into()),
+        "validate" | "validate-level" => {
+            Command::Validate(args.by_ref().map(PathBuf::from).collect())
+        }
+        "list-levels" => Command::ListLevels,
+        "scores" => match args.next().as_deref() {
+            Some("--history") => Command::Scores { history: true },
+            Some(arg) => return Err(CliError::Unexpected(arg.to_string())),
+            None => Command::Scores { history: false },
+        },
+        "help" => Command::Help,
+        _ => return Err(CliError::UnknownCommand(name)),
+    };
+
+    match args.next() {
+        Some(arg) => Err(CliError::Unexpected(arg)),
+        None => Ok(command),
+    }
+}
+
+impl Options {
+    /// The options set in the config file `text`.
+    ///
+    /// # Errors
+    ///
+    /// If a line is not an option, or has an invalid value.
+    pub fn from_config(text: &str) -> Result<Self, CliError> {
+        let mut options = Self::default();
+
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .map(|(key, value)| (key.trim(), value.trim()))
+                .ok_or(CliError::InvalidConfig(n + 1))?;
+
+            match key {
+                "fps" => options.fps = Some(parse(key, value)?),
+                "mouse" => options.mouse = Some(parse(key, value)?),
+                "theme" => options.theme = Some(theme(value)?),
+                "name" => options.name = Some(value.to_string()),
+                "scores" => options.scores = Some(value.into()),
+                "screen_shake" => options.screen_shake = Some(parse(key, value)?),
+                "fov_shading" => options.fov_shading = Some(parse(key, value)?),
+                _ => return Err(CliError::InvalidConfig(n + 1)),
+            }
+        }
+
+        Ok(options)
+    }
+
+    /// These options, with those that are not set taken from `other`.
+    #[must_use]
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            fps: self.fps.or(other.fps),
+            mouse: self.mouse.or(other.mouse),
+            theme: self.theme.or(other.theme),
+            name: self.name.or(other.name),
+            scores: self.scores.or(other.scores),
+            screen_shake: self.screen_shake.or(other.screen_shake),
+            fov_shading: self.fov_shading.or(other.fov_shading),
+            config: self.config.or(other.config),
+        }
+    }
+}
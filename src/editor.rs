@@ -0,0 +1,418 @@
+//! A level editor that works on the text of a level, the same text the
+//! game loads. Levels are painted a cell at a time with a brush, and each
+//! stroke can be undone. The editor is drawn with the rendering of `Map`, so
+//! a level looks the way it will in the game.
+
+use crate::{
+    entity::{pickups::PickupKind, player::HOST_ID},
+    geometry::Pos,
+    map::{validate, Map},
+    render::{Color, Frame, Render},
+    theme::Role,
+};
+
+/// Strokes that can be undone before the oldest is forgotten.
+const UNDO_LIMIT: usize = 100;
+
+/// What painting a cell puts in it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Brush {
+    Wall,
+    Fire,
+    Sludge,
+    Goblo,
+    /// The start marker, of which a level has one.
+    Start,
+    Pickup(PickupKind),
+    Erase,
+}
+
+impl Brush {
+    /// The brushes in the order they are picked with the number keys, from 1 to 9 and then 0.
+    pub const ALL: [Brush; 10] = [
+        Brush::Wall,
+        Brush::Fire,
+        Brush::Sludge,
+        Brush::Goblo,
+        Brush::Start,
+        Brush::Pickup(PickupKind::Health),
+        Brush::Pickup(PickupKind::Ammo),
+        Brush::Pickup(PickupKind::Weapon),
+        Brush::Pickup(PickupKind::Charge),
+        Brush::Erase,
+    ];
+
+    /// The character the brush writes into the level text.
+    #[must_use]
+    pub fn glyph(self) -> char {
+        match self {
+            Brush::Wall => '#',
+            Brush::Fire => '^',
+            Brush::Sludge => '~',
+            Brush::Goblo => 'G',
+            Brush::Start => '@',
+            Brush::Pickup(kind) => kind.glyph(),
+            Brush::Erase => ' ',
+        }
+    }
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Brush::Wall => "wall",
+            Brush::Fire => "fire",
+            Brush::Sludge => "sludge",
+            Brush::Goblo => "goblo",
+            Brush::Start => "start",
+            Brush::Pickup(kind) => kind.name(),
+            Brush::Erase => "erase",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EditorEvent {
+    /// The mouse moved over a cell.
+    Hover(Pos),
+    /// Starts a stroke of the brush at a cell.
+    Paint(Pos),
+    /// Starts a stroke that erases, at a cell.
+    Erase(Pos),
+    /// Carries the stroke on to a cell the mouse was dragged over.
+    Drag(Pos),
+    /// Moves the cursor by whole cells.
+    Move(i32, i32),
+    /// Paints the cell under the cursor, as a stroke of its own.
+    PaintCursor,
+    Brush(Brush),
+    Undo,
+    Redo,
+    Save,
+    /// Plays the level from the cursor.
+    TestPlay,
+    Quit,
+}
+
+#[derive(Debug, Clone)]
+pub struct Editor {
+    /// The `:` lines before the rows, kept as they are.
+    header: Vec<String>,
+    rows: Vec<Vec<char>>,
+    /// The cell edits happen at, by column and row.
+    pub cursor: (usize, usize),
+    pub brush: Brush,
+    /// The brush of the stroke being painted, and whether it can already be undone.
+    stroke: Option<(Brush, bool)>,
+    undo: Vec<Vec<Vec<char>>>,
+    redo: Vec<Vec<Vec<char>>>,
+    /// The text of the level when it was last saved.
+    saved: String,
+    /// A line telling the user what just happened.
+    pub status: String,
+    /// Whether quitting was asked for once with unsaved changes.
+    confirm_quit: bool,
+    pub quit: bool,
+}
+
+/// The text of a new level: an empty room of `w` by `h` cells, walled in, with the start in the middle.
+#[must_use]
+pub fn blank(w: usize, h: usize) -> String {
+    let (w, h) = (w.max(3), h.max(3));
+    let mut rows = vec![];
+    for y in 0..h {
+        let row: String = (0..w)
+            .map(|x| match (x, y) {
+                _ if x == 0 || y == 0 || x == w - 1 || y == h - 1 => '#',
+                _ if x == w / 2 && y == h / 2 => '@',
+                _ => ' ',
+            })
+            .collect();
+        rows.push(row);
+    }
+
+    format!(":name Untitled\n{}", rows.join("\n"))
+}
+
+impl Editor {
+    /// An editor for the level in `text`. Rows are padded with spaces to the
+    /// width of the widest, and a level without any cells gets an empty room.
+    #[must_use]
+    pub fn new(text: &str) -> Self {
+        let header: Vec<String> = text
+            .lines()
+            .take_while(|line| line.starts_with(':'))
+            .map(String::from)
+            .collect();
+        let mut rows: Vec<Vec<char>> = text
+            .lines()
+            .skip(header.len())
+            .map(|line| line.chars().collect())
+            .collect();
+        if rows.iter().all(Vec::is_empty) {
+            rows = blank(40, 12)
+                .lines()
+                .skip(1)
+                .map(|line| line.chars().collect())
+                .collect();
+        }
+        let w = rows.iter().map(Vec::len).max().unwrap_or(0);
+        for row in &mut rows {
+            row.resize(w, ' ');
+        }
+
+        let mut editor = Self {
+            header,
+            rows,
+            cursor: (0, 0),
+            brush: Brush::Wall,
+            stroke: None,
+            undo: vec![],
+            redo: vec![],
+            saved: String::new(),
+            status: String::new(),
+            confirm_quit: false,
+            quit: false,
+        };
+        editor.cursor = editor.find('@').unwrap_or((0, 0));
+        editor.saved = editor.text();
+        editor
+    }
+
+    /// The level as text, the way it is saved.
+    #[must_use]
+    pub fn text(&self) -> String {
+        let rows = self.rows.iter().map(|row| row.iter().collect::<String>());
+        self.header
+            .iter()
+            .cloned()
+            .chain(rows)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The character in the cell at column `x` and row `y`.
+    #[must_use]
+    pub fn glyph(&self, x: usize, y: usize) -> Option<char> {
+        self.rows.get(y)?.get(x).copied()
+    }
+
+    /// The width and height of the level in cells.
+    #[must_use]
+    pub fn size(&self) -> (usize, usize) {
+        (self.rows[0].len(), self.rows.len())
+    }
+
+    /// Whether the level changed since it was last saved.
+    #[must_use]
+    pub fn is_modified(&self) -> bool {
+        self.text() != self.saved
+    }
+
+    pub fn handle(&mut self, event: EditorEvent) {
+        if event != EditorEvent::Quit {
+            self.confirm_quit = false;
+        }
+
+        match event {
+            EditorEvent::Hover(pos) => {
+                if let Some(cell) = self.cell(pos) {
+                    self.cursor = cell;
+                }
+            }
+            EditorEvent::Paint(pos) => self.start_stroke(pos, self.brush),
+            EditorEvent::Erase(pos) => self.start_stroke(pos, Brush::Erase),
+            EditorEvent::Drag(pos) => {
+                if let Some(cell) = self.cell(pos) {
+                    self.cursor = cell;
+                    self.paint();
+                }
+            }
+            EditorEvent::Move(dx, dy) => {
+                let (w, h) = self.size();
+                let (x, y) = self.cursor;
+                self.cursor = (
+                    x.saturating_add_signed(dx as isize)
+                        .min(w.saturating_sub(1)),
+                    y.saturating_add_signed(dy as isize)
+                        .min(h.saturating_sub(1)),
+                );
+            }
+            EditorEvent::PaintCursor => {
+                self.stroke = Some((self.brush, false));
+                self.paint();
+            }
+            EditorEvent::Brush(brush) => self.brush = brush,
+            EditorEvent::Undo => self.undo(),
+            EditorEvent::Redo => self.redo(),
+            EditorEvent::Quit if self.is_modified() && !self.confirm_quit => {
+                self.confirm_quit = true;
+                self.status = "Unsaved changes, press q again to quit".to_string();
+            }
+            EditorEvent::Quit => self.quit = true,
+            // Saving and test-playing need more than the editor, so they are
+            // up to whoever runs it, through `save` and `test_level`.
+            EditorEvent::Save | EditorEvent::TestPlay => {}
+        }
+    }
+
+    /// The cell of the level at the screen position `pos`, if it is in the level.
+    fn cell(&self, Pos(x, y): Pos) -> Option<(usize, usize)> {
+        let (w, h) = self.size();
+        let (x, y) = (x.floor(), y.floor());
+        if x < 0. || y < 0. || x >= w as f32 || y >= h as f32 {
+            return None;
+        }
+
+        Some((x as usize, y as usize))
+    }
+
+    fn find(&self, glyph: char) -> Option<(usize, usize)> {
+        self.rows.iter().enumerate().find_map(|(y, row)| {
+            let x = row.iter().position(|c| *c == glyph)?;
+            Some((x, y))
+        })
+    }
+
+    fn start_stroke(&mut self, pos: Pos, brush: Brush) {
+        if let Some(cell) = self.cell(pos) {
+            self.cursor = cell;
+            self.stroke = Some((brush, false));
+            self.paint();
+        }
+    }
+
+    /// Paints the cell under the cursor with the brush of the stroke. The
+    /// first change of a stroke is where undoing it goes back to.
+    fn paint(&mut self) {
+        let Some((brush, undoable)) = self.stroke else {
+            return;
+        };
+        let (x, y) = self.cursor;
+        if self.rows[y][x] == brush.glyph() {
+            return;
+        }
+
+        if !undoable {
+            self.undo.push(self.rows.clone());
+            if self.undo.len() > UNDO_LIMIT {
+                self.undo.remove(0);
+            }
+            self.redo.clear();
+            self.stroke = Some((brush, true));
+        }
+        if brush == Brush::Start {
+            if let Some((x, y)) = self.find('@') {
+                self.rows[y][x] = ' ';
+            }
+        }
+        self.rows[y][x] = brush.glyph();
+    }
+
+    fn undo(&mut self) {
+        if let Some(rows) = self.undo.pop() {
+            self.redo.push(std::mem::replace(&mut self.rows, rows));
+        }
+        self.stroke = None;
+    }
+
+    fn redo(&mut self) {
+        if let Some(rows) = self.redo.pop() {
+            self.undo.push(std::mem::replace(&mut self.rows, rows));
+        }
+        self.stroke = None;
+    }
+
+    /// Saves the level with `write` unless it has errors, and tells the user how it went.
+    ///
+    /// # Errors
+    ///
+    /// If `write` fails. Problems with the level are only shown in the status line.
+    pub fn save(&mut self, write: impl FnOnce(&str) -> std::io::Result<()>) -> std::io::Result<()> {
+        let text = self.text();
+        let problems = validate::validate(&text);
+        if let Some(problem) = problems.iter().find(|problem| problem.is_error()) {
+            self.status = format!("Cannot save, {problem}");
+            return Ok(());
+        }
+
+        write(&text)?;
+        self.status = match problems.first() {
+            Some(warning) => format!("Saved, but {warning}"),
+            None => "Saved".to_string(),
+        };
+        self.saved = text;
+        Ok(())
+    }
+
+    /// The level with the start moved to the cursor, to play it from there.
+    #[must_use]
+    pub fn test_level(&self) -> String {
+        let mut editor = self.clone();
+        editor.stroke = Some((Brush::Start, false));
+        editor.paint();
+        editor.text()
+    }
+
+    /// Draws the level, the cursor and the room it is in, and a status line
+    /// and the keys at the bottom of `frame`.
+    pub fn render(&self, frame: &mut Frame) {
+        frame.clear();
+        let map = Map::try_from(self.text().as_str()).ok();
+        let (cx, cy) = self.cursor;
+        let cursor = Pos(cx as f32, cy as f32);
+
+        let room = map.as_ref().and_then(|map| map.room_at(&cursor));
+        if let Some(room) = room {
+            let Pos(x0, y0) = room.rect.pos;
+            for y in 0..room.rect.h as usize {
+                for x in 0..room.rect.w as usize {
+                    frame.shade(Pos(x0 + x as f32, y0 + y as f32), Color::Role(Role::Fov));
+                }
+            }
+        }
+        if let Some(map) = &map {
+            map.render(frame);
+        }
+        for (y, row) in self.rows.iter().enumerate() {
+            for (x, &glyph) in row.iter().enumerate() {
+                let fg = match glyph {
+                    'G' => Role::Enemy,
+                    '@' => Role::Player(HOST_ID),
+                    _ if PickupKind::from_glyph(glyph).is_some() => Role::Pickup,
+                    _ => continue,
+                };
+                frame.put(Pos(x as f32, y as f32), glyph, Some(Color::Role(fg)));
+            }
+        }
+        frame.shade(cursor, Color::Role(Role::Crosshair));
+
+        let hud = Color::Role(Role::Hud);
+        let keys = "0-9 brush  click paint  right click erase  space paint  u undo  r redo  t test  s save  q quit";
+        frame.print_colored(Pos(0., f32::from(frame.h) - 2.), keys, hud);
+
+        let brushes: Vec<_> = Brush::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, brush)| {
+                let name = format!("{} {}", (i + 1) % 10, brush.name());
+                if *brush == self.brush {
+                    format!("[{name}]")
+                } else {
+                    name
+                }
+            })
+            .collect();
+        let mut status = vec![brushes.join(" "), format!("{cx},{cy}")];
+        if let Some(room) = room {
+            status.push(room.name.clone());
+        }
+        if self.is_modified() {
+            status.push("modified".to_string());
+        }
+        if !self.status.is_empty() {
+            status.push(self.status.clone());
+        }
+        let status = status.join("  ");
+        frame.print_colored(Pos(0., f32::from(frame.h) - 1.), &status, hud);
+    }
+}
@@ -0,0 +1,277 @@
+use std::ops::BitOr;
+
+use super::{
+    sprites::{Facing, Sprite},
+    status::Status,
+};
+use crate::{
+    geometry::Pos,
+    render::{Color, Frame},
+};
+
+/// Top left corner of an entity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Position(pub Pos);
+
+/// How far an entity moves every frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Velocity(pub Pos);
+
+/// How far an entity was actually moved last frame, after statuses, pushes
+/// and walls had their say.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Moved(pub Pos);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hitbox {
+    pub w: f32,
+    pub h: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Health {
+    pub hp: u32,
+    pub max: u32,
+}
+
+impl Health {
+    #[must_use]
+    pub fn new(max: u32) -> Self {
+        Self { hp: max, max }
+    }
+
+    #[must_use]
+    pub fn is_alive(&self) -> bool {
+        self.hp > 0
+    }
+
+    pub fn damage(&mut self, dmg: u32) {
+        self.hp = self.hp.saturating_sub(dmg);
+    }
+
+    /// Gives back up to `hp` health, never going over the maximum.
+    pub fn heal(&mut self, hp: u32) {
+        self.hp = (self.hp + hp).min(self.max);
+    }
+}
+
+/// Lets an entity be pushed around. Pushes add to `velocity`, which friction
+/// wears down again, on top of whatever `Velocity` the entity moves with by itself.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Physics {
+    pub velocity: Pos,
+    /// Added to the velocity every frame, until it is reset at the end of the frame.
+    pub acceleration: Pos,
+    /// How much of its velocity is lost every frame, from 0 to 1.
+    pub friction: f32,
+    pub mass: f32,
+}
+
+impl Physics {
+    #[must_use]
+    pub fn new(mass: f32, friction: f32) -> Self {
+        Self {
+            velocity: Pos::default(),
+            acceleration: Pos::default(),
+            friction,
+            mass,
+        }
+    }
+
+    /// Pushes the entity, heavier entities are pushed less far.
+    pub fn push(&mut self, impulse: Pos) {
+        self.velocity += impulse / self.mass;
+    }
+}
+
+/// How an entity decides where to go.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ai {
+    /// Walks straight towards the closest player.
+    Chase { speed: f32 },
+}
+
+/// Hurts what its collider acts on when touching it, then waits `cooldown` frames before it can do so again.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Melee {
+    pub dmg: u32,
+    pub cooldown: u32,
+    pub ready_in: u32,
+    pub knockback: f32,
+}
+
+impl Melee {
+    #[must_use]
+    pub fn new(dmg: u32, cooldown: u32, knockback: f32) -> Self {
+        Self {
+            dmg,
+            cooldown,
+            ready_in: 0,
+            knockback,
+        }
+    }
+
+    #[must_use]
+    pub fn is_ready(&self) -> bool {
+        self.ready_in == 0
+    }
+}
+
+/// Hurts whatever its collider acts on, afflicts it with `status` if it has one, and is gone.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Projectile {
+    pub dmg: u32,
+    pub knockback: f32,
+    pub status: Option<Status>,
+}
+
+/// Blows up once its fuse has burnt down, hurting and pushing away everything
+/// within `radius` rows, and afflicting it with `status` if it has one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Explosive {
+    pub fuse: u32,
+    pub radius: f32,
+    pub force: f32,
+    pub dmg: u32,
+    pub status: Option<Status>,
+}
+
+/// A set of collision layers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Layers(u8);
+
+impl Layers {
+    pub const NONE: Self = Self(0);
+    pub const PLAYER: Self = Self(1);
+    pub const ENEMY: Self = Self(1 << 1);
+    pub const PROJECTILE: Self = Self(1 << 2);
+    pub const WALL: Self = Self(1 << 3);
+    pub const PICKUP: Self = Self(1 << 4);
+
+    #[must_use]
+    pub fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+}
+
+impl BitOr for Layers {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Which layer an entity collides on, and which layers it acts on when it touches them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Collider {
+    pub layer: Layers,
+    pub mask: Layers,
+}
+
+impl Collider {
+    #[must_use]
+    pub fn new(layer: Layers, mask: Layers) -> Self {
+        Self { layer, mask }
+    }
+
+    #[must_use]
+    pub fn acts_on(&self, other: &Collider) -> bool {
+        self.mask.intersects(other.layer)
+    }
+}
+
+/// Entities with a lifetime are despawned once they are `ttl` frames old.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lifetime {
+    pub age: u32,
+    pub ttl: u32,
+}
+
+impl Lifetime {
+    #[must_use]
+    pub fn new(ttl: u32) -> Self {
+        Self { age: 0, ttl }
+    }
+}
+
+/// Entities are drawn layer by layer, so that later layers end up on top.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Layer {
+    Pickup,
+    Enemy,
+    Projectile,
+    Effect,
+    Player,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Glyph {
+    /// Drawn as is, one row per line.
+    Text(String),
+    /// Animated, walking while the entity moves and idling otherwise.
+    Sprite(&'static Sprite),
+}
+
+/// What an entity is up to when it is drawn, which decides what its glyph looks like.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Pose {
+    /// Frames the entity, or the animation it is in, has been going for.
+    pub age: u32,
+    pub facing: Facing,
+    pub moving: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Renderable {
+    pub glyph: Glyph,
+    pub fg: Option<Color>,
+    /// Where the glyph is drawn relative to the position of the entity.
+    pub offset: Pos,
+    pub layer: Layer,
+}
+
+impl Renderable {
+    #[must_use]
+    pub fn new(glyph: Glyph, layer: Layer) -> Self {
+        Self {
+            glyph,
+            fg: None,
+            offset: Pos(0., 0.),
+            layer,
+        }
+    }
+
+    #[must_use]
+    pub fn fg(self, fg: Color) -> Self {
+        Self {
+            fg: Some(fg),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn offset(self, offset: Pos) -> Self {
+        Self { offset, ..self }
+    }
+
+    /// Draws the glyph for an entity at `pos` that is in `pose`.
+    pub fn draw(&self, frame: &mut Frame, pos: Pos, pose: Pose) {
+        let Pos(x, y) = pos.transpose(self.offset);
+        let age = pose.age;
+
+        match &self.glyph {
+            Glyph::Text(text) => {
+                for (row, line) in text.lines().enumerate() {
+                    frame.print_colored(Pos(x, y + row as f32), line, self.fg);
+                }
+            }
+            Glyph::Sprite(sprite) => {
+                let clip = sprite.clip(if pose.moving { "walk" } else { "idle" });
+                if let Some(sprite_frame) = clip.and_then(|clip| clip.frame(pose.facing, age)) {
+                    sprite_frame.draw(frame, Pos(x, y), self.fg);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use super::bundle;
+use crate::{entity::particles::Emitter, geometry::Pos, render::Color, world::Bundle};
+
+const FIRE: &[Color] = &[Color::White, Color::Yellow, Color::Red, Color::Red];
+
+/// A fireball flying out to about `radius` rows from `center`, and debris
+/// thrown up that falls back down.
+#[must_use]
+pub fn bundles(center: Pos, radius: f32) -> Vec<Bundle> {
+    // Lives just long enough to reach the edge of the blast at full speed.
+    let speed = 0.5;
+    let lifetime = (radius / speed).ceil() as u32;
+
+    let fire = Emitter::new("@#*+:.", 24, lifetime)
+        .lifetime(lifetime / 2, lifetime)
+        .speed(speed / 4., speed)
+        .colors(FIRE);
+    let debris = Emitter::new(",.'`", 8, 30)
+        .lifetime(20, 30)
+        .direction(-std::f32::consts::FRAC_PI_2, 1.)
+        .speed(0.2, 0.5)
+        .gravity(0.03);
+
+    vec![bundle(center, fire), bundle(center, debris)]
+}
@@ -1,58 +1,35 @@
-use crossterm::{cursor::MoveTo, style::Print};
-
 use crate::{
-    entity::{effect::Effect, Entity},
+    entity::{
+        components::{Glyph, Layer, Lifetime, Position, Renderable, Velocity},
+        particles::Emitter,
+        EntityKind,
+    },
     geometry::Pos,
-    render::Render,
+    render::Color,
+    theme::Role,
+    world::Bundle,
 };
 
-#[derive(Debug, Clone)]
-pub struct HitEffect {
-    pos: Pos,
-    dmg: u32,
-    frame: u8,
-}
-
-impl HitEffect {
-    #[must_use]
-    pub fn new(pos: &Pos, dmg: u32) -> Self {
-        Self {
-            pos: *pos,
-            dmg,
-            frame: 0,
-        }
-    }
-}
-
-impl Effect for HitEffect {
-    fn is_done(&self) -> bool {
-        self.frame >= 5
-    }
-}
-
-impl Entity for HitEffect {
-    fn id(&self) -> String {
-        "effect:hit".to_string()
-    }
-
-    fn update(&mut self, _: &crate::state::State) {
-        self.frame += 1;
-        self.pos.1 -= 0.5;
-    }
-
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
-    }
-}
-
-impl Render for HitEffect {
-    fn render(
-        &self,
-        stdout: &mut std::io::Stdout,
-    ) -> std::result::Result<(), std::boxed::Box<(dyn std::error::Error + 'static)>> {
-        let (x, y) = self.pos.into();
-        crossterm::queue!(stdout, MoveTo(x, y), Print("-"), Print(self.dmg))?;
-
-        Ok(())
-    }
+/// The damage dealt, floating up from where it was dealt, and a spray of
+/// blood falling from the wound.
+#[must_use]
+pub fn bundles(pos: Pos, dmg: u32) -> Vec<Bundle> {
+    let number = Bundle::default()
+        .kind(EntityKind::Effect)
+        .position(Position(pos))
+        .velocity(Velocity(Pos(0., -0.5)))
+        .lifetime(Lifetime::new(5))
+        .renderable(
+            Renderable::new(Glyph::Text(format!("-{dmg}")), Layer::Effect)
+                .fg(Color::Role(Role::Damage)),
+        );
+
+    let blood = Emitter::new("*,.", 2 + dmg.min(6), 12)
+        .lifetime(8, 16)
+        .direction(-std::f32::consts::FRAC_PI_2, 1.2)
+        .speed(0.1, 0.3)
+        .gravity(0.04)
+        .colors(&[Color::Role(Role::Damage)]);
+
+    vec![number, super::bundle(pos + Pos(1., 1.), blood)]
 }
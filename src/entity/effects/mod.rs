@@ -1,2 +1,16 @@
-pub mod hit;
 pub mod blink;
+pub mod explosion;
+pub mod hit;
+pub mod muzzle;
+
+use super::{components::Position, particles::Emitter, EntityKind};
+use crate::{geometry::Pos, world::Bundle};
+
+/// An effect entity at `pos` that runs `emitter` until it is done.
+#[must_use]
+pub fn bundle(pos: Pos, emitter: Emitter) -> Bundle {
+    Bundle::default()
+        .kind(EntityKind::Effect)
+        .position(Position(pos))
+        .emitter(emitter)
+}
@@ -0,0 +1,11 @@
+use crate::{entity::particles::Emitter, render::Color};
+
+/// A short flash of sparks, spraying out the way a shot went.
+#[must_use]
+pub fn flash(angle: f32) -> Emitter {
+    Emitter::new("*+'", 3, 4)
+        .lifetime(2, 4)
+        .direction(angle, 0.4)
+        .speed(0.3, 0.6)
+        .colors(&[Color::Yellow, Color::White])
+}
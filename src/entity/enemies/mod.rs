@@ -1,79 +1,47 @@
 use super::{
-    effects::hit::HitEffect, enemy::Enemy, projectile::Projectile, projectiles::Bullet, Collidable,
-    Entity,
+    components::{
+        Ai, Collider, Glyph, Health, Hitbox, Layer, Layers, Melee, Physics, Position, Renderable,
+        Velocity,
+    },
+    pickups::{Drops, PickupKind},
+    sprites, EntityKind,
 };
-use crate::{geometry::Pos, render::Render};
-use crossterm::{cursor::MoveTo, style::Print};
-
-#[derive(Debug, Clone, Copy)]
-pub struct Goblo {
-    hp: u32,
-    pos: Pos,
-    vel: f32,
-}
-
-impl Goblo {
-    #[must_use]
-    pub fn new(pos: Pos) -> Self {
-        Self {
-            hp: 10,
-            pos,
-            vel: 0.05,
-        }
-    }
-}
-
-impl Enemy for Goblo {
-    fn is_alive(&self) -> bool {
-        self.hp > 0
-    }
-
-    fn dmg(&self) -> u32 {
-        todo!()
-    }
-}
-
-impl Entity for Goblo {
-    fn id(&self) -> String {
-        "goblo".to_string()
-    }
-
-    fn update(&mut self, state: &crate::state::State) {
-        let alpha = self.pos.angle(&state.player.borrow().hitbox().center());
-        self.pos = Pos(
-            self.pos.0 + alpha.cos() * self.vel * 2.,
-            self.pos.1 + alpha.sin() * self.vel,
-        );
-    }
-
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
-    }
-}
-
-impl Render for Goblo {
-    fn render(&self, stdout: &mut std::io::Stdout) -> Result<(), Box<dyn std::error::Error>> {
-        let (x, y) = self.pos.into();
-        crossterm::queue!(stdout, MoveTo(x, y), Print("G"))?;
-
-        Ok(())
-    }
-}
-
-impl Collidable for Goblo {
-    fn hitbox(&self) -> crate::geometry::Rect {
-        crate::geometry::Rect {
-            pos: self.pos,
-            w: 2.,
-            h: 2.,
-        }
-    }
-
-    fn on_hit(&mut self, other: Box<&dyn Collidable>, state: &crate::state::State) {
-        if let Some(projectile) = (*other).as_any().downcast_ref::<Bullet>() {
-            self.hp = self.hp.saturating_sub(projectile.dmg());
-            let effect = HitEffect::new(&self.pos, projectile.dmg());
-            state.spawn_effect(effect);
-        }
-    }
+use crate::{geometry::Pos, render::Color, theme::Role, world::Bundle};
+
+/// Frames a Goblo waits after hitting a player before it can hit again.
+const ATTACK_COOLDOWN: u32 = 125;
+
+/// Points for killing a Goblo, before the combo multiplier.
+const GOBLO_BOUNTY: u32 = 100;
+
+/// What a Goblo may leave behind, about every third one something.
+const GOBLO_DROPS: Drops = Drops(&[
+    (PickupKind::Health, 0.12),
+    (PickupKind::Ammo, 0.12),
+    (PickupKind::Charge, 0.06),
+    (PickupKind::Weapon, 0.03),
+]);
+
+/// # Panics
+///
+/// If the Goblo sprite is missing from the game.
+#[must_use]
+pub fn goblo(pos: Pos) -> Bundle {
+    let sprite = sprites::get("goblo").expect("the goblo sprite ships with the game");
+
+    Bundle::default()
+        .kind(EntityKind::Goblo)
+        .position(Position(pos))
+        .velocity(Velocity(Pos(0., 0.)))
+        .physics(Physics::new(1., 0.15))
+        .hitbox(Hitbox { w: 2., h: 2. })
+        .collider(Collider::new(Layers::ENEMY, Layers::PLAYER | Layers::WALL))
+        .health(Health::new(10))
+        .ai(Ai::Chase { speed: 0.05 })
+        .melee(Melee::new(5, ATTACK_COOLDOWN, 1.))
+        .drops(GOBLO_DROPS)
+        .bounty(GOBLO_BOUNTY)
+        .renderable(
+            Renderable::new(Glyph::Sprite(sprite), Layer::Enemy).fg(Color::Role(Role::Enemy)),
+        )
 }
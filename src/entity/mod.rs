@@ -1,22 +1,42 @@
-pub mod effect;
+pub mod components;
 pub mod effects;
 pub mod enemies;
-pub mod enemy;
+pub mod particles;
+pub mod pickups;
 pub mod player;
-pub mod projectile;
 pub mod projectiles;
+pub mod sprites;
+pub mod status;
 
-use std::fmt::Debug;
+use std::fmt::Display;
 
-use crate::{geometry::Rect, state::State};
+use pickups::PickupKind;
+use player::PlayerId;
 
-pub trait Entity: Debug {
-    fn id(&self) -> String;
-    fn update(&mut self, state: &State);
-    fn as_any(&self) -> &dyn std::any::Any;
+/// Identifies a spawned entity. Ids are never reused, so an id of an entity that
+/// has been despawned will not point to another entity later on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EntityId(pub u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntityKind {
+    Player(PlayerId),
+    Goblo,
+    Bullet,
+    Grenade,
+    Effect,
+    Pickup(PickupKind),
 }
 
-pub trait Collidable: Entity {
-    fn hitbox(&self) -> Rect;
-    fn on_hit(&mut self, other: Box<&dyn Collidable>, state: &State);
+impl Display for EntityKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EntityKind::Player(id) => write!(f, "player {id}"),
+            EntityKind::Goblo => write!(f, "goblo"),
+            EntityKind::Bullet => write!(f, "bullet"),
+            EntityKind::Grenade => write!(f, "grenade"),
+            EntityKind::Effect => write!(f, "effect"),
+            EntityKind::Pickup(kind) => write!(f, "{}", kind.name()),
+        }
+    }
 }
@@ -0,0 +1,180 @@
+use crate::{
+    geometry::Pos,
+    render::{Color, Frame},
+    rng::Rng,
+};
+
+/// A single speck of an effect, drawn as one cell.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Particle {
+    pub pos: Pos,
+    pub velocity: Pos,
+    pub age: u32,
+    pub ttl: u32,
+}
+
+impl Particle {
+    /// How far through its life the particle is, from 0 to 1.
+    fn progress(&self) -> f32 {
+        self.age as f32 / self.ttl.max(1) as f32
+    }
+}
+
+/// Spawns particles where it is and moves them along until they die. Every
+/// emitter keeps its own particles, so a particle costs no more than an entry
+/// in a list, and dozens of emitters can run at once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Emitter {
+    /// Particles spawned as soon as the emitter starts.
+    pub burst: u32,
+    /// Particles spawned every frame while emitting, fractions adding up over frames.
+    pub rate: f32,
+    /// Frames it keeps emitting for after the burst.
+    pub duration: u32,
+    /// Frames a particle lives, picked between the two.
+    pub lifetime: (u32, u32),
+    /// Direction, in radians, particles fly off in.
+    pub angle: f32,
+    /// How far off `angle` particles may fly, in radians either way.
+    pub spread: f32,
+    /// Rows a particle moves every frame, picked between the two.
+    pub speed: (f32, f32),
+    /// Added to how fast every particle falls, every frame.
+    pub gravity: f32,
+    /// Characters a particle goes through over its life, first to last.
+    pub chars: &'static str,
+    /// Colors a particle goes through over its life. Particles are uncolored if empty.
+    pub colors: &'static [Color],
+    age: u32,
+    pending: f32,
+    particles: Vec<Particle>,
+}
+
+impl Emitter {
+    /// A single burst of `burst` still particles that live for `lifetime` frames.
+    #[must_use]
+    pub fn new(chars: &'static str, burst: u32, lifetime: u32) -> Self {
+        Self {
+            burst,
+            rate: 0.,
+            duration: 0,
+            lifetime: (lifetime, lifetime),
+            angle: 0.,
+            spread: std::f32::consts::PI,
+            speed: (0., 0.),
+            gravity: 0.,
+            chars,
+            colors: &[],
+            age: 0,
+            pending: 0.,
+            particles: vec![],
+        }
+    }
+
+    /// Keeps spawning `rate` particles a frame for `duration` frames.
+    #[must_use]
+    pub fn rate(self, rate: f32, duration: u32) -> Self {
+        Self {
+            rate,
+            duration,
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn lifetime(self, min: u32, max: u32) -> Self {
+        Self {
+            lifetime: (min, max),
+            ..self
+        }
+    }
+
+    /// Sends particles off towards `angle`, at most `spread` radians off it.
+    #[must_use]
+    pub fn direction(self, angle: f32, spread: f32) -> Self {
+        Self {
+            angle,
+            spread,
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn speed(self, min: f32, max: f32) -> Self {
+        Self {
+            speed: (min, max),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn gravity(self, gravity: f32) -> Self {
+        Self { gravity, ..self }
+    }
+
+    #[must_use]
+    pub fn colors(self, colors: &'static [Color]) -> Self {
+        Self { colors, ..self }
+    }
+
+    #[must_use]
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles
+    }
+
+    /// Whether it is done emitting and all of its particles have died.
+    #[must_use]
+    pub fn is_done(&self) -> bool {
+        self.age > self.duration && self.particles.is_empty()
+    }
+
+    /// Spawns this frame's particles at `pos`, then moves every particle along and lets old ones die.
+    pub fn update(&mut self, pos: Pos, rng: &mut Rng) {
+        let mut spawns = if self.age == 0 { self.burst } else { 0 };
+        if self.age < self.duration {
+            self.pending += self.rate;
+            spawns += self.pending as u32;
+            self.pending = self.pending.fract();
+        }
+
+        for _ in 0..spawns {
+            let angle = self.angle + rng.range(-self.spread, self.spread);
+            let speed = rng.range(self.speed.0, self.speed.1);
+            let ttl = rng.range(self.lifetime.0 as f32, self.lifetime.1 as f32 + 1.) as u32;
+            self.particles.push(Particle {
+                pos,
+                velocity: (Pos::from_angle(angle) * speed).to_cells(),
+                age: 0,
+                ttl: ttl.clamp(self.lifetime.0, self.lifetime.1),
+            });
+        }
+
+        for particle in &mut self.particles {
+            particle.velocity.1 += self.gravity;
+            particle.pos += particle.velocity;
+            particle.age += 1;
+        }
+        self.particles
+            .retain(|particle| particle.age < particle.ttl);
+        self.age += 1;
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        let chars: Vec<char> = self.chars.chars().collect();
+
+        for particle in &self.particles {
+            let progress = particle.progress();
+            let ch = ramp(&chars, progress);
+            let fg = ramp(self.colors, progress);
+            if let Some(ch) = ch {
+                frame.put(particle.pos, ch, fg);
+            }
+        }
+    }
+}
+
+/// The step of `ramp` that is `progress` of the way through it.
+fn ramp<T: Copy>(ramp: &[T], progress: f32) -> Option<T> {
+    let i = (progress * ramp.len() as f32) as usize;
+    ramp.get(i.min(ramp.len().saturating_sub(1))).copied()
+}
@@ -0,0 +1,86 @@
+use super::{
+    components::{Collider, Glyph, Hitbox, Layer, Layers, Position, Renderable},
+    EntityKind,
+};
+use crate::{geometry::Pos, render::Color, rng::Rng, theme::Role, world::Bundle};
+
+/// Something lying around for a player to pick up by walking over it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickupKind {
+    /// Kept until the player heals with it.
+    Health,
+    /// A spare magazine, which makes the next reload instant.
+    Ammo,
+    /// A weapon the player takes up, keeping the one they held.
+    Weapon,
+    /// Makes an ability ready while it is cooling down.
+    Charge,
+}
+
+impl PickupKind {
+    pub const ALL: [Self; 4] = [
+        PickupKind::Health,
+        PickupKind::Ammo,
+        PickupKind::Weapon,
+        PickupKind::Charge,
+    ];
+
+    /// The character the pickup is drawn with, and placed with in a level.
+    #[must_use]
+    pub fn glyph(self) -> char {
+        match self {
+            PickupKind::Health => '+',
+            PickupKind::Ammo => '=',
+            PickupKind::Weapon => '!',
+            PickupKind::Charge => '*',
+        }
+    }
+
+    #[must_use]
+    pub fn from_glyph(glyph: char) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.glyph() == glyph)
+    }
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            PickupKind::Health => "medkit",
+            PickupKind::Ammo => "magazine",
+            PickupKind::Weapon => "smg",
+            PickupKind::Charge => "ability charge",
+        }
+    }
+}
+
+/// What an enemy may leave behind when it dies: the chance, from 0 to 1, of
+/// dropping each kind of pickup. At most one pickup is dropped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Drops(pub &'static [(PickupKind, f32)]);
+
+impl Drops {
+    /// The pickup dropped this time, if any.
+    pub fn roll(&self, rng: &mut Rng) -> Option<PickupKind> {
+        let mut roll = rng.f32();
+        for (kind, chance) in self.0 {
+            if roll < *chance {
+                return Some(*kind);
+            }
+            roll -= chance;
+        }
+        None
+    }
+}
+
+#[must_use]
+pub fn bundle(kind: PickupKind, pos: Pos) -> Bundle {
+    Bundle::default()
+        .kind(EntityKind::Pickup(kind))
+        .position(Position(pos))
+        .hitbox(Hitbox { w: 1., h: 1. })
+        .collider(Collider::new(Layers::PICKUP, Layers::NONE))
+        .pickup(kind)
+        .renderable(
+            Renderable::new(Glyph::Text(kind.glyph().to_string()), Layer::Pickup)
+                .fg(Color::Role(Role::Pickup)),
+        )
+}
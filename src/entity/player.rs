@@ -1,78 +1,279 @@
-use super::{enemy::Enemy, projectile::Projectile, Collidable, Entity};
-use crate::{
-    geometry::{Pos, Rect},
-    render::Render,
+use std::collections::BTreeMap;
+
+use super::{
+    components::{Collider, Glyph, Health, Hitbox, Layer, Layers, Physics, Position, Renderable},
+    pickups::PickupKind,
+    sprites, EntityKind,
 };
-use crossterm::{
-    cursor::MoveTo,
-    style::{Color, Print, ResetColor, SetForegroundColor},
+use crate::{
+    geometry::Pos,
+    render::{Color, Frame},
+    theme::Role,
+    world::Bundle,
 };
-use std::error::Error;
 
-#[derive(Debug, Clone)]
+pub type PlayerId = u8;
+
+/// The id of the player in a single player game, or the one hosting a networked game.
+pub const HOST_ID: PlayerId = 0;
+
+pub const MAX_HP: u32 = 100;
+
+/// Health a medkit gives back.
+pub const MEDKIT_HP: u32 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Ability {
     Blink,
+    Grenade,
+}
+
+impl Ability {
+    pub const ALL: [Self; 2] = [Ability::Blink, Ability::Grenade];
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Ability::Blink => "blink",
+            Ability::Grenade => "grenade",
+        }
+    }
+
+    /// Frames to wait after using the ability before it can be used again.
+    #[must_use]
+    pub fn cooldown(self) -> u32 {
+        match self {
+            Ability::Blink => 250,
+            Ability::Grenade => 750,
+        }
+    }
+}
+
+/// What a player shoots with. Once the magazine is empty, it reloads by itself.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Weapon {
+    pub name: &'static str,
+    pub ammo: u32,
+    pub magazine: u32,
+    /// Frames it takes to reload.
+    pub reload: u32,
+    /// Frames left until it is reloaded, 0 when it is not reloading.
+    pub reloading: u32,
+}
+
+impl Weapon {
+    #[must_use]
+    pub fn pistol() -> Self {
+        Self {
+            name: "pistol",
+            ammo: 12,
+            magazine: 12,
+            reload: 200,
+            reloading: 0,
+        }
+    }
+
+    /// Uses up a round, starting to reload if it was the last one. False if there was nothing to shoot.
+    pub fn fire(&mut self) -> bool {
+        if self.ammo == 0 {
+            return false;
+        }
+        self.ammo -= 1;
+        if self.ammo == 0 {
+            self.reloading = self.reload;
+        }
+        true
+    }
+
+    /// Fires quickly, but empties its magazine as quickly.
+    #[must_use]
+    pub fn smg() -> Self {
+        Self {
+            name: "smg",
+            ammo: 30,
+            magazine: 30,
+            reload: 250,
+            reloading: 0,
+        }
+    }
+
+    /// Advances reloading by a frame.
+    pub fn tick(&mut self) {
+        if self.reloading == 0 {
+            return;
+        }
+        self.reloading -= 1;
+        if self.reloading == 0 {
+            self.ammo = self.magazine;
+        }
+    }
+}
+
+/// What a player carries besides the weapon they hold. There is only room
+/// for a few of each kind of pickup.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Inventory {
+    pub medkits: u32,
+    /// Spare magazines, each making a reload instant.
+    pub magazines: u32,
+    /// Charges that each make an ability ready while it is cooling down.
+    pub charges: u32,
+    /// Weapons carried but not held.
+    pub weapons: Vec<Weapon>,
 }
 
-#[derive(Debug)]
+impl Inventory {
+    /// The most of each kind of pickup a player can carry.
+    pub const ROOM: u32 = 3;
+}
+
+/// The component of an entity controlled by a player.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Player {
-    pub pos: Pos,
+    pub id: PlayerId,
     pub aim: f32,
     pub fov: u32,
-    hp: u32,
+    pub weapon: Weapon,
+    /// Frames left until each ability can be used again, missing when it is ready.
+    pub cooldowns: BTreeMap<Ability, u32>,
+    pub inventory: Inventory,
 }
 
 impl Player {
     #[must_use]
-    pub fn new(pos: Pos) -> Self {
+    pub fn new(id: PlayerId) -> Self {
         Self {
-            pos,
+            id,
             aim: 0.,
             fov: 90,
-            hp: 100,
+            weapon: Weapon::pistol(),
+            cooldowns: BTreeMap::new(),
+            inventory: Inventory::default(),
         }
     }
-}
 
-impl Entity for Player {
-    fn id(&self) -> String {
-        "player".to_string()
+    /// Frames until `ability` can be used, 0 when it is ready.
+    #[must_use]
+    pub fn cooldown(&self, ability: Ability) -> u32 {
+        self.cooldowns.get(&ability).copied().unwrap_or(0)
     }
 
-    fn update(&mut self, _: &crate::state::State) {}
+    /// Puts `ability` on cooldown if it is ready, or if there is a charge to
+    /// spend on it, returning whether it could be used.
+    pub fn use_ability(&mut self, ability: Ability) -> bool {
+        if self.cooldown(ability) > 0 {
+            if self.inventory.charges == 0 {
+                return false;
+            }
+            self.inventory.charges -= 1;
+        }
+        self.cooldowns.insert(ability, ability.cooldown());
+        true
+    }
 
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    /// Fires the weapon, reloading it at once if it runs dry and there is a
+    /// spare magazine. False if there was nothing to shoot.
+    pub fn fire(&mut self) -> bool {
+        if !self.weapon.fire() {
+            return false;
+        }
+        if self.weapon.reloading > 0 && self.inventory.magazines > 0 {
+            self.inventory.magazines -= 1;
+            self.weapon.reloading = 0;
+            self.weapon.ammo = self.weapon.magazine;
+        }
+        true
     }
-}
 
-impl Render for Player {
-    fn render(&self, stdout: &mut std::io::Stdout) -> Result<(), Box<dyn Error>> {
-        let (x, y) = self.hitbox().center().into();
-        crossterm::queue!(
-            stdout,
-            SetForegroundColor(Color::Red),
-            MoveTo(x - 1, y - 1),
-            Print("╭━╮"),
-            MoveTo(x - 1, y),
-            Print("╰━╯"),
-            ResetColor,
-        )?;
-        Ok(())
+    /// Holds the next weapon carried, putting away the one held. False if no other weapon is carried.
+    pub fn switch_weapon(&mut self) -> bool {
+        if self.inventory.weapons.is_empty() {
+            return false;
+        }
+        let next = self.inventory.weapons.remove(0);
+        let held = std::mem::replace(&mut self.weapon, next);
+        self.inventory.weapons.push(held);
+        true
     }
-}
 
-impl Collidable for Player {
-    fn hitbox(&self) -> Rect {
-        Rect::new(&self.pos, 3., 3.)
+    /// Takes a pickup of `kind`, returning false if there is no room for it.
+    /// A weapon is taken up at once, unless the player already has one like it.
+    pub fn pick_up(&mut self, kind: PickupKind) -> bool {
+        let inventory = &mut self.inventory;
+        let count = match kind {
+            PickupKind::Health => &mut inventory.medkits,
+            PickupKind::Ammo => &mut inventory.magazines,
+            PickupKind::Charge => &mut inventory.charges,
+            PickupKind::Weapon => {
+                let weapon = Weapon::smg();
+                let has = |held: &Weapon| held.name == weapon.name;
+                if has(&self.weapon) || inventory.weapons.iter().any(has) {
+                    return false;
+                }
+                let held = std::mem::replace(&mut self.weapon, weapon);
+                self.inventory.weapons.push(held);
+                return true;
+            }
+        };
+        if *count >= Inventory::ROOM {
+            return false;
+        }
+        *count += 1;
+        true
     }
 
-    fn on_hit(&mut self, other: Box<&dyn Collidable>, _: &crate::state::State) {
-        if let Some(projectile) = other.as_any().downcast_ref::<Box<dyn Projectile>>() {
-            self.hp = self.hp.saturating_sub(projectile.dmg());
+    /// Advances reloading and cooldowns by a frame.
+    pub fn tick(&mut self) {
+        self.weapon.tick();
+        for ready_in in self.cooldowns.values_mut() {
+            *ready_in = ready_in.saturating_sub(1);
         }
-        if let Some(enemy) = other.as_any().downcast_ref::<Box<dyn Enemy>>() {
-            self.hp = self.hp.saturating_sub(enemy.dmg());
+        self.cooldowns.retain(|_, ready_in| *ready_in > 0);
+    }
+
+    /// Shades the cone the player at `pos` is aiming in.
+    pub fn render_fov(&self, pos: Pos, frame: &mut Frame) {
+        let fov = self.fov as i32;
+
+        for a in (-fov / 2..fov / 2).step_by(2) {
+            let ray_angle = self.aim + (a as f32).to_radians();
+
+            for p in 1..=20 {
+                let x = pos.0 + ray_angle.cos() * p as f32;
+                let y = pos.1 + ray_angle.sin() * p as f32;
+
+                frame.shade(Pos(x, y), Color::Role(Role::Fov));
+            }
         }
     }
+
+    pub fn render_crosshair(&self, pos: Pos, frame: &mut Frame) {
+        let crosshair = pos + (Pos::from_angle(self.aim) * 5.).to_cells();
+        frame.print_colored(crosshair, "⌖", Color::Role(Role::Crosshair));
+    }
+}
+
+/// # Panics
+///
+/// If the player sprite is missing from the game.
+#[must_use]
+pub fn renderable(id: PlayerId) -> Renderable {
+    let sprite = sprites::get("player").expect("the player sprite ships with the game");
+
+    Renderable::new(Glyph::Sprite(sprite), Layer::Player)
+        .fg(Color::Role(Role::Player(id)))
+        .offset(Pos(0.5, 0.5))
+}
+
+#[must_use]
+pub fn bundle(id: PlayerId, pos: Pos) -> Bundle {
+    Bundle::default()
+        .kind(EntityKind::Player(id))
+        .player(Player::new(id))
+        .position(Position(pos))
+        .physics(Physics::new(3., 0.2))
+        .hitbox(Hitbox { w: 3., h: 3. })
+        .collider(Collider::new(Layers::PLAYER, Layers::WALL | Layers::PICKUP))
+        .health(Health::new(MAX_HP))
+        .renderable(renderable(id))
 }
@@ -1,71 +1,58 @@
-use super::{projectile::Projectile, Collidable, Entity};
-use crate::{geometry::Pos, render::Render};
-use crossterm::{cursor::MoveTo, style::Print};
-
-#[derive(Debug, Clone, Copy)]
-pub struct Bullet {
-    pos: Pos,
-    angle: f32,
-    vel: f32,
-}
-
-impl Bullet {
-    #[must_use]
-    pub fn new(pos: Pos, angle: f32) -> Self {
-        Self {
-            pos,
-            angle,
-            vel: 0.5,
-        }
-    }
-}
-
-impl Render for Bullet {
-    fn render(&self, stdout: &mut std::io::Stdout) -> Result<(), Box<dyn std::error::Error>> {
-        let (x, y) = self.pos.into();
-        crossterm::queue!(stdout, MoveTo(x, y), Print('•'))?;
-
-        Ok(())
-    }
+use super::{
+    components::{
+        Collider, Explosive, Glyph, Hitbox, Layer, Layers, Physics, Position, Projectile,
+        Renderable, Velocity,
+    },
+    status::{Status, StatusKind},
+    EntityKind,
+};
+use crate::{geometry::Pos, world::Bundle};
+
+/// Briefly slows down what it hits.
+#[must_use]
+pub fn bullet(center: Pos, angle: f32) -> Bundle {
+    Bundle::default()
+        .kind(EntityKind::Bullet)
+        .position(Position(center - Pos(0.5, 0.5)))
+        .velocity(Velocity((Pos::from_angle(angle) * 0.5).to_cells()))
+        .hitbox(Hitbox { w: 1., h: 1. })
+        .collider(Collider::new(
+            Layers::PROJECTILE,
+            Layers::ENEMY | Layers::WALL,
+        ))
+        .projectile(Projectile {
+            dmg: 1,
+            knockback: 0.3,
+            status: Some(Status::new(StatusKind::Slow, 30)),
+        })
+        .renderable(Renderable::new(
+            Glyph::Text("•".to_string()),
+            Layer::Projectile,
+        ))
 }
 
-impl Projectile for Bullet {
-    fn dmg(&self) -> u32 {
-        1
-    }
-
-    fn pos(&self) -> &Pos {
-        &self.pos
-    }
-}
-
-impl Entity for Bullet {
-    fn id(&self) -> String {
-        "Bullet".to_string()
-    }
-
-    fn update(&mut self, _: &crate::state::State) {
-        let x = self.pos.0 + self.angle.cos() * self.vel;
-        let y = self.pos.1 + self.angle.sin() * self.vel;
-
-        self.pos = Pos(x, y);
-    }
-
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
-    }
-}
-
-impl Collidable for Bullet {
-    fn hitbox(&self) -> crate::geometry::Rect {
-        crate::geometry::Rect {
-            pos: self.pos,
-            w: 1.,
-            h: 1.,
-        }
-    }
-
-    fn on_hit(&mut self, _other: Box<&dyn Collidable>, _state: &crate::state::State) {
-        self.pos = Pos(-1., -1.);
-    }
+/// Thrown rather than shot, it slides to a stop and blows up once its fuse
+/// has burnt down, stunning what it hurts.
+#[must_use]
+pub fn grenade(center: Pos, angle: f32) -> Bundle {
+    let mut physics = Physics::new(1., 0.04);
+    physics.push(Pos::from_angle(angle).to_cells() * 0.6);
+
+    Bundle::default()
+        .kind(EntityKind::Grenade)
+        .position(Position(center - Pos(0.5, 0.5)))
+        .physics(physics)
+        .hitbox(Hitbox { w: 1., h: 1. })
+        .collider(Collider::new(Layers::NONE, Layers::WALL))
+        .explosive(Explosive {
+            fuse: 150,
+            radius: 6.,
+            force: 2.,
+            dmg: 5,
+            status: Some(Status::new(StatusKind::Stun, 90)),
+        })
+        .renderable(Renderable::new(
+            Glyph::Text("●".to_string()),
+            Layer::Projectile,
+        ))
 }
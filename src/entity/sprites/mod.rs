@@ -0,0 +1,287 @@
+//! Multi-cell pictures of entities, read from the sprite files next to this
+//! module. A sprite file is a list of clips, each made of frames for one or
+//! more facings:
+//!
+//! ```text
+//! # Comments start with a hash, and go between frames.
+//! clip walk 20        <- a clip named walk, showing each frame for 20 ticks
+//! face left           <- the frames that follow are for facing left
+//! frame
+//! (G                  <- the cells of the frame, spaces are see-through
+//! /\
+//! colors
+//! .g                  <- one color per cell, `.` keeps the color of the entity
+//! ..
+//! ```
+//!
+//! Colors are `k`, `r`, `g`, `y`, `b`, `m`, `c` and `w`, for black, red,
+//! green, yellow, blue, magenta, cyan and white.
+
+use std::{collections::HashMap, fmt::Display, str::FromStr, sync::OnceLock};
+
+use crate::{
+    geometry::Pos,
+    render::{Color, Frame},
+};
+
+/// Sprites that ship with the game, by name.
+const SPRITES: [(&str, &str); 2] = [
+    ("player", include_str!("player.txt")),
+    ("goblo", include_str!("goblo.txt")),
+];
+
+/// The built in sprite called `name`, read from its file the first time it is asked for.
+///
+/// # Panics
+///
+/// If the file of a built in sprite is broken.
+#[must_use]
+pub fn get(name: &str) -> Option<&'static Sprite> {
+    static LOADED: OnceLock<HashMap<&str, Sprite>> = OnceLock::new();
+
+    let loaded = LOADED.get_or_init(|| {
+        SPRITES
+            .iter()
+            .map(|(name, text)| match text.parse() {
+                Ok(sprite) => (*name, sprite),
+                Err(err) => panic!("sprite {name} is broken: {err}"),
+            })
+            .collect()
+    });
+
+    loaded.get(name)
+}
+
+/// Which way an entity is looking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Facing {
+    #[default]
+    Right,
+    Down,
+    Left,
+    Up,
+}
+
+impl Facing {
+    /// The facing closest to `angle`, in radians.
+    #[must_use]
+    pub fn from_angle(angle: f32) -> Self {
+        let quarter = (angle / std::f32::consts::FRAC_PI_2).round() as i32;
+
+        match quarter.rem_euclid(4) {
+            0 => Facing::Right,
+            1 => Facing::Down,
+            2 => Facing::Left,
+            _ => Facing::Up,
+        }
+    }
+}
+
+impl FromStr for Facing {
+    type Err = SpriteError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "right" => Ok(Facing::Right),
+            "down" => Ok(Facing::Down),
+            "left" => Ok(Facing::Left),
+            "up" => Ok(Facing::Up),
+            _ => Err(SpriteError::UnknownFacing(s.to_string())),
+        }
+    }
+}
+
+/// A cell of a frame. `None` cells are see-through.
+type SpriteCell = Option<(char, Option<Color>)>;
+
+/// A single picture of a sprite, row by row.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpriteFrame {
+    rows: Vec<Vec<SpriteCell>>,
+}
+
+impl SpriteFrame {
+    /// Draws the frame with its top left corner at `pos`. Cells without a
+    /// color of their own are drawn in `fg`, and cells that fall outside of
+    /// `frame` are left out.
+    pub fn draw(&self, frame: &mut Frame, pos: Pos, fg: Option<Color>) {
+        for (y, row) in self.rows.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if let Some((ch, color)) = cell {
+                    frame.put(pos + Pos(x as f32, y as f32), *ch, color.or(fg));
+                }
+            }
+        }
+    }
+}
+
+/// An animation, with frames for each way it can face.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clip {
+    pub name: String,
+    /// Ticks each frame is shown for.
+    pub ticks: u32,
+    facings: Vec<(Facing, Vec<SpriteFrame>)>,
+}
+
+impl Clip {
+    /// The frame to show `age` ticks into the clip, looping, for facing
+    /// `facing`, or for the first facing the clip has if it lacks that one.
+    #[must_use]
+    pub fn frame(&self, facing: Facing, age: u32) -> Option<&SpriteFrame> {
+        let (_, frames) = self
+            .facings
+            .iter()
+            .find(|(f, _)| *f == facing)
+            .or(self.facings.first())?;
+        let n = age / self.ticks.max(1);
+
+        frames.get(n as usize % frames.len().max(1))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sprite {
+    clips: Vec<Clip>,
+}
+
+impl Sprite {
+    /// The clip called `name`, or the first one if the sprite has no such clip.
+    #[must_use]
+    pub fn clip(&self, name: &str) -> Option<&Clip> {
+        self.clips
+            .iter()
+            .find(|clip| clip.name == name)
+            .or(self.clips.first())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpriteError {
+    /// A line that is neither a keyword nor part of a frame, by its line number.
+    UnexpectedLine(usize),
+    UnknownFacing(String),
+    UnknownColor(char),
+    InvalidTicks(String),
+    /// The sprite has no clips with frames in them.
+    Empty,
+}
+
+impl Display for SpriteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpriteError::UnexpectedLine(line) => write!(f, "unexpected line {line}"),
+            SpriteError::UnknownFacing(facing) => write!(f, "unknown facing {facing}"),
+            SpriteError::UnknownColor(color) => write!(f, "unknown color {color}"),
+            SpriteError::InvalidTicks(ticks) => write!(f, "invalid ticks {ticks}"),
+            SpriteError::Empty => write!(f, "sprite has no frames"),
+        }
+    }
+}
+
+impl std::error::Error for SpriteError {}
+
+fn color(c: char) -> Result<Option<Color>, SpriteError> {
+    Ok(Some(match c {
+        '.' | ' ' => return Ok(None),
+        'k' => Color::Black,
+        'r' => Color::Red,
+        'g' => Color::Green,
+        'y' => Color::Yellow,
+        'b' => Color::Blue,
+        'm' => Color::Magenta,
+        'c' => Color::Cyan,
+        'w' => Color::White,
+        c => return Err(SpriteError::UnknownColor(c)),
+    }))
+}
+
+/// The frames of the last clip for `facing`, added to the clip if it has none yet.
+fn frames(clips: &mut [Clip], facing: Facing) -> Option<&mut Vec<SpriteFrame>> {
+    let clip = clips.last_mut()?;
+    if !clip.facings.iter().any(|(f, _)| *f == facing) {
+        clip.facings.push((facing, vec![]));
+    }
+    let i = clip.facings.iter().position(|(f, _)| *f == facing)?;
+
+    Some(&mut clip.facings[i].1)
+}
+
+/// What the lines that are not keywords are read as.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Section {
+    None,
+    Cells,
+    Colors(usize),
+}
+
+impl FromStr for Sprite {
+    type Err = SpriteError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut clips: Vec<Clip> = vec![];
+        let mut facing = Facing::default();
+        let mut section = Section::None;
+
+        for (n, line) in s.lines().enumerate() {
+            let words: Vec<_> = line.split_whitespace().collect();
+            match (section, words.as_slice()) {
+                (_, []) => {}
+                (Section::None, [comment, ..]) if comment.starts_with('#') => {}
+                (_, ["clip", name, ticks]) => {
+                    let ticks = ticks
+                        .parse()
+                        .map_err(|_| SpriteError::InvalidTicks((*ticks).to_string()))?;
+                    clips.push(Clip {
+                        name: (*name).to_string(),
+                        ticks,
+                        facings: vec![],
+                    });
+                    facing = Facing::default();
+                    section = Section::None;
+                }
+                (_, ["face", f]) => {
+                    facing = f.parse()?;
+                    section = Section::None;
+                }
+                (_, ["frame"]) => {
+                    frames(&mut clips, facing)
+                        .ok_or(SpriteError::UnexpectedLine(n + 1))?
+                        .push(SpriteFrame::default());
+                    section = Section::Cells;
+                }
+                (_, ["colors"]) => section = Section::Colors(0),
+                (Section::Cells, _) => {
+                    let frame = frames(&mut clips, facing)
+                        .and_then(|frames| frames.last_mut())
+                        .ok_or(SpriteError::UnexpectedLine(n + 1))?;
+                    let row = line
+                        .chars()
+                        .map(|ch| (ch != ' ').then_some((ch, None)))
+                        .collect();
+                    frame.rows.push(row);
+                }
+                (Section::Colors(y), _) => {
+                    let row = frames(&mut clips, facing)
+                        .and_then(|frames| frames.last_mut())
+                        .and_then(|frame| frame.rows.get_mut(y))
+                        .ok_or(SpriteError::UnexpectedLine(n + 1))?;
+                    for (cell, c) in row.iter_mut().zip(line.chars()) {
+                        if let Some((_, fg)) = cell {
+                            *fg = color(c)?;
+                        }
+                    }
+                    section = Section::Colors(y + 1);
+                }
+                (Section::None, _) => return Err(SpriteError::UnexpectedLine(n + 1)),
+            }
+        }
+
+        clips.retain(|clip| !clip.facings.is_empty());
+        if clips.is_empty() {
+            return Err(SpriteError::Empty);
+        }
+
+        Ok(Self { clips })
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{render::Color, theme::Role};
+
+/// Poison stacks up to this many times, hurting once per stack.
+const MAX_POISON: u32 = 5;
+
+/// Slows stack up to this many times, each taking a bit more off the speed.
+const MAX_SLOW: u32 = 3;
+
+/// How much of its speed an entity loses per stack of slow.
+const SLOW_PER_STACK: f32 = 0.25;
+
+/// How much more damage a vulnerable entity takes.
+const VULNERABLE_FACTOR: f32 = 1.5;
+
+/// Frames between two ticks of damage over time.
+pub const DOT_EVERY: u32 = 20;
+
+/// Kinds of statuses, ordered by how much they matter: when an entity has
+/// several, the tint of the last one shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StatusKind {
+    /// Takes more damage from everything. Does not stack.
+    Vulnerable,
+    /// Moves slower, more so per stack.
+    Slow,
+    /// Hurts every `DOT_EVERY` frames, once per stack.
+    Poison,
+    /// Hurts every `DOT_EVERY` frames. Catching fire again only makes it burn longer.
+    Burning,
+    /// Can neither move by itself nor attack. Stuns do not stack.
+    Stun,
+}
+
+impl StatusKind {
+    /// The tint an entity gets while it has this status.
+    #[must_use]
+    pub fn color(self) -> Color {
+        match self {
+            StatusKind::Burning => Color::Role(Role::Burning),
+            StatusKind::Poison => Color::Role(Role::Poison),
+            StatusKind::Slow => Color::Role(Role::Slow),
+            StatusKind::Stun => Color::Role(Role::Stun),
+            StatusKind::Vulnerable => Color::Role(Role::Vulnerable),
+        }
+    }
+
+    fn max_stacks(self) -> u32 {
+        match self {
+            StatusKind::Poison => MAX_POISON,
+            StatusKind::Slow => MAX_SLOW,
+            StatusKind::Burning | StatusKind::Stun | StatusKind::Vulnerable => 1,
+        }
+    }
+}
+
+/// A status that wears off after `ticks` frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Status {
+    pub kind: StatusKind,
+    pub ticks: u32,
+    pub stacks: u32,
+}
+
+impl Status {
+    #[must_use]
+    pub fn new(kind: StatusKind, ticks: u32) -> Self {
+        Self {
+            kind,
+            ticks,
+            stacks: 1,
+        }
+    }
+
+    /// How much it hurts when damage over time ticks.
+    #[must_use]
+    pub fn dmg(&self) -> u32 {
+        match self.kind {
+            StatusKind::Burning | StatusKind::Poison => self.stacks,
+            StatusKind::Slow | StatusKind::Stun | StatusKind::Vulnerable => 0,
+        }
+    }
+}
+
+/// The statuses an entity has, at most one per kind.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Statuses(pub Vec<Status>);
+
+impl Statuses {
+    /// Adds a status. One of a kind the entity already has lasts for the
+    /// longer of the two, and adds its stacks if the kind stacks.
+    pub fn apply(&mut self, status: Status) {
+        let Some(current) = self.0.iter_mut().find(|s| s.kind == status.kind) else {
+            self.0.push(status);
+            self.0.sort_by_key(|s| s.kind);
+            return;
+        };
+
+        current.ticks = current.ticks.max(status.ticks);
+        current.stacks = (current.stacks + status.stacks).min(status.kind.max_stacks());
+    }
+
+    #[must_use]
+    pub fn get(&self, kind: StatusKind) -> Option<&Status> {
+        self.0.iter().find(|s| s.kind == kind)
+    }
+
+    #[must_use]
+    pub fn has(&self, kind: StatusKind) -> bool {
+        self.get(kind).is_some()
+    }
+
+    /// How much of its own speed the entity keeps, from 0 to 1.
+    #[must_use]
+    pub fn speed(&self) -> f32 {
+        if self.has(StatusKind::Stun) {
+            return 0.;
+        }
+        let slow = self.get(StatusKind::Slow).map_or(0, |s| s.stacks);
+
+        1. - SLOW_PER_STACK * slow as f32
+    }
+
+    /// How much `dmg` ends up hurting.
+    #[must_use]
+    pub fn damage(&self, dmg: u32) -> u32 {
+        if self.has(StatusKind::Vulnerable) {
+            (dmg as f32 * VULNERABLE_FACTOR).round() as u32
+        } else {
+            dmg
+        }
+    }
+
+    /// The tint of the status that matters most right now.
+    #[must_use]
+    pub fn color(&self) -> Option<Color> {
+        self.0.iter().map(|s| s.kind).max().map(StatusKind::color)
+    }
+}
@@ -1,23 +1,43 @@
-use crate::{entity::player::Ability, geometry::Pos};
+use crate::{
+    entity::player::{Ability, PlayerId},
+    geometry::Pos,
+};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Event {
-    Player(PlayerEvent),
+    Player(PlayerId, PlayerEvent),
     Game(GameEvent),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum GameEvent {
+    /// Opens the pause menu.
     Pause,
     Play,
+    /// Picks the highlighted item of a menu.
+    Select,
+    /// Leaves a menu, or opens the pause menu while playing.
+    Back,
     Resize(u16, u16),
+    Join(PlayerId),
+    Leave(PlayerId),
+    /// Opens or closes the full message log.
+    ToggleLog,
+    /// Scrolls the open message log back by this many entries, or forward if negative.
+    ScrollLog(i16),
+    /// Puts the action the rebind screen is asking about on this key.
+    Bind(char),
     Quit,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum PlayerEvent {
     Move(f32, f32),
     Aim(Pos),
     Shoot,
     Ability(Ability),
+    /// Heals with a medkit from the inventory.
+    UseMedkit,
+    /// Holds the next weapon carried.
+    SwitchWeapon,
 }
@@ -0,0 +1,198 @@
+use std::time::Duration;
+
+use crate::{
+    entity::{
+        player::{PlayerId, HOST_ID},
+        EntityId, EntityKind,
+    },
+    event::{Event, PlayerEvent},
+    geometry::{Pos, Rect},
+    hud::Hud,
+    map::{Map, MapError},
+    render::Frame,
+    scene::Scenes,
+    scoreboard::Scoreboard,
+    settings::Settings,
+    state::State,
+};
+
+/// How long a single step of the game represents.
+pub const TICK: Duration = Duration::from_millis(4);
+
+/// A game that can be driven without a terminal: feed it inputs one
+/// tick at a time and look at what happened.
+#[derive(Debug)]
+pub struct Game {
+    state: State,
+    tick: u64,
+}
+
+/// Everything a player could know about the game at a given tick.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Observation {
+    pub tick: u64,
+    pub players: Vec<PlayerView>,
+    pub enemies: Vec<EntityView>,
+    pub projectiles: Vec<EntityView>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerView {
+    pub id: PlayerId,
+    pub hitbox: Rect,
+    pub aim: f32,
+    pub hp: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityView {
+    pub id: EntityId,
+    pub kind: EntityKind,
+    pub hitbox: Rect,
+}
+
+impl Game {
+    /// A single player game of `level`, where the player has the id `HOST_ID`.
+    ///
+    /// # Errors
+    ///
+    /// If `level` cannot be read as a map.
+    pub fn new(level: &str, seed: u64) -> Result<Self, MapError> {
+        let mut game = Self::lobby(level, seed)?;
+        game.state.add_player(HOST_ID);
+        Ok(game)
+    }
+
+    /// A game of `level` without any players, they are added as they `GameEvent::Join`.
+    ///
+    /// # Errors
+    ///
+    /// If `level` cannot be read as a map.
+    pub fn lobby(level: &str, seed: u64) -> Result<Self, MapError> {
+        let map = Map::try_from(level)?;
+        let canvas = map.bounds.clone();
+
+        Ok(Self {
+            state: State::new(map, canvas, seed),
+            tick: 0,
+        })
+    }
+
+    /// Applies the inputs and advances the game by one tick.
+    pub fn step(&mut self, inputs: impl IntoIterator<Item = Event>) {
+        for event in inputs {
+            self.state.handle(event);
+        }
+        self.state.frame();
+        self.tick += 1;
+    }
+
+    #[must_use]
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    #[must_use]
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// The HUD, to pick whose stats it shows and tell it the frame rate.
+    pub fn hud_mut(&mut self) -> &mut Hud {
+        &mut self.state.hud
+    }
+
+    #[must_use]
+    pub fn map(&self) -> &Map {
+        &self.state.map
+    }
+
+    /// The open scenes, to start the game on a menu.
+    pub fn scenes_mut(&mut self) -> &mut Scenes {
+        &mut self.state.scenes
+    }
+
+    pub fn settings_mut(&mut self) -> &mut Settings {
+        &mut self.state.settings
+    }
+
+    /// The runs played before, shown in the high scores menu.
+    pub fn scoreboard_mut(&mut self) -> &mut Scoreboard {
+        &mut self.state.scoreboard
+    }
+
+    /// Makes the game go on while menus are open, for games run by every
+    /// player of a networked session.
+    pub fn set_networked(&mut self, networked: bool) {
+        self.state.networked = networked;
+    }
+
+    /// Navigates the open menu with an event of the local player, without
+    /// it reaching the world. Networked games take menu input this way.
+    pub fn navigate(&mut self, event: &PlayerEvent) {
+        self.state.navigate(event);
+    }
+
+    /// Whether every player has died.
+    #[must_use]
+    pub fn is_lost(&self) -> bool {
+        self.state.is_lost()
+    }
+
+    /// Whether every enemy in the level has been killed.
+    #[must_use]
+    pub fn is_cleared(&self) -> bool {
+        self.state.is_cleared()
+    }
+
+    #[must_use]
+    pub fn observe(&self) -> Observation {
+        let world = &self.state.world;
+
+        let players = world
+            .players
+            .iter()
+            .filter_map(|(id, player)| {
+                Some(PlayerView {
+                    id: player.id,
+                    hitbox: world.hitbox(*id)?,
+                    aim: player.aim,
+                    hp: world.healths.get(id).map_or(0, |health| health.hp),
+                })
+            })
+            .collect();
+
+        let view = |id: &EntityId| {
+            Some(EntityView {
+                id: *id,
+                kind: *world.kinds.get(id)?,
+                hitbox: world.hitbox(*id)?,
+            })
+        };
+
+        Observation {
+            tick: self.tick,
+            players,
+            enemies: world.ais.keys().filter_map(view).collect(),
+            projectiles: world.projectiles.keys().filter_map(view).collect(),
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        self.state.render(frame);
+    }
+}
+
+impl Observation {
+    #[must_use]
+    pub fn player(&self, id: PlayerId) -> Option<&PlayerView> {
+        self.players.iter().find(|player| player.id == id)
+    }
+}
+
+impl PlayerView {
+    #[must_use]
+    pub fn center(&self) -> Pos {
+        self.hitbox.center()
+    }
+}
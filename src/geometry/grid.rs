@@ -0,0 +1,80 @@
+use std::ops::RangeInclusive;
+
+use super::Rect;
+
+/// A uniform grid over `bounds` that finds what might overlap a rect without
+/// looking at everything. Items are stored in every cell their rect touches,
+/// and rects outside of the bounds are kept in the cells along the edge.
+#[derive(Debug, Clone)]
+pub struct Grid<T> {
+    bounds: Rect,
+    cell: f32,
+    cols: usize,
+    rows: usize,
+    cells: Vec<Vec<T>>,
+}
+
+impl<T: Copy + Ord> Grid<T> {
+    #[must_use]
+    pub fn new(bounds: Rect, cell: f32) -> Self {
+        let cols = ((bounds.w / cell).ceil() as usize).max(1);
+        let rows = ((bounds.h / cell).ceil() as usize).max(1);
+
+        Self {
+            bounds,
+            cell,
+            cols,
+            rows,
+            cells: vec![vec![]; cols * rows],
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for cell in &mut self.cells {
+            cell.clear();
+        }
+    }
+
+    pub fn insert(&mut self, rect: &Rect, item: T) {
+        let (cols, rows) = self.span(rect);
+
+        for y in rows {
+            for x in cols.clone() {
+                self.cells[y * self.cols + x].push(item);
+            }
+        }
+    }
+
+    /// Items in the cells `rect` touches, sorted and without duplicates. They
+    /// are only candidates, and still need to be checked against `rect`.
+    #[must_use]
+    pub fn query(&self, rect: &Rect) -> Vec<T> {
+        let (cols, rows) = self.span(rect);
+        let mut items = vec![];
+
+        for y in rows {
+            for x in cols.clone() {
+                items.extend_from_slice(&self.cells[y * self.cols + x]);
+            }
+        }
+        items.sort_unstable();
+        items.dedup();
+
+        items
+    }
+
+    fn span(&self, rect: &Rect) -> (RangeInclusive<usize>, RangeInclusive<usize>) {
+        let index = |from: f32, at: f32, len: usize| {
+            let i = ((at - from) / self.cell).floor().max(0.) as usize;
+            i.min(len - 1)
+        };
+        let Rect { pos, w, h } = rect;
+
+        (
+            index(self.bounds.pos.0, pos.0, self.cols)
+                ..=index(self.bounds.pos.0, pos.0 + w, self.cols),
+            index(self.bounds.pos.1, pos.1, self.rows)
+                ..=index(self.bounds.pos.1, pos.1 + h, self.rows),
+        )
+    }
+}
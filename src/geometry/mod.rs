@@ -1,7 +1,71 @@
-#[derive(Debug, Clone, Copy, PartialEq)]
+pub mod grid;
+mod shapes;
+
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+pub use shapes::{Circle, Segment};
+
+/// Terminal cells are about twice as tall as they are wide, so it takes two
+/// cells sideways to cover the distance of one cell up or down.
+pub const CELL_ASPECT: f32 = 2.;
+
+/// A point in the world, or the vector between two points.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub struct Pos(pub f32, pub f32);
 
 impl Pos {
+    /// The vector of length one pointing at `angle` radians.
+    #[must_use]
+    pub fn from_angle(angle: f32) -> Pos {
+        Pos(angle.cos(), angle.sin())
+    }
+
+    #[must_use]
+    pub fn length(self) -> f32 {
+        self.dot(self).sqrt()
+    }
+
+    /// The vector of length one pointing the same way, or the zero vector if this is it.
+    #[must_use]
+    pub fn normalize(self) -> Pos {
+        let length = self.length();
+        if length == 0. {
+            return self;
+        }
+        self / length
+    }
+
+    #[must_use]
+    pub fn dot(self, other: Pos) -> f32 {
+        self.0 * other.0 + self.1 * other.1
+    }
+
+    /// The point `t` of the way from this one to `other`.
+    #[must_use]
+    pub fn lerp(self, other: Pos, t: f32) -> Pos {
+        self + (other - self) * t
+    }
+
+    /// Rotates the vector `angle` radians, clockwise on screen since y points down.
+    #[must_use]
+    pub fn rotate(self, angle: f32) -> Pos {
+        let (sin, cos) = angle.sin_cos();
+        Pos(self.0 * cos - self.1 * sin, self.0 * sin + self.1 * cos)
+    }
+
+    /// Stretches a vector measured in rows along both axes into cells, so that
+    /// it looks as long on screen whichever way it points.
+    #[must_use]
+    pub fn to_cells(self) -> Pos {
+        Pos(self.0 * CELL_ASPECT, self.1)
+    }
+
+    /// Undoes `to_cells`.
+    #[must_use]
+    pub fn from_cells(self) -> Pos {
+        Pos(self.0 / CELL_ASPECT, self.1)
+    }
+
     #[must_use]
     pub fn transpose(&self, Pos(dx, dy): Pos) -> Pos {
         Pos(self.0 + dx, self.1 + dy)
@@ -21,13 +85,65 @@ impl Pos {
     }
 }
 
+impl Add for Pos {
+    type Output = Pos;
+
+    fn add(self, rhs: Pos) -> Pos {
+        Pos(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl AddAssign for Pos {
+    fn add_assign(&mut self, rhs: Pos) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Pos {
+    type Output = Pos;
+
+    fn sub(self, rhs: Pos) -> Pos {
+        Pos(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl SubAssign for Pos {
+    fn sub_assign(&mut self, rhs: Pos) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<f32> for Pos {
+    type Output = Pos;
+
+    fn mul(self, rhs: f32) -> Pos {
+        Pos(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+impl Div<f32> for Pos {
+    type Output = Pos;
+
+    fn div(self, rhs: f32) -> Pos {
+        Pos(self.0 / rhs, self.1 / rhs)
+    }
+}
+
+impl Neg for Pos {
+    type Output = Pos;
+
+    fn neg(self) -> Pos {
+        Pos(-self.0, -self.1)
+    }
+}
+
 impl From<Pos> for (u16, u16) {
     fn from(value: Pos) -> Self {
         (value.0 as u16, value.1 as u16)
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Rect {
     pub pos: Pos,
     pub w: f32,
@@ -51,7 +167,7 @@ impl Rect {
     #[must_use]
     pub fn contains(&self, Pos(x, y): &Pos) -> bool {
         (self.pos.0..self.pos.0 + self.w).contains(x)
-            && (self.pos.0..self.pos.0 + self.h).contains(y)
+            && (self.pos.1..self.pos.1 + self.h).contains(y)
     }
 
     #[must_use]
@@ -61,4 +177,81 @@ impl Rect {
             && self.pos.1 < other.pos.1 + other.h
             && self.pos.1 + self.h > other.pos.1
     }
+
+    /// The smallest rect that covers both this and `other`.
+    #[must_use]
+    pub fn union(&self, other: &Rect) -> Rect {
+        let x = self.pos.0.min(other.pos.0);
+        let y = self.pos.1.min(other.pos.1);
+        let w = (self.pos.0 + self.w).max(other.pos.0 + other.w) - x;
+        let h = (self.pos.1 + self.h).max(other.pos.1 + other.h) - y;
+
+        Rect::new(&Pos(x, y), w, h)
+    }
+
+    /// Where a `w` by `h` rect may have its corner to overlap this one.
+    #[must_use]
+    pub fn expand(&self, w: f32, h: f32) -> Rect {
+        Rect::new(&Pos(self.pos.0 - w, self.pos.1 - h), self.w + w, self.h + h)
+    }
+
+    /// Where the segment from `from` to `to` first enters the rect, if it does.
+    /// A segment that starts inside the rect hits it right away, without a normal.
+    #[must_use]
+    pub fn raycast(&self, from: &Pos, to: &Pos) -> Option<Hit> {
+        let axes = [
+            (from.0, to.0 - from.0, self.pos.0, self.pos.0 + self.w, true),
+            (
+                from.1,
+                to.1 - from.1,
+                self.pos.1,
+                self.pos.1 + self.h,
+                false,
+            ),
+        ];
+        let mut t_near = 0f32;
+        let mut t_far = 1f32;
+        let mut normal = Pos(0., 0.);
+
+        for (start, delta, min, max, horizontal) in axes {
+            if delta == 0. {
+                if start <= min || start >= max {
+                    return None;
+                }
+                continue;
+            }
+
+            let (t1, t2) = ((min - start) / delta, (max - start) / delta);
+            let (enter, exit) = (t1.min(t2), t1.max(t2));
+            if enter > t_near {
+                t_near = enter;
+                let side = -delta.signum();
+                normal = if horizontal {
+                    Pos(side, 0.)
+                } else {
+                    Pos(0., side)
+                };
+            }
+            t_far = t_far.min(exit);
+
+            if t_near >= t_far {
+                return None;
+            }
+        }
+
+        Some(Hit {
+            t: t_near,
+            point: from.lerp(*to, t_near),
+            normal,
+        })
+    }
+}
+
+/// Where a moving point hit something, `t` of the way along its path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hit {
+    pub t: f32,
+    pub point: Pos,
+    /// Points out of the side that was hit.
+    pub normal: Pos,
 }
@@ -0,0 +1,91 @@
+use super::{Pos, Rect};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle {
+    pub center: Pos,
+    pub r: f32,
+}
+
+impl Circle {
+    #[must_use]
+    pub fn new(center: Pos, r: f32) -> Self {
+        Self { center, r }
+    }
+
+    #[must_use]
+    pub fn contains(&self, pos: &Pos) -> bool {
+        self.center.distance(pos) <= self.r
+    }
+
+    #[must_use]
+    pub fn intersects(&self, other: &Circle) -> bool {
+        self.center.distance(&other.center) <= self.r + other.r
+    }
+
+    #[must_use]
+    pub fn intersects_rect(&self, rect: &Rect) -> bool {
+        let closest = Pos(
+            self.center.0.clamp(rect.pos.0, rect.pos.0 + rect.w),
+            self.center.1.clamp(rect.pos.1, rect.pos.1 + rect.h),
+        );
+        self.contains(&closest)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+    pub from: Pos,
+    pub to: Pos,
+}
+
+impl Segment {
+    #[must_use]
+    pub fn new(from: Pos, to: Pos) -> Self {
+        Self { from, to }
+    }
+
+    #[must_use]
+    pub fn length(&self) -> f32 {
+        self.from.distance(&self.to)
+    }
+
+    /// The point on the segment closest to `pos`.
+    #[must_use]
+    pub fn closest_point(&self, pos: &Pos) -> Pos {
+        let delta = self.to - self.from;
+        let length = delta.dot(delta);
+        if length == 0. {
+            return self.from;
+        }
+        let t = ((*pos - self.from).dot(delta) / length).clamp(0., 1.);
+
+        self.from.lerp(self.to, t)
+    }
+
+    /// Where the two segments cross, if they do. Segments lying on top of each other do not cross.
+    #[must_use]
+    pub fn intersection(&self, other: &Segment) -> Option<Pos> {
+        let d1 = self.to - self.from;
+        let d2 = other.to - other.from;
+        let cross = d1.0 * d2.1 - d1.1 * d2.0;
+        if cross == 0. {
+            return None;
+        }
+
+        let between = other.from - self.from;
+        let t = (between.0 * d2.1 - between.1 * d2.0) / cross;
+        let u = (between.0 * d1.1 - between.1 * d1.0) / cross;
+
+        ((0. ..=1.).contains(&t) && (0. ..=1.).contains(&u)).then(|| self.from.lerp(self.to, t))
+    }
+
+    #[must_use]
+    pub fn intersects_rect(&self, rect: &Rect) -> bool {
+        rect.raycast(&self.from, &self.to).is_some()
+    }
+
+    #[must_use]
+    pub fn intersects_circle(&self, circle: &Circle) -> bool {
+        circle.contains(&self.closest_point(&circle.center))
+    }
+}
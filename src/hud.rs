@@ -0,0 +1,181 @@
+use crate::{
+    entity::{
+        pickups::PickupKind,
+        player::{Ability, Inventory, PlayerId, HOST_ID},
+    },
+    game::TICK,
+    geometry::Pos,
+    render::{Color, Frame},
+    state::State,
+    theme::Role,
+};
+
+/// Cells taken up by the health bar, not counting its brackets.
+const HP_BAR: usize = 10;
+
+/// Spacing between the parts of a bar.
+const GAP: &str = "  ";
+
+/// The bars drawn over the top and bottom rows of the canvas: where the
+/// player is, the score and combo, and how many enemies are left on top, and how the player is
+/// doing and what they carry at the bottom, with the tick and frame rate on
+/// the right.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hud {
+    /// The player whose health, weapon and abilities are shown.
+    pub player: PlayerId,
+    /// Frames drawn per second, if whoever draws them is counting.
+    pub fps: Option<u32>,
+}
+
+impl Default for Hud {
+    fn default() -> Self {
+        Self {
+            player: HOST_ID,
+            fps: None,
+        }
+    }
+}
+
+impl Hud {
+    pub fn render(&self, state: &State, frame: &mut Frame) {
+        let canvas = &state.canvas;
+        if canvas.h < 2. {
+            return;
+        }
+
+        let enemies = state.world.ais.len();
+        let mut top_right = vec![format!("{} pts", state.stats.score)];
+        let combo = state.stats.combo_at(state.world.frames);
+        if combo > 1 {
+            top_right.push(format!("x{combo} combo"));
+        }
+        top_right.push(format!(
+            "{enemies} {} left",
+            plural(enemies, "enemy", "enemies")
+        ));
+        let top_right = top_right.join(GAP);
+        bar(frame, 0., canvas.w, &self.location(state), &top_right);
+
+        let counter = match self.fps {
+            Some(fps) => format!("tick {} · {fps} fps", state.world.frames),
+            None => format!("tick {}", state.world.frames),
+        };
+        bar(
+            frame,
+            canvas.h - 1.,
+            canvas.w,
+            &self.status(state),
+            &counter,
+        );
+    }
+
+    /// The name of the level and of the room the player is in.
+    fn location(&self, state: &State) -> String {
+        let room = state
+            .world
+            .player(self.player)
+            .and_then(|id| state.world.hitbox(id))
+            .and_then(|hitbox| state.map.room_at(&hitbox.center()));
+
+        match room {
+            Some(room) if !state.map.name.is_empty() => {
+                format!("{} · {}", state.map.name, room.name)
+            }
+            Some(room) => room.name.clone(),
+            None => state.map.name.clone(),
+        }
+    }
+
+    /// Health, ammo, cooldowns and inventory of the player.
+    fn status(&self, state: &State) -> String {
+        let world = &state.world;
+        let Some(id) = world.player(self.player) else {
+            return String::new();
+        };
+        let mut parts = vec![];
+
+        if let Some(health) = world.healths.get(&id) {
+            let filled = (health.hp as usize * HP_BAR).div_ceil(health.max.max(1) as usize);
+            parts.push(format!(
+                "HP [{}{}] {}/{}",
+                "█".repeat(filled),
+                "·".repeat(HP_BAR - filled.min(HP_BAR)),
+                health.hp,
+                health.max
+            ));
+        }
+
+        if let Some(player) = world.players.get(&id) {
+            let weapon = &player.weapon;
+            parts.push(if weapon.reloading > 0 {
+                format!("{} reloading {}", weapon.name, seconds(weapon.reloading))
+            } else {
+                format!("{} {}/{}", weapon.name, weapon.ammo, weapon.magazine)
+            });
+
+            for ability in Ability::ALL {
+                parts.push(match player.cooldown(ability) {
+                    0 => format!("{} ready", ability.name()),
+                    ticks => format!("{} {}", ability.name(), seconds(ticks)),
+                });
+            }
+
+            parts.push(inventory(&player.inventory));
+        }
+
+        parts.join(GAP)
+    }
+}
+
+/// Clears the row at `y` and prints `left` and `right` on either end of it.
+/// When there is not enough room for both, `right` is left out, and `left`
+/// is cut off at the edge.
+fn bar(frame: &mut Frame, y: f32, w: f32, left: &str, right: &str) {
+    let w = w.max(0.) as usize;
+    let fg = Color::Role(Role::Hud);
+    frame.print_colored(Pos(0., y), &" ".repeat(w), fg);
+
+    let left: String = left.chars().take(w).collect();
+    let left_len = left.chars().count();
+    frame.print_colored(Pos(0., y), &left, fg);
+
+    let right_len = right.chars().count();
+    if left_len + GAP.len() + right_len <= w {
+        frame.print_colored(Pos((w - right_len) as f32, y), right, fg);
+    }
+}
+
+/// How many of each pickup the player carries, by the glyph of the pickup,
+/// and the weapons they carry but do not hold.
+fn inventory(inventory: &Inventory) -> String {
+    let counts = [
+        (PickupKind::Health, inventory.medkits),
+        (PickupKind::Ammo, inventory.magazines),
+        (PickupKind::Charge, inventory.charges),
+    ];
+    let mut parts: Vec<_> = counts
+        .iter()
+        .map(|(kind, count)| format!("{}{count}", kind.glyph()))
+        .collect();
+    parts.extend(
+        inventory
+            .weapons
+            .iter()
+            .map(|weapon| weapon.name.to_string()),
+    );
+
+    parts.join(" ")
+}
+
+fn seconds(ticks: u32) -> String {
+    format!("{:.1}s", (TICK * ticks).as_secs_f32())
+}
+
+fn plural(n: usize, one: &'static str, many: &'static str) -> &'static str {
+    if n == 1 {
+        one
+    } else {
+        many
+    }
+}
@@ -1,79 +1,171 @@
 use std::sync::mpsc::Sender;
 
-use crossterm::event::{Event, KeyCode, KeyEvent};
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 
-use crate::{event::GameEvent, geometry::Pos};
+use crate::{
+    editor::{Brush, EditorEvent},
+    entity::player::{Ability, PlayerId},
+    event::{GameEvent, PlayerEvent},
+    geometry::Pos,
+    scene::Scene,
+    settings::{Bind, Keys},
+    state::State,
+};
 
-pub fn handle_input(tx: Sender<crate::event::Event>) {
+/// Entries the message log scrolls by per page.
+const LOG_PAGE: i16 = 10;
+
+/// Reads terminal events on a thread of its own, and sends those that
+/// `translate` turns into something on `tx`.
+pub fn forward<T: Send + 'static>(
+    tx: Sender<T>,
+    translate: impl Fn(Event) -> Option<T> + Send + 'static,
+) {
     std::thread::spawn(move || {
         while let Ok(event) = crossterm::event::read() {
-            if let Some(action) = match event {
-                Event::FocusLost => Some(crate::event::Event::Game(GameEvent::Pause)),
-                Event::Resize(w, h) => Some(crate::event::Event::Game(GameEvent::Resize(w, h))),
-                Event::Mouse(evt) => match evt {
-                    crossterm::event::MouseEvent {
-                        kind:
-                            crossterm::event::MouseEventKind::Down(crossterm::event::MouseButton::Left),
-                        ..
-                    } => Some(crate::event::Event::Player(
-                        crate::event::PlayerEvent::Shoot,
-                    )),
-                    crossterm::event::MouseEvent {
-                        kind:
-                            crossterm::event::MouseEventKind::Drag(crossterm::event::MouseButton::Left)
-                            | crossterm::event::MouseEventKind::Moved,
-                        ..
-                    } => Some(crate::event::Event::Player(crate::event::PlayerEvent::Aim(
-                        Pos(evt.column.into(), evt.row.into()),
-                    ))),
-                    _ => None,
-                },
-                Event::Key(key) => match key {
-                    KeyEvent {
-                        code: KeyCode::Char('q') | KeyCode::Esc,
-                        ..
-                    } => Some(crate::event::Event::Game(GameEvent::Quit)),
-                    KeyEvent {
-                        code: KeyCode::Char('w'),
-                        ..
-                    } => Some(crate::event::Event::Player(
-                        crate::event::PlayerEvent::Move(0.0, -1.0),
-                    )),
-                    KeyEvent {
-                        code: KeyCode::Char('a'),
-                        ..
-                    } => Some(crate::event::Event::Player(
-                        crate::event::PlayerEvent::Move(-2.0, 0.0),
-                    )),
-                    KeyEvent {
-                        code: KeyCode::Char('s'),
-                        ..
-                    } => Some(crate::event::Event::Player(
-                        crate::event::PlayerEvent::Move(0.0, 1.0),
-                    )),
-                    KeyEvent {
-                        code: KeyCode::Char('d'),
-                        ..
-                    } => Some(crate::event::Event::Player(
-                        crate::event::PlayerEvent::Move(2.0, 0.0),
-                    )),
-                    KeyEvent {
-                        code: KeyCode::Char('p'),
-                        ..
-                    } => Some(crate::event::Event::Game(crate::event::GameEvent::Pause)),
-                    KeyEvent {
-                        code: KeyCode::Char('e'),
-                        ..
-                    } => Some(crate::event::Event::Player(
-                        crate::event::PlayerEvent::Ability(crate::entity::player::Ability::Blink),
-                    )),
-                    _ => None,
-                },
-                _ => None,
-            } {
+            if let Some(action) = translate(event) {
                 tx.send(action).ok();
             }
         }
         drop(tx);
     });
 }
+
+/// What the terminal event `event` does in `state`, played as player
+/// `player_id` with the keys of its settings. While the rebind screen is
+/// open, the next key pressed is the one it asked for.
+#[must_use]
+pub fn read(event: &Event, state: &State, player_id: PlayerId) -> Option<crate::event::Event> {
+    if let (Scene::Rebind(_), Some(key)) = (state.scenes.top(), bindable(event)) {
+        return Some(crate::event::Event::Game(GameEvent::Bind(key)));
+    }
+    translate(event, player_id, &state.settings.keys)
+}
+
+/// The key pressed in `event`, if it is one that actions can be put on.
+fn bindable(event: &Event) -> Option<char> {
+    match event {
+        Event::Key(KeyEvent {
+            code: KeyCode::Char(key),
+            modifiers,
+            ..
+        }) if !modifiers.contains(KeyModifiers::CONTROL) => Some(*key),
+        _ => None,
+    }
+}
+
+/// What the terminal event `event` does in the game, played as player
+/// `player_id` with `keys`.
+#[must_use]
+pub fn translate(event: &Event, player_id: PlayerId, keys: &Keys) -> Option<crate::event::Event> {
+    match event {
+        Event::FocusLost => Some(crate::event::Event::Game(GameEvent::Pause)),
+        Event::Resize(w, h) => Some(crate::event::Event::Game(GameEvent::Resize(*w, *h))),
+        Event::Mouse(evt) => match evt {
+            crossterm::event::MouseEvent {
+                kind: crossterm::event::MouseEventKind::Down(crossterm::event::MouseButton::Left),
+                ..
+            } => Some(crate::event::Event::Player(
+                player_id,
+                crate::event::PlayerEvent::Shoot,
+            )),
+            crossterm::event::MouseEvent {
+                kind:
+                    crossterm::event::MouseEventKind::Drag(crossterm::event::MouseButton::Left)
+                    | crossterm::event::MouseEventKind::Moved,
+                ..
+            } => Some(crate::event::Event::Player(
+                player_id,
+                crate::event::PlayerEvent::Aim(Pos(evt.column.into(), evt.row.into())),
+            )),
+            _ => None,
+        },
+        // Raw mode keeps ctrl-c from being a signal, so it is read as a key.
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('c'),
+            modifiers,
+            ..
+        }) if modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(crate::event::Event::Game(GameEvent::Quit))
+        }
+        Event::Key(KeyEvent { code, .. }) => key(*code, player_id, keys),
+        _ => None,
+    }
+}
+
+/// What the terminal event `event` does in the level editor.
+#[must_use]
+pub fn edit(event: &Event) -> Option<EditorEvent> {
+    match event {
+        Event::Mouse(MouseEvent {
+            kind, column, row, ..
+        }) => {
+            let pos = Pos((*column).into(), (*row).into());
+            match kind {
+                MouseEventKind::Down(MouseButton::Left) => Some(EditorEvent::Paint(pos)),
+                MouseEventKind::Down(MouseButton::Right) => Some(EditorEvent::Erase(pos)),
+                MouseEventKind::Drag(_) => Some(EditorEvent::Drag(pos)),
+                MouseEventKind::Moved => Some(EditorEvent::Hover(pos)),
+                _ => None,
+            }
+        }
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('c'),
+            modifiers,
+            ..
+        }) if modifiers.contains(KeyModifiers::CONTROL) => Some(EditorEvent::Quit),
+        Event::Key(KeyEvent { code, .. }) => match code {
+            KeyCode::Char('q') | KeyCode::Esc => Some(EditorEvent::Quit),
+            KeyCode::Char('u') => Some(EditorEvent::Undo),
+            KeyCode::Char('r') => Some(EditorEvent::Redo),
+            KeyCode::Char('s') => Some(EditorEvent::Save),
+            KeyCode::Char('t') => Some(EditorEvent::TestPlay),
+            KeyCode::Char(' ') | KeyCode::Enter => Some(EditorEvent::PaintCursor),
+            KeyCode::Up => Some(EditorEvent::Move(0, -1)),
+            KeyCode::Down => Some(EditorEvent::Move(0, 1)),
+            KeyCode::Left => Some(EditorEvent::Move(-1, 0)),
+            KeyCode::Right => Some(EditorEvent::Move(1, 0)),
+            KeyCode::Char(c) => {
+                let i = (c.to_digit(10)? + 9) % 10;
+                Brush::ALL.get(i as usize).copied().map(EditorEvent::Brush)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// What pressing the key `code` does, with the letters bound by `keys`.
+fn key(code: KeyCode, player_id: PlayerId, keys: &Keys) -> Option<crate::event::Event> {
+    let game = |event| Some(crate::event::Event::Game(event));
+    let player = |event| Some(crate::event::Event::Player(player_id, event));
+
+    let bind = match code {
+        KeyCode::Char(key) => keys.bind_of(key)?,
+        KeyCode::Up => Bind::Up,
+        KeyCode::Left => Bind::Left,
+        KeyCode::Down => Bind::Down,
+        KeyCode::Right => Bind::Right,
+        KeyCode::Esc => return game(GameEvent::Back),
+        KeyCode::Enter => return game(GameEvent::Select),
+        KeyCode::PageUp => return game(GameEvent::ScrollLog(LOG_PAGE)),
+        KeyCode::PageDown => return game(GameEvent::ScrollLog(-LOG_PAGE)),
+        _ => return None,
+    };
+
+    match bind {
+        Bind::Up => player(PlayerEvent::Move(0.0, -1.0)),
+        Bind::Left => player(PlayerEvent::Move(-2.0, 0.0)),
+        Bind::Down => player(PlayerEvent::Move(0.0, 1.0)),
+        Bind::Right => player(PlayerEvent::Move(2.0, 0.0)),
+        Bind::Blink => player(PlayerEvent::Ability(Ability::Blink)),
+        Bind::Grenade => player(PlayerEvent::Ability(Ability::Grenade)),
+        Bind::Medkit => player(PlayerEvent::UseMedkit),
+        Bind::SwitchWeapon => player(PlayerEvent::SwitchWeapon),
+        Bind::Log => game(GameEvent::ToggleLog),
+        Bind::Pause => game(GameEvent::Back),
+        Bind::Quit => game(GameEvent::Quit),
+    }
+}
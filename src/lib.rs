@@ -1,9 +1,29 @@
-#![feature(trait_upcasting)]
+pub mod bot;
+pub mod cli;
+pub mod editor;
 pub mod effect;
 pub mod entity;
 pub mod event;
+pub mod game;
 pub mod geometry;
+pub mod hud;
+#[cfg(feature = "tui")]
 pub mod input;
+pub mod log;
 pub mod map;
+pub mod net;
 pub mod render;
+pub mod replay;
+pub mod rng;
+pub mod scene;
+pub mod score;
+pub mod scoreboard;
+pub mod settings;
 pub mod state;
+pub mod systems;
+pub mod theme;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod world;
+
+pub use game::Game;
@@ -0,0 +1,159 @@
+use std::{collections::VecDeque, fmt::Display};
+
+use crate::{
+    game::TICK,
+    geometry::{Pos, Rect},
+    render::{Color, Frame},
+    theme::Role,
+};
+
+/// Entries kept before the oldest ones are dropped.
+pub const CAPACITY: usize = 500;
+
+/// Lines shown in the tail at the bottom of the screen.
+const TAIL: usize = 3;
+
+/// Frames an entry stays in the tail, about six seconds.
+const TAIL_TICKS: u32 = 1500;
+
+/// What a message is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Combat,
+    Pickup,
+    System,
+    /// Only shown in the full log.
+    Debug,
+}
+
+impl Display for Category {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Category::Combat => "combat",
+            Category::Pickup => "pickup",
+            Category::System => "system",
+            Category::Debug => "debug",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// The frame the message was posted on.
+    pub tick: u32,
+    pub category: Category,
+    pub text: String,
+}
+
+impl Display for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let secs = (TICK * self.tick).as_secs_f32();
+        write!(f, "[{secs:>6.1}s] {:<6} {}", self.category, self.text)
+    }
+}
+
+/// The messages posted during a game, oldest first, keeping at most `CAPACITY` of them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MessageLog {
+    entries: VecDeque<Entry>,
+    /// Whether the full log is shown instead of the tail.
+    pub open: bool,
+    /// How many entries back from the newest the full log is scrolled.
+    pub scroll: usize,
+}
+
+impl MessageLog {
+    pub fn push(&mut self, tick: u32, category: Category, text: impl Display) {
+        if self.entries.len() == CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(Entry {
+            tick,
+            category,
+            text: text.to_string(),
+        });
+    }
+
+    #[must_use]
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &Entry> {
+        self.entries.iter()
+    }
+
+    #[must_use]
+    pub fn latest(&self) -> Option<&Entry> {
+        self.entries.back()
+    }
+
+    /// The last few entries that are not debug messages and were posted
+    /// recently, as of frame `now`, oldest first.
+    pub fn tail(&self, now: u32) -> impl Iterator<Item = &Entry> {
+        let mut tail: Vec<_> = self
+            .entries
+            .iter()
+            .rev()
+            .filter(|entry| entry.category != Category::Debug)
+            .take_while(|entry| now.saturating_sub(entry.tick) < TAIL_TICKS)
+            .take(TAIL)
+            .collect();
+        tail.reverse();
+        tail.into_iter()
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.scroll = 0;
+    }
+
+    /// Scrolls the full log `by` entries back in time, or forward if negative.
+    pub fn scroll(&mut self, by: i16) {
+        let max = self.entries.len().saturating_sub(1);
+        self.scroll = self.scroll.saturating_add_signed(isize::from(by)).min(max);
+    }
+
+    /// Draws the tail just above the bottom row of `canvas`, or the full log over all of it when open.
+    pub fn render(&self, now: u32, canvas: &Rect, frame: &mut Frame) {
+        let fg = Color::Role(Role::Hud);
+
+        if !self.open {
+            let tail: Vec<_> = self.tail(now).collect();
+            let bottom = canvas.h - 1.;
+            for (i, entry) in tail.iter().enumerate() {
+                let y = bottom - (tail.len() - i) as f32;
+                frame.print_colored(Pos(0., y), &entry.text, fg);
+            }
+            return;
+        }
+
+        let rows = canvas.h.max(0.) as usize;
+        let blank = " ".repeat(canvas.w.max(0.) as usize);
+        for y in 0..rows {
+            frame.print_colored(Pos(0., y as f32), &blank, fg);
+        }
+        frame.print_colored(
+            Pos(0., 0.),
+            &format!(
+                "Message log, {} entries (l to close, PgUp/PgDn to scroll)",
+                self.entries.len()
+            ),
+            fg,
+        );
+
+        let shown = rows.saturating_sub(1);
+        let visible: Vec<_> = self
+            .entries
+            .iter()
+            .rev()
+            .skip(self.scroll)
+            .take(shown)
+            .collect();
+        for (i, entry) in visible.iter().rev().enumerate() {
+            let color = match entry.category {
+                Category::Debug => Color::Role(Role::Wall),
+                _ => fg,
+            };
+            frame.print_colored(Pos(0., (i + 1) as f32), &entry.to_string(), color);
+        }
+    }
+}
@@ -1,76 +1,656 @@
-use std::{error::Error, thread, time::Duration};
+use std::{
+    error::Error,
+    fs, io,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::mpsc::Receiver,
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crossterm::event::Event as TerminalEvent;
 
 use breach::{
-    entity::{effects::blink::BlinkEffect, projectiles::Bullet, Collidable},
-    event::GameEvent,
-    geometry::{Pos, Rect},
-    state::State,
+    bot::{self, runner},
+    cli::{Cli, Command, Options, USAGE},
+    editor::{self, Editor, EditorEvent},
+    entity::{player::HOST_ID, EntityKind},
+    event::{Event, GameEvent},
+    game::TICK,
+    map::{
+        levels,
+        validate::{self, Problem},
+        Map,
+    },
+    net::{self, client::Client},
+    render::Frame,
+    replay::Replay,
+    scene::Scene,
+    scoreboard::{self, Run, Scoreboard},
+    settings::Keys,
+    theme::{ColorDepth, Palette},
+    tui::{Session, Terminal},
+    Game,
 };
-use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
 
-fn main() -> Result<(), Box<dyn Error>> {
+/// The width and height of the level `breach edit` makes when there is none yet.
+const NEW_LEVEL_SIZE: (usize, usize) = (60, 20);
+
+/// Exit code for a command line that could not be read.
+const USAGE_ERROR: u8 = 2;
+
+fn main() -> ExitCode {
+    let cli = match Cli::parse(std::env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(err) => {
+            eprintln!("breach: {err}\nRun breach --help to see the commands and options.");
+            return ExitCode::from(USAGE_ERROR);
+        }
+    };
+    let options = match with_config(cli.options) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("breach: {err}");
+            return ExitCode::from(USAGE_ERROR);
+        }
+    };
+
+    match run_command(cli.command, &options) {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("breach: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// The options from the command line, with the rest read from the config file if one is given.
+fn with_config(options: Options) -> Result<Options, Box<dyn Error>> {
+    let Some(path) = &options.config else {
+        return Ok(options);
+    };
+    let text = fs::read_to_string(path)
+        .map_err(|err| format!("cannot read config {}: {err}", path.display()))?;
+    let config = Options::from_config(&text)?;
+
+    Ok(options.or(config))
+}
+
+fn run_command(command: Command, options: &Options) -> Result<ExitCode, Box<dyn Error>> {
+    match command {
+        Command::Help => println!("{USAGE}"),
+        Command::Version => println!("breach {}", env!("CARGO_PKG_VERSION")),
+        Command::ListLevels => list_levels()?,
+        Command::Scores { history } => print_scores(options, history)?,
+        Command::Validate(paths) => return validate_levels(&paths),
+        Command::Bot { name, games } => run_bot(&name, games)?,
+        Command::Replay(path) => watch(&path, options)?,
+        Command::Edit(path) => edit(&path, options)?,
+        command => play(&command, options)?,
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn list_levels() -> Result<(), Box<dyn Error>> {
+    for (name, text) in levels::LEVELS {
+        let map = Map::try_from(text).map_err(|err| format!("{name}: {err}"))?;
+        println!(
+            "{name}\t{}\t{}x{}\t{} enemies",
+            map.name,
+            map.bounds.w,
+            map.bounds.h,
+            map.spawns
+                .iter()
+                .filter(|spawn| spawn.kind == EntityKind::Goblo)
+                .count()
+        );
+    }
+
+    Ok(())
+}
+
+/// Prints the problems with each level, and fails if any of them has an error.
+fn validate_levels(paths: &[PathBuf]) -> Result<ExitCode, Box<dyn Error>> {
+    let levels = if paths.is_empty() {
+        levels::LEVELS
+            .iter()
+            .map(|(name, text)| ((*name).to_string(), (*text).to_string()))
+            .collect()
+    } else {
+        paths
+            .iter()
+            .map(|path| Ok((path.display().to_string(), read_level(path)?)))
+            .collect::<Result<Vec<_>, Box<dyn Error>>>()?
+    };
+
+    let mut failed = false;
+    for (name, text) in levels {
+        let problems = validate::validate(&text);
+        for problem in &problems {
+            let severity = if problem.is_error() {
+                "error"
+            } else {
+                "warning"
+            };
+            println!("{name}: {severity}: {problem}");
+        }
+        failed |= problems.iter().any(Problem::is_error);
+        if problems.is_empty() {
+            println!("{name}: ok");
+        }
+    }
+
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+fn read_level(path: &Path) -> Result<String, Box<dyn Error>> {
+    fs::read_to_string(path)
+        .map_err(|err| format!("cannot read level {}: {err}", path.display()).into())
+}
+
+/// The palette of the theme in the options, or of the environment.
+fn palette(options: &Options) -> Palette {
+    match options.theme {
+        Some(theme) => Palette::new(theme, ColorDepth::detect()),
+        None => Palette::detect(),
+    }
+}
+
+/// The settings of `game` from the options, and its theme from `terminal`.
+fn configure(game: &mut Game, terminal: &Terminal, options: &Options) {
+    let settings = game.settings_mut();
+    settings.theme = terminal.palette().theme;
+    settings.screen_shake = options.screen_shake.unwrap_or(settings.screen_shake);
+    settings.fov_shading = options.fov_shading.unwrap_or(settings.fov_shading);
+}
+
+/// Starts the threads that pass on terminal events as they are, to be read
+/// with the keys of whatever reads them. `None` is a signal to quit.
+fn listen() -> Result<Receiver<Option<TerminalEvent>>, Box<dyn Error>> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    breach::tui::quit_on_signals(tx.clone(), None)?;
+    breach::input::forward(tx, |event| Some(Some(event)));
+
+    Ok(rx)
+}
+
+fn play(command: &Command, options: &Options) -> Result<(), Box<dyn Error>> {
     let (width, height) = crossterm::terminal::size()?;
-    let mut state = State::new(Rect::new(
-        &Pos(0.0, 0.0),
-        f32::from(width),
-        f32::from(height),
-    ));
-    let (tx, rx) = std::sync::mpsc::channel::<breach::event::Event>();
 
-    breach::input::handle_input(tx.clone());
+    let client = match command {
+        Command::Host(addr) => Some(net::host(addr)?),
+        Command::Join(addr) => Some(net::join(addr)?),
+        Command::Connect(addr) => Some(Client::connect(addr)?),
+        _ => None,
+    };
+    let id = client.as_ref().map_or(HOST_ID, |client| client.id);
+
+    let (level, seed, record) = match command {
+        Command::Play {
+            level: Some(path),
+            seed,
+            record,
+        } => {
+            let text = read_level(path)?;
+            let problems = validate::validate(&text);
+            if let Some(problem) = problems.iter().find(|problem| problem.is_error()) {
+                return Err(format!("{}: {problem}", path.display()).into());
+            }
+            (text, seed.unwrap_or(0), record.as_deref())
+        }
+        Command::Play { seed, record, .. } => (
+            levels::LEVELS[0].1.to_string(),
+            seed.unwrap_or(0),
+            record.as_deref(),
+        ),
+        _ => (levels::LEVELS[0].1.to_string(), 0, None),
+    };
+
+    let (scoreboard, scores) = match load_scoreboard(options) {
+        Ok((scoreboard, path)) if !scoreboard.is_writable() => {
+            eprintln!(
+                "breach: {} is from a newer version, runs will not be saved",
+                path.display()
+            );
+            (scoreboard, None)
+        }
+        Ok((scoreboard, path)) => (scoreboard, Some(path)),
+        Err(err) => {
+            eprintln!("breach: {err}, runs will not be saved");
+            (Scoreboard::default(), None)
+        }
+    };
+
+    let input = listen()?;
+    let mut terminal = Terminal::with_palette(palette(options));
+    let session = Session::start(options.mouse.unwrap_or(true))?;
+
+    let frame = Frame::new(width, height);
+    let result = if let Some(client) = client {
+        run_client(client, &mut terminal, &input, frame)
+    } else {
+        let mut game = Game::new(&level, seed)?;
+        if *command == Command::Menu {
+            game.scenes_mut().reset(Scene::MainMenu);
+        }
+        game.hud_mut().player = id;
+        if options.fps == Some(true) {
+            game.hud_mut().fps = Some(0);
+        }
+        configure(&mut game, &terminal, options);
+        *game.scoreboard_mut() = scoreboard;
+
+        let mut records = Records {
+            replay: record.map(|path| (path, Replay::new(&level, seed))),
+            scores,
+            player: player_name(options),
+            seed,
+            error: None,
+        };
+        let result = run(&mut game, &mut terminal, &mut records, &input, frame);
+        if let Some((path, replay)) = records.replay {
+            fs::write(path, replay.to_string())
+                .map_err(|err| format!("cannot save replay {}: {err}", path.display()))?;
+        }
+        if let Some(err) = records.error {
+            return Err(err.into());
+        }
+        result
+    };
+
+    drop(session);
+    result
+}
+
+/// What is kept of a game: a replay of it, if one was asked for, and each
+/// run of a level, on the scoreboard.
+struct Records<'a> {
+    replay: Option<(&'a Path, Replay)>,
+    /// Where the scoreboard is saved, unless it cannot be.
+    scores: Option<PathBuf>,
+    player: String,
+    seed: u64,
+    /// Why the scoreboard could not be saved, once it could not.
+    error: Option<String>,
+}
+
+impl Records<'_> {
+    /// Puts the run of `game` that just ended on its scoreboard, and saves it.
+    fn finish(&mut self, game: &mut Game) {
+        let state = game.state();
+        let level = if state.map.name.is_empty() {
+            "Untitled".to_string()
+        } else {
+            state.map.name.clone()
+        };
+        let run = Run {
+            date: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs()),
+            level,
+            seed: self.seed,
+            cleared: state.stats.cleared_at.is_some(),
+            score: state.stats.score,
+            time: state.world.frames,
+            player: self.player.clone(),
+            replay: self
+                .replay
+                .as_ref()
+                .map(|(path, _)| fs::canonicalize(path).unwrap_or(path.to_path_buf())),
+        };
+        game.scoreboard_mut().add(run);
+
+        let Some(path) = &self.scores else {
+            return;
+        };
+        if let Err(err) = save_scoreboard(path, &game.state().scoreboard) {
+            self.error = Some(format!("cannot save scoreboard {}: {err}", path.display()));
+            self.scores = None;
+        }
+    }
+}
+
+/// The scoreboard in the options or the data directory, and where it is
+/// kept. There is an empty one until a run has been saved.
+fn load_scoreboard(options: &Options) -> Result<(Scoreboard, PathBuf), Box<dyn Error>> {
+    let path = options
+        .scores
+        .clone()
+        .or_else(scoreboard::default_path)
+        .ok_or("cannot find a data directory for the scoreboard, try --scores")?;
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Ok((Scoreboard::default(), path))
+        }
+        Err(err) => return Err(format!("cannot read scoreboard {}: {err}", path.display()).into()),
+    };
+    let scoreboard = text
+        .parse()
+        .map_err(|err| format!("{}: {err}", path.display()))?;
+
+    Ok((scoreboard, path))
+}
+
+/// Writes the scoreboard next to `path` first, so that it is never left half saved.
+fn save_scoreboard(path: &Path, scoreboard: &Scoreboard) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let saving = path.with_extension("saving");
+    fs::write(&saving, scoreboard.to_string())?;
+    fs::rename(saving, path)
+}
 
-    crossterm::terminal::enable_raw_mode()?;
-    crossterm::execute!(std::io::stdout(), EnableMouseCapture)?;
+/// The name runs go on the scoreboard with: the one in the options, or
+/// else that of the user.
+fn player_name(options: &Options) -> String {
+    options
+        .name
+        .clone()
+        .or_else(|| std::env::var("USER").ok())
+        .or_else(|| std::env::var("USERNAME").ok())
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| "player".to_string())
+}
+
+/// Prints the best runs of each level, or every run, the latest first.
+fn print_scores(options: &Options, history: bool) -> Result<(), Box<dyn Error>> {
+    let (scoreboard, _) = load_scoreboard(options)?;
+    if !history {
+        let lines = scoreboard.leaderboard();
+        if lines.is_empty() {
+            println!("No level has been cleared yet");
+        }
+        for line in lines {
+            println!("{line}");
+        }
+        return Ok(());
+    }
+
+    for run in scoreboard.runs.iter().rev() {
+        println!(
+            "{}\t{}\tseed {}\t{}\t{}\t{:.1}s\t{}\t{}",
+            scoreboard::date(run.date),
+            run.level,
+            run.seed,
+            if run.cleared { "cleared" } else { "lost" },
+            run.score,
+            run.seconds(),
+            run.player,
+            run.replay
+                .as_ref()
+                .map_or("-".to_string(), |path| path.display().to_string()),
+        );
+    }
+    Ok(())
+}
+
+/// Runs the game until a player quits, counting frames if the HUD shows
+/// the frame rate, and keeping the records of it.
+fn run(
+    game: &mut Game,
+    terminal: &mut Terminal,
+    records: &mut Records,
+    input: &Receiver<Option<TerminalEvent>>,
+    mut frame: Frame,
+) -> Result<(), Box<dyn Error>> {
+    let mut inputs = vec![Event::Game(GameEvent::Resize(frame.w, frame.h))];
+    let mut second = Instant::now();
+    let mut frames = 0;
 
     loop {
-        if let Ok(action) = rx.try_recv() {
+        while let Ok(event) = input.try_recv() {
+            let Some(event) = event else {
+                return Ok(());
+            };
+            let state = game.state();
+            let Some(action) = breach::input::read(&event, state, state.hud.player) else {
+                continue;
+            };
             match action {
-                breach::event::Event::Player(player_event) => match player_event {
-                    breach::event::PlayerEvent::Move(x, y) => {
-                        let hbox = state.player.borrow().hitbox();
-                        let old = state.player.borrow().pos;
-                        let new = Pos(
-                            (old.0 + x).clamp(0.0, state.canvas.w - hbox.w),
-                            (old.1 + y).clamp(0.0, state.canvas.h - hbox.h),
-                        );
-                        state.player.borrow_mut().pos = new;
-                    }
-                    breach::event::PlayerEvent::Aim(pos) => {
-                        let player_pos = state.player.borrow().pos;
-                        state.player.borrow_mut().aim = player_pos.angle(&pos);
-                    }
-                    breach::event::PlayerEvent::Shoot => {
-                        let player = state.player.borrow();
-                        let bullet = Bullet::new(player.hitbox().center(), player.aim);
-                        state.spawn_projectile(bullet);
-                    }
-                    breach::event::PlayerEvent::Ability(_) => {
-                        let aim = state.player.borrow().aim;
-                        let c = state.player.borrow().hitbox().center();
-                        let x = c.0 + aim.cos() * 10.0;
-                        let y = c.1 + aim.sin() * 5.0;
-                        state.spawn_effect(BlinkEffect::new(c, Pos(x, y)));
-                        state.player.borrow_mut().pos =
-                            Pos(x.clamp(0., state.canvas.w), y.clamp(0., state.canvas.h));
+                Event::Game(GameEvent::Quit) => return Ok(()),
+                Event::Game(GameEvent::Resize(w, h)) => {
+                    frame = Frame::new(w, h);
+                    inputs.push(action);
+                }
+                action => inputs.push(action),
+            }
+        }
+        if let Some((_, replay)) = records.replay.as_mut() {
+            replay.record(game.tick(), &inputs);
+        }
+        let ended = |game: &Game| {
+            matches!(
+                game.state().scenes.top(),
+                Scene::Cleared(_) | Scene::GameOver
+            )
+        };
+        let was_over = ended(game);
+        game.step(inputs.drain(..));
+        if ended(game) && !was_over {
+            records.finish(game);
+        }
+        if game.state().quit {
+            return Ok(());
+        }
+
+        frames += 1;
+        if game.state().hud.fps.is_some() && second.elapsed() >= Duration::from_secs(1) {
+            game.hud_mut().fps = Some(frames);
+            frames = 0;
+            second = Instant::now();
+        }
+        let theme = game.state().settings.theme;
+        if terminal.palette().theme != theme {
+            terminal.set_palette(Palette::new(theme, terminal.palette().depth));
+        }
+        game.render(&mut frame);
+        terminal.draw(&frame)?;
+        thread::sleep(TICK);
+    }
+}
+
+fn run_client(
+    mut client: Client,
+    terminal: &mut Terminal,
+    input: &Receiver<Option<TerminalEvent>>,
+    mut frame: Frame,
+) -> Result<(), Box<dyn Error>> {
+    let keys = Keys::default();
+
+    loop {
+        while let Ok(event) = input.try_recv() {
+            let Some(event) = event else {
+                return Ok(());
+            };
+            let Some(action) = breach::input::translate(&event, client.id, &keys) else {
+                continue;
+            };
+            match action {
+                Event::Game(GameEvent::Quit | GameEvent::Back) => return Ok(()),
+                Event::Game(GameEvent::Resize(w, h)) => frame = Frame::new(w, h),
+                Event::Player(_, event) => client.send(event)?,
+                Event::Game(_) => {}
+            }
+        }
+        client.poll()?;
+        client.render(&mut frame);
+        terminal.draw(&frame)?;
+        thread::sleep(TICK);
+    }
+}
+
+/// Plays a recorded game back at the speed it was played, until it ends or the viewer quits.
+fn watch(path: &Path, options: &Options) -> Result<(), Box<dyn Error>> {
+    let text = fs::read_to_string(path)
+        .map_err(|err| format!("cannot read replay {}: {err}", path.display()))?;
+    let replay: Replay = text
+        .parse()
+        .map_err(|err| format!("{}: {err}", path.display()))?;
+    let mut game = replay
+        .game()
+        .map_err(|err| format!("{}: {err}", path.display()))?;
+
+    let (width, height) = crossterm::terminal::size()?;
+    let mut frame = Frame::new(width, height);
+    let input = listen()?;
+    let mut terminal = Terminal::with_palette(palette(options));
+    configure(&mut game, &terminal, options);
+    let session = Session::start(false)?;
+
+    let result = (|| -> Result<(), Box<dyn Error>> {
+        while game.tick() < replay.steps {
+            for event in input.try_iter() {
+                let Some(event) = event else {
+                    return Ok(());
+                };
+                match breach::input::translate(&event, HOST_ID, &Keys::default()) {
+                    Some(Event::Game(GameEvent::Quit | GameEvent::Back)) => return Ok(()),
+                    Some(Event::Game(GameEvent::Resize(w, h))) => frame = Frame::new(w, h),
+                    _ => {}
+                }
+            }
+            game.step(replay.inputs(game.tick()));
+            game.render(&mut frame);
+            terminal.draw(&frame)?;
+            thread::sleep(TICK);
+        }
+        Ok(())
+    })();
+
+    drop(session);
+    result?;
+    let outcome = if game.is_cleared() {
+        "cleared"
+    } else if game.is_lost() {
+        "lost"
+    } else {
+        "unfinished"
+    };
+    println!(
+        "replayed {} of {} steps: {outcome}",
+        game.tick(),
+        replay.steps
+    );
+
+    Ok(())
+}
+
+/// Edits the level at `path` until the user quits, test-playing it when asked.
+fn edit(path: &Path, options: &Options) -> Result<(), Box<dyn Error>> {
+    let mut editor = if path.exists() {
+        Editor::new(&read_level(path)?)
+    } else {
+        let mut editor = Editor::new(&editor::blank(NEW_LEVEL_SIZE.0, NEW_LEVEL_SIZE.1));
+        editor.status = format!("New level {}", path.display());
+        editor
+    };
+
+    // Terminal events are read as editing or as playing, depending on whether
+    // the level is being test-played.
+    let rx = listen()?;
+
+    let (width, height) = crossterm::terminal::size()?;
+    let mut terminal = Terminal::with_palette(palette(options));
+    let session = Session::start(options.mouse.unwrap_or(true))?;
+    let result = run_editor(
+        &mut editor,
+        path,
+        &mut terminal,
+        &rx,
+        Frame::new(width, height),
+        options,
+    );
+
+    drop(session);
+    result
+}
+
+fn run_editor(
+    editor: &mut Editor,
+    path: &Path,
+    terminal: &mut Terminal,
+    input: &Receiver<Option<TerminalEvent>>,
+    mut frame: Frame,
+    options: &Options,
+) -> Result<(), Box<dyn Error>> {
+    let mut test: Option<Game> = None;
+    let mut inputs = vec![];
+
+    loop {
+        for event in input.try_iter() {
+            let Some(event) = event else {
+                return Ok(());
+            };
+            if let TerminalEvent::Resize(w, h) = event {
+                frame = Frame::new(w, h);
+            }
+
+            if let Some(game) = &test {
+                match breach::input::read(&event, game.state(), HOST_ID) {
+                    Some(Event::Game(GameEvent::Quit | GameEvent::Back)) => test = None,
+                    Some(event) => inputs.push(event),
+                    None => {}
+                }
+                continue;
+            }
+            match breach::input::edit(&event) {
+                Some(EditorEvent::Save) => {
+                    if let Err(err) = editor.save(|text| fs::write(path, text)) {
+                        editor.status = format!("Cannot save, {err}");
                     }
-                },
-                breach::event::Event::Game(game_event) => match game_event {
-                    GameEvent::Pause => state.pause(),
-                    GameEvent::Play => state.play(),
-                    GameEvent::Resize(w, h) => {
-                        state.canvas = Rect::new(&Pos(0., 0.), f32::from(w), f32::from(h));
+                }
+                Some(EditorEvent::TestPlay) => match Game::new(&editor.test_level(), 0) {
+                    Ok(mut game) => {
+                        game.hud_mut().player = HOST_ID;
+                        configure(&mut game, terminal, options);
+                        inputs = vec![Event::Game(GameEvent::Resize(frame.w, frame.h))];
+                        test = Some(game);
                     }
-                    GameEvent::Quit => break,
+                    Err(err) => editor.status = format!("Cannot test, {err}"),
                 },
+                Some(event) => editor.handle(event),
+                None => {}
+            }
+        }
+
+        if let Some(game) = &mut test {
+            game.step(inputs.drain(..));
+            if game.state().quit {
+                test = None;
+            } else {
+                game.render(&mut frame);
+            }
+        }
+        if test.is_none() {
+            if editor.quit {
+                return Ok(());
             }
+            editor.render(&mut frame);
         }
-        state.frame();
-        state.render()?;
-        thread::sleep(Duration::from_millis(4));
+        terminal.draw(&frame)?;
+        thread::sleep(TICK);
+    }
+}
+
+fn run_bot(name: &str, games: u32) -> Result<(), Box<dyn Error>> {
+    if bot::by_name(name).is_none() {
+        return Err(format!("unknown bot {name}, try one of {}", bot::BOTS.join(", ")).into());
     }
 
-    crossterm::execute!(std::io::stdout(), DisableMouseCapture)?;
-    crossterm::terminal::disable_raw_mode()?;
+    for level in levels::LEVELS {
+        let make_bot = || bot::by_name(name).expect("bot exists");
+        println!(
+            "{}",
+            runner::run(make_bot, level, games, runner::MAX_TICKS)?
+        );
+    }
 
     Ok(())
 }
@@ -0,0 +1,11 @@
+/// Levels that ship with the game, by name.
+pub const LEVELS: [(&str, &str); 1] = [("level_1", include_str!("level_1.txt"))];
+
+/// The text of a built in level.
+#[must_use]
+pub fn get(name: &str) -> Option<&'static str> {
+    LEVELS
+        .iter()
+        .find(|(level, _)| *level == name)
+        .map(|(_, text)| *text)
+}
@@ -1,16 +1,74 @@
-use crossterm::{cursor::MoveTo, style::Print};
-
 use crate::{
+    entity::{
+        pickups::PickupKind,
+        status::{Status, StatusKind},
+        EntityKind,
+    },
     geometry::{Pos, Rect},
-    render::Render,
+    render::{Color, Frame, Render},
+    theme::Role,
 };
 use std::fmt::Display;
 
-#[derive(Debug)]
+pub mod levels;
+pub mod validate;
+
+/// A level, read from text with one character per cell and as wide as its
+/// widest row. It may start with lines naming the level and the rooms in
+/// it, by their top left corner and size in cells:
+///
+/// ```text
+/// :name Outpost
+/// :room Entrance 0 6 22 30
+/// ```
+#[derive(Debug, Clone)]
 pub struct Map {
+    pub name: String,
+    pub rooms: Vec<Room>,
+    pub bounds: Rect,
     pub start_pos: Pos,
+    pub spawns: Vec<Spawn>,
     pub level: Vec<Tile>,
     pub seen: Vec<Tile>,
+    /// The kind of tile in every cell, row by row.
+    cells: Vec<Option<TileType>>,
+}
+
+/// A named part of the level.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Room {
+    pub name: String,
+    pub rect: Rect,
+}
+
+impl Room {
+    /// A room from the words after `:room`, or `None` if they are not a name and four numbers.
+    fn parse(words: &str) -> Option<Self> {
+        let mut words: Vec<&str> = words.split_whitespace().collect();
+        let numbers = words.split_off(words.len().checked_sub(4)?);
+        let [x, y, w, h] = numbers
+            .iter()
+            .map(|n| n.parse::<f32>().ok())
+            .collect::<Option<Vec<_>>>()?[..]
+        else {
+            return None;
+        };
+        if words.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            name: words.join(" "),
+            rect: Rect::new(&Pos(x, y), w, h),
+        })
+    }
+}
+
+/// An enemy or a pickup placed in the level.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spawn {
+    pub kind: EntityKind,
+    pub pos: Pos,
 }
 
 #[derive(Debug, Clone)]
@@ -22,12 +80,30 @@ pub struct Tile {
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TileType {
     Wall,
+    /// Sets whatever walks through it on fire.
+    Fire,
+    /// Poisons whatever walks through it.
+    Sludge,
+}
+
+impl TileType {
+    /// What standing on the tile afflicts an entity with.
+    #[must_use]
+    pub fn status(self) -> Option<Status> {
+        match self {
+            TileType::Wall => None,
+            TileType::Fire => Some(Status::new(StatusKind::Burning, 60)),
+            TileType::Sludge => Some(Status::new(StatusKind::Poison, 200)),
+        }
+    }
 }
 
 impl Display for Tile {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let c = match self.kind {
             TileType::Wall => '#',
+            TileType::Fire => '^',
+            TileType::Sludge => '~',
         };
 
         write!(f, "{c}")
@@ -40,53 +116,166 @@ impl TryFrom<char> for TileType {
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
             '#' => Ok(TileType::Wall),
+            '^' => Ok(TileType::Fire),
+            '~' => Ok(TileType::Sludge),
             _ => Err("Invalid tile character"),
         }
     }
 }
 
-impl From<&str> for Map {
-    fn from(value: &str) -> Self {
-        let tile_chars: Vec<Vec<_>> = value.lines().map(|line| line.chars().collect()).collect();
-        let w = tile_chars[0].len();
+/// Why the text of a level could not be read as a map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapError {
+    /// There is not a single cell below the header.
+    Empty,
+}
+
+impl Display for MapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MapError::Empty => write!(f, "the level has no rows"),
+        }
+    }
+}
+
+impl std::error::Error for MapError {}
+
+impl TryFrom<&str> for Map {
+    type Error = MapError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut name = String::new();
+        let mut rooms = vec![];
+        let header = value.lines().take_while(|line| line.starts_with(':'));
+        for line in header {
+            if let Some(rest) = line.strip_prefix(":name ") {
+                name = rest.trim().to_string();
+            } else if let Some(room) = line.strip_prefix(":room ").and_then(Room::parse) {
+                rooms.push(room);
+            }
+        }
+
+        let tile_chars: Vec<Vec<_>> = value
+            .lines()
+            .skip_while(|line| line.starts_with(':'))
+            .map(|line| line.chars().collect())
+            .collect();
+        let w = tile_chars.iter().map(Vec::len).max().unwrap_or(0);
+        if w == 0 {
+            return Err(MapError::Empty);
+        }
         let h = tile_chars.len();
         let mut start_pos = Pos(0., 0.);
 
         let mut level = vec![];
+        let mut spawns = vec![];
+        let mut cells = vec![None; w * h];
 
         #[allow(clippy::needless_range_loop)]
         for y in 0..h {
             for x in 0..w {
-                if let Ok(kind) = tile_chars[y][x].try_into() {
-                    let rect = Rect::new(&Pos(x as f32, y as f32), 1., 1.);
-                    level.push(Tile { rect, kind });
-                    if tile_chars[y][x] == '@' {
-                        start_pos = Pos(x as f32, y as f32);
+                let pos = Pos(x as f32, y as f32);
+                match tile_chars[y].get(x) {
+                    Some('@') => start_pos = pos,
+                    Some('G') => spawns.push(Spawn {
+                        kind: EntityKind::Goblo,
+                        pos,
+                    }),
+                    Some(&c) => {
+                        if let Some(kind) = PickupKind::from_glyph(c) {
+                            spawns.push(Spawn {
+                                kind: EntityKind::Pickup(kind),
+                                pos,
+                            });
+                        } else if let Ok(kind) = c.try_into() {
+                            let rect = Rect::new(&pos, 1., 1.);
+                            level.push(Tile { rect, kind });
+                            cells[y * w + x] = Some(kind);
+                        }
                     }
+                    None => {}
                 }
             }
         }
 
-        Self {
+        Ok(Self {
+            name,
+            rooms,
+            bounds: Rect::new(&Pos(0., 0.), w as f32, h as f32),
             start_pos,
+            spawns,
             level,
             seen: vec![],
+            cells,
+        })
+    }
+}
+
+impl Map {
+    /// The first room that `pos` is in, if it is in any.
+    #[must_use]
+    pub fn room_at(&self, pos: &Pos) -> Option<&Room> {
+        self.rooms.iter().find(|room| room.rect.contains(pos))
+    }
+
+    /// The kind of tile in the cell at `pos`, if there is one.
+    #[must_use]
+    pub fn tile_at(&self, Pos(x, y): &Pos) -> Option<TileType> {
+        if *x < 0. || *y < 0. || *x >= self.bounds.w || *y >= self.bounds.h {
+            return None;
         }
+
+        let w = self.bounds.w as usize;
+        self.cells[*y as usize * w + *x as usize]
+    }
+
+    #[must_use]
+    pub fn is_wall(&self, pos: &Pos) -> bool {
+        self.tile_at(pos) == Some(TileType::Wall)
+    }
+
+    /// Whether any of the cells `rect` covers is a wall.
+    #[must_use]
+    pub fn blocks(&self, rect: &Rect) -> bool {
+        self.tiles_under(rect).any(|tile| tile == TileType::Wall)
+    }
+
+    /// The kinds of tiles in the cells `rect` covers.
+    pub fn tiles_under(&self, rect: &Rect) -> impl Iterator<Item = TileType> + '_ {
+        let (x0, y0) = (rect.pos.0.floor() as i32, rect.pos.1.floor() as i32);
+        let (x1, y1) = (
+            (rect.pos.0 + rect.w).ceil() as i32,
+            (rect.pos.1 + rect.h).ceil() as i32,
+        );
+
+        (y0..y1)
+            .flat_map(move |y| (x0..x1).map(move |x| Pos(x as f32, y as f32)))
+            .filter_map(|pos| self.tile_at(&pos))
+    }
+
+    /// Whether the straight line between `from` and `to` is not blocked by any wall.
+    #[must_use]
+    pub fn line_of_sight(&self, from: &Pos, to: &Pos) -> bool {
+        let distance = from.distance(to);
+        let steps = (distance * 2.).ceil() as u32;
+        let angle = from.angle(to);
+
+        (0..=steps).all(|step| {
+            let d = (step as f32 / 2.).min(distance);
+            !self.is_wall(&from.transpose(Pos(angle.cos() * d, angle.sin() * d)))
+        })
     }
 }
 
 impl Render for Map {
-    fn render(&self, stdout: &mut std::io::Stdout) -> Result<(), Box<dyn std::error::Error>> {
+    fn render(&self, frame: &mut Frame) {
         for tile in &self.level {
-            if tile.kind == TileType::Wall {
-                crossterm::queue!(
-                    stdout,
-                    MoveTo(tile.rect.pos.0 as u16, tile.rect.pos.1 as u16),
-                    Print(tile)
-                )?;
-            }
+            let fg = match tile.kind {
+                TileType::Wall => Role::Wall,
+                TileType::Fire => Role::Burning,
+                TileType::Sludge => Role::Poison,
+            };
+            frame.print_colored(tile.rect.pos, &tile.to_string(), Color::Role(fg));
         }
-
-        Ok(())
     }
 }
@@ -0,0 +1,279 @@
+use std::{collections::VecDeque, fmt::Display};
+
+use crate::{
+    entity::{enemies, pickups::PickupKind, EntityKind},
+    geometry::{Pos, Rect},
+};
+
+use super::Map;
+
+/// The characters a level is drawn with, besides those of pickups.
+const GLYPHS: [char; 6] = [' ', '#', '^', '~', 'G', '@'];
+
+/// Something wrong with the text of a level.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Problem {
+    /// The level has no rows after its header.
+    Empty,
+    MissingStart,
+    /// More than one `@`, at these cells.
+    DuplicateStart(Vec<Pos>),
+    /// A row that is not as wide as the first.
+    Ragged {
+        row: usize,
+        width: usize,
+        expected: usize,
+    },
+    /// A character that is not a tile, an enemy or the start, at these cells.
+    UnknownGlyph(char, Vec<Pos>),
+    /// An enemy that would spawn overlapping a wall, at its spawn.
+    EnemyInWall(Pos),
+    /// Open cells that cannot be walked to from the start, and how many
+    /// enemies spawn among them.
+    Unreachable {
+        area: Rect,
+        cells: usize,
+        enemies: usize,
+    },
+    /// A room that cannot be walked out of, by its name.
+    NoExit(String),
+}
+
+impl Problem {
+    /// Whether the level cannot be played, or cannot be cleared, because of
+    /// the problem. The others are only worth a look.
+    #[must_use]
+    pub fn is_error(&self) -> bool {
+        match self {
+            Problem::Unreachable { enemies, .. } => *enemies > 0,
+            Problem::NoExit(_) => false,
+            _ => true,
+        }
+    }
+}
+
+fn cells(cells: &[Pos]) -> String {
+    let cells: Vec<_> = cells.iter().map(|Pos(x, y)| format!("{x},{y}")).collect();
+    cells.join(" ")
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Problem::Empty => write!(f, "the level has no rows"),
+            Problem::MissingStart => write!(f, "there is no start marker @"),
+            Problem::DuplicateStart(at) => {
+                write!(f, "there is more than one start marker @, at {}", cells(at))
+            }
+            Problem::Ragged {
+                row,
+                width,
+                expected,
+            } => write!(
+                f,
+                "row {row} is {width} cells wide, the first row is {expected}"
+            ),
+            Problem::UnknownGlyph(glyph, at) => {
+                write!(f, "unknown character {glyph:?} at {}", cells(at))
+            }
+            Problem::EnemyInWall(Pos(x, y)) => write!(f, "the enemy at {x},{y} is in a wall"),
+            Problem::Unreachable {
+                area,
+                cells,
+                enemies,
+            } => {
+                let Pos(x, y) = area.pos;
+                let (x1, y1) = (x + area.w - 1., y + area.h - 1.);
+                write!(
+                    f,
+                    "{cells} cells between {x},{y} and {x1},{y1} cannot be reached from the start"
+                )?;
+                match enemies {
+                    0 => Ok(()),
+                    1 => write!(f, ", with an enemy in them"),
+                    n => write!(f, ", with {n} enemies in them"),
+                }
+            }
+            Problem::NoExit(room) => write!(f, "the room {room} has no way out"),
+        }
+    }
+}
+
+/// The problems with the level in `text`, none if it can be played. The
+/// level is only analyzed as a `Map` once its text has a single start.
+#[must_use]
+pub fn validate(text: &str) -> Vec<Problem> {
+    let rows: Vec<&str> = text
+        .lines()
+        .skip_while(|line| line.starts_with(':'))
+        .collect();
+    if rows.iter().all(|row| row.is_empty()) {
+        return vec![Problem::Empty];
+    }
+
+    let mut problems = vec![];
+    let expected = rows[0].chars().count();
+    for (row, line) in rows.iter().enumerate() {
+        let width = line.chars().count();
+        if width != expected {
+            problems.push(Problem::Ragged {
+                row,
+                width,
+                expected,
+            });
+        }
+    }
+
+    let glyphs = |wanted: &dyn Fn(char) -> bool| -> Vec<(char, Pos)> {
+        rows.iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.chars()
+                    .enumerate()
+                    .map(move |(x, c)| (c, Pos(x as f32, y as f32)))
+            })
+            .filter(|(c, _)| wanted(*c))
+            .collect()
+    };
+
+    let mut unknown: Vec<(char, Vec<Pos>)> = vec![];
+    for (glyph, pos) in glyphs(&|c| !GLYPHS.contains(&c) && PickupKind::from_glyph(c).is_none()) {
+        match unknown.iter_mut().find(|(c, _)| *c == glyph) {
+            Some((_, at)) => at.push(pos),
+            None => unknown.push((glyph, vec![pos])),
+        }
+    }
+    problems.extend(
+        unknown
+            .into_iter()
+            .map(|(glyph, at)| Problem::UnknownGlyph(glyph, at)),
+    );
+
+    let starts: Vec<_> = glyphs(&|c| c == '@')
+        .into_iter()
+        .map(|(_, pos)| pos)
+        .collect();
+    match starts.len() {
+        0 => problems.push(Problem::MissingStart),
+        1 => problems.extend(Map::try_from(text).iter().flat_map(analyze)),
+        _ => problems.push(Problem::DuplicateStart(starts)),
+    }
+
+    problems
+}
+
+/// The problems with how `map` plays: enemies in walls, areas that cannot
+/// be reached from the start, and rooms with no way out. Walking is taken
+/// to be from cell to cell, up, down, left and right, through anything but walls.
+#[must_use]
+pub fn analyze(map: &Map) -> Vec<Problem> {
+    let mut problems = vec![];
+
+    let enemies: Vec<_> = map
+        .spawns
+        .iter()
+        .filter(|spawn| spawn.kind == EntityKind::Goblo)
+        .collect();
+    for spawn in &enemies {
+        let hitbox = enemies::goblo(spawn.pos).hitbox;
+        if hitbox.is_some_and(|hitbox| map.blocks(&Rect::new(&spawn.pos, hitbox.w, hitbox.h))) {
+            problems.push(Problem::EnemyInWall(spawn.pos));
+        }
+    }
+
+    let (w, h) = (map.bounds.w as usize, map.bounds.h as usize);
+    let open = |x: usize, y: usize| !map.is_wall(&Pos(x as f32, y as f32));
+    let mut reached = vec![false; w * h];
+    let Pos(sx, sy) = map.start_pos;
+    fill(w, h, (sx as usize, sy as usize), &mut reached, open);
+
+    for i in 0..w * h {
+        if reached[i] || !open(i % w, i / w) {
+            continue;
+        }
+        let mut area = vec![false; w * h];
+        fill(w, h, (i % w, i / w), &mut area, open);
+
+        let found: Vec<usize> = (0..w * h).filter(|j| area[*j]).collect();
+        let (mut x0, mut y0, mut x1, mut y1) = (w, h, 0, 0);
+        for &j in &found {
+            reached[j] = true;
+            (x0, y0) = (x0.min(j % w), y0.min(j / w));
+            (x1, y1) = (x1.max(j % w), y1.max(j / w));
+        }
+        let enemies = enemies
+            .iter()
+            .filter(|spawn| area[spawn.pos.1 as usize * w + spawn.pos.0 as usize])
+            .count();
+
+        problems.push(Problem::Unreachable {
+            area: Rect::new(
+                &Pos(x0 as f32, y0 as f32),
+                (x1 - x0 + 1) as f32,
+                (y1 - y0 + 1) as f32,
+            ),
+            cells: found.len(),
+            enemies,
+        });
+    }
+
+    for room in &map.rooms {
+        if !has_exit(map, &room.rect) {
+            problems.push(Problem::NoExit(room.name.clone()));
+        }
+    }
+
+    problems
+}
+
+/// Marks the cells that can be walked to from `from` in `filled`, going
+/// only through cells that are `open`.
+fn fill(
+    w: usize,
+    h: usize,
+    from: (usize, usize),
+    filled: &mut [bool],
+    open: impl Fn(usize, usize) -> bool,
+) {
+    let mut queue = VecDeque::from([from]);
+    while let Some((x, y)) = queue.pop_front() {
+        if x >= w || y >= h || filled[y * w + x] || !open(x, y) {
+            continue;
+        }
+        filled[y * w + x] = true;
+        queue.push_back((x + 1, y));
+        queue.push_back((x, y + 1));
+        if let Some(x) = x.checked_sub(1) {
+            queue.push_back((x, y));
+        }
+        if let Some(y) = y.checked_sub(1) {
+            queue.push_back((x, y));
+        }
+    }
+}
+
+/// Whether an open cell of the room at `rect` is next to an open cell
+/// outside of it. A room that covers the whole level needs no way out.
+fn has_exit(map: &Map, rect: &Rect) -> bool {
+    let inside = |pos: &Pos| rect.contains(pos) && map.bounds.contains(pos);
+    let open = |pos: &Pos| map.bounds.contains(pos) && !map.is_wall(pos);
+
+    let Pos(x0, y0) = rect.pos;
+    let outside_any = (0..map.bounds.h as usize)
+        .any(|y| (0..map.bounds.w as usize).any(|x| !rect.contains(&Pos(x as f32, y as f32))));
+    if !outside_any {
+        return true;
+    }
+
+    (0..rect.h as usize).any(|dy| {
+        (0..rect.w as usize).any(|dx| {
+            let pos = Pos(x0 + dx as f32, y0 + dy as f32);
+            inside(&pos)
+                && open(&pos)
+                && [(1., 0.), (-1., 0.), (0., 1.), (0., -1.)]
+                    .into_iter()
+                    .map(|(nx, ny)| pos.transpose(Pos(nx, ny)))
+                    .any(|next| !rect.contains(&next) && open(&next))
+        })
+    })
+}
@@ -0,0 +1,173 @@
+use std::{
+    io::{self, BufReader},
+    net::{Shutdown, TcpStream, ToSocketAddrs},
+    sync::mpsc::{channel, Receiver, TryRecvError},
+    thread,
+    time::Instant,
+};
+
+use crate::{
+    entity::{player::PlayerId, EntityKind},
+    event::PlayerEvent,
+    render::{Frame, Render},
+};
+
+use crate::game::TICK;
+
+use super::{
+    server::SNAPSHOT_EVERY,
+    snapshot::Snapshot,
+    wire::{read_frame, write_frame, ClientMessage, ServerMessage, PROTOCOL_VERSION},
+};
+
+/// A connection to a `breach-server`. The client does not simulate
+/// anything, it draws the snapshots sent by the server and smooths out
+/// the movement between them.
+#[derive(Debug)]
+pub struct Client {
+    pub id: PlayerId,
+    stream: TcpStream,
+    rx: Receiver<ServerMessage>,
+    prev: Snapshot,
+    latest: Snapshot,
+    received: Instant,
+}
+
+impl Client {
+    /**
+    Connects to the server on `addr`
+
+    # Errors
+    If the server cannot be reached or speaks another protocol version
+    */
+    pub fn connect(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let mut stream = TcpStream::connect(addr)?;
+        stream.set_nodelay(true)?;
+
+        let hello = ClientMessage::Hello {
+            version: PROTOCOL_VERSION,
+        };
+        write_frame(&mut stream, &hello.encode())?;
+
+        let id = match ServerMessage::decode(&read_frame(&mut stream)?)? {
+            ServerMessage::Welcome {
+                version: PROTOCOL_VERSION,
+                id,
+            } => id,
+            ServerMessage::Welcome { version, .. } => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("server speaks version {version}, client {PROTOCOL_VERSION}"),
+                ))
+            }
+            ServerMessage::Reject(reason) => {
+                return Err(io::Error::new(io::ErrorKind::ConnectionRefused, reason))
+            }
+            message => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("expected a welcome from the server, got {message:?}"),
+                ))
+            }
+        };
+
+        let (tx, rx) = channel();
+        let mut reader = BufReader::new(stream.try_clone()?);
+        thread::spawn(move || {
+            while let Ok(frame) = read_frame(&mut reader) {
+                let Ok(message) = ServerMessage::decode(&frame) else {
+                    return;
+                };
+                if tx.send(message).is_err() {
+                    return;
+                }
+            }
+        });
+
+        Ok(Self {
+            id,
+            stream,
+            rx,
+            prev: Snapshot::default(),
+            latest: Snapshot::default(),
+            received: Instant::now(),
+        })
+    }
+
+    /**
+    Sends an event for the local player to the server
+
+    # Errors
+    If the connection to the server is lost
+    */
+    pub fn send(&mut self, event: PlayerEvent) -> io::Result<()> {
+        write_frame(&mut self.stream, &ClientMessage::Event(event).encode())
+    }
+
+    /**
+    Takes in everything the server has sent since the last call
+
+    # Errors
+    If the connection to the server is lost
+    */
+    pub fn poll(&mut self) -> io::Result<()> {
+        loop {
+            let snapshot = match self.rx.try_recv() {
+                Ok(ServerMessage::Snapshot(snapshot)) => snapshot,
+                Ok(ServerMessage::Delta(delta)) => match self.latest.apply(&delta) {
+                    Some(snapshot) => snapshot,
+                    None => continue,
+                },
+                Ok(_) => continue,
+                Err(TryRecvError::Empty) => return Ok(()),
+                Err(TryRecvError::Disconnected) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::ConnectionAborted,
+                        "server closed the connection",
+                    ))
+                }
+            };
+
+            self.prev = std::mem::replace(&mut self.latest, snapshot);
+            self.received = Instant::now();
+        }
+    }
+
+    /// The latest state of the game the server sent.
+    #[must_use]
+    pub fn snapshot(&self) -> &Snapshot {
+        &self.latest
+    }
+
+    /// Draws the game as it was between the last two snapshots.
+    pub fn render(&self, frame: &mut Frame) {
+        let interval = TICK * SNAPSHOT_EVERY as u32;
+        let t = self.received.elapsed().as_secs_f32() / interval.as_secs_f32();
+        let entities = self.prev.interpolate(&self.latest, t);
+        let is_player = |kind| matches!(kind, EntityKind::Player(_));
+
+        frame.clear();
+
+        for entity in &entities {
+            if let Some(player) = entity.player() {
+                player.render_fov(entity.pos, frame);
+            }
+        }
+
+        for entity in entities.iter().filter(|entity| !is_player(entity.kind)) {
+            entity.render(frame);
+        }
+
+        for entity in entities.iter().filter(|entity| is_player(entity.kind)) {
+            entity.render(frame);
+        }
+    }
+}
+
+impl Drop for Client {
+    /// Closes the connection, which the thread reading from it would
+    /// otherwise keep open.
+    fn drop(&mut self) {
+        self.stream.shutdown(Shutdown::Both).ok();
+    }
+}
@@ -0,0 +1,47 @@
+pub mod client;
+pub mod protocol;
+pub mod server;
+pub mod snapshot;
+pub mod wire;
+
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, ToSocketAddrs},
+    thread,
+};
+
+use client::Client;
+use server::Server;
+
+/**
+Hosts a game on `addr` and joins it. The game is simulated by a `Server`
+in the background, and the host plays it as a `Client` like everyone who
+joins, so every player sees the one game the server runs.
+
+# Errors
+If the address cannot be bound
+*/
+pub fn host(addr: impl ToSocketAddrs) -> io::Result<Client> {
+    let server = Server::bind(addr)?;
+    let mut local = server.local_addr()?;
+    if local.ip().is_unspecified() {
+        local.set_ip(if local.is_ipv4() {
+            IpAddr::V4(Ipv4Addr::LOCALHOST)
+        } else {
+            IpAddr::V6(Ipv6Addr::LOCALHOST)
+        });
+    }
+    thread::spawn(move || server.run());
+
+    Client::connect(local)
+}
+
+/**
+Joins a game hosted on `addr`.
+
+# Errors
+If the host cannot be reached or speaks another protocol version
+*/
+pub fn join(addr: impl ToSocketAddrs) -> io::Result<Client> {
+    Client::connect(addr)
+}
@@ -0,0 +1,176 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    entity::player::{Ability, PlayerId},
+    event::{Event, GameEvent, PlayerEvent},
+    geometry::Pos,
+};
+
+/// A single line of the text protocol, which replays record their inputs in.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    /// Sent once by the host to a newly connected client with its assigned id.
+    Welcome(PlayerId),
+    Event(Event),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtocolError(String);
+
+impl Display for ProtocolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid message: {}", self.0)
+    }
+}
+
+impl std::error::Error for ProtocolError {}
+
+impl Message {
+    /// Encodes the message as a single line, or `None` for events that are local to a client.
+    #[must_use]
+    pub fn encode(&self) -> Option<String> {
+        let line = match self {
+            Message::Welcome(id) => format!("welcome {id}"),
+            Message::Event(Event::Game(GameEvent::Join(id))) => format!("join {id}"),
+            Message::Event(Event::Game(GameEvent::Leave(id))) => format!("leave {id}"),
+            Message::Event(Event::Game(_)) => return None,
+            Message::Event(Event::Player(id, event)) => match event {
+                PlayerEvent::Move(x, y) => format!("{id} move {x} {y}"),
+                PlayerEvent::Aim(Pos(x, y)) => format!("{id} aim {x} {y}"),
+                PlayerEvent::Shoot => format!("{id} shoot"),
+                PlayerEvent::Ability(Ability::Blink) => format!("{id} ability blink"),
+                PlayerEvent::Ability(Ability::Grenade) => format!("{id} ability grenade"),
+                PlayerEvent::UseMedkit => format!("{id} medkit"),
+                PlayerEvent::SwitchWeapon => format!("{id} switch"),
+            },
+        };
+
+        Some(line)
+    }
+}
+
+impl FromStr for Message {
+    type Err = ProtocolError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let err = || ProtocolError(line.to_string());
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let id = |s: &str| s.parse::<PlayerId>().map_err(|_| err());
+        let num = |s: &str| {
+            s.parse::<f32>()
+                .ok()
+                .filter(|n| n.is_finite())
+                .ok_or_else(err)
+        };
+
+        let message = match parts.as_slice() {
+            ["welcome", player] => Message::Welcome(id(player)?),
+            ["join", player] => Message::Event(Event::Game(GameEvent::Join(id(player)?))),
+            ["leave", player] => Message::Event(Event::Game(GameEvent::Leave(id(player)?))),
+            [player, "move", x, y] => Message::Event(Event::Player(
+                id(player)?,
+                PlayerEvent::Move(num(x)?, num(y)?),
+            )),
+            [player, "aim", x, y] => Message::Event(Event::Player(
+                id(player)?,
+                PlayerEvent::Aim(Pos(num(x)?, num(y)?)),
+            )),
+            [player, "shoot"] => Message::Event(Event::Player(id(player)?, PlayerEvent::Shoot)),
+            [player, "medkit"] => {
+                Message::Event(Event::Player(id(player)?, PlayerEvent::UseMedkit))
+            }
+            [player, "switch"] => {
+                Message::Event(Event::Player(id(player)?, PlayerEvent::SwitchWeapon))
+            }
+            [player, "ability", "blink"] => Message::Event(Event::Player(
+                id(player)?,
+                PlayerEvent::Ability(Ability::Blink),
+            )),
+            [player, "ability", "grenade"] => Message::Event(Event::Player(
+                id(player)?,
+                PlayerEvent::Ability(Ability::Grenade),
+            )),
+            _ => return Err(err()),
+        };
+
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(message: &Message) -> Message {
+        let line = message.encode().expect("the message is sent over the wire");
+        line.parse().expect("the line reads back")
+    }
+
+    #[test]
+    fn reads_back_every_message_it_writes() {
+        let events = [
+            PlayerEvent::Move(1.5, -2.),
+            PlayerEvent::Aim(Pos(10.25, 3.)),
+            PlayerEvent::Shoot,
+            PlayerEvent::Ability(Ability::Blink),
+            PlayerEvent::Ability(Ability::Grenade),
+            PlayerEvent::UseMedkit,
+            PlayerEvent::SwitchWeapon,
+        ];
+        let messages = [
+            Message::Welcome(3),
+            Message::Event(Event::Game(GameEvent::Join(2))),
+            Message::Event(Event::Game(GameEvent::Leave(255))),
+        ]
+        .into_iter()
+        .chain(
+            events
+                .into_iter()
+                .map(|event| Message::Event(Event::Player(7, event))),
+        );
+
+        for message in messages {
+            assert_eq!(round_trip(&message), message);
+        }
+    }
+
+    #[test]
+    fn keeps_local_events_off_the_wire() {
+        for event in [
+            GameEvent::Pause,
+            GameEvent::Select,
+            GameEvent::Resize(80, 24),
+            GameEvent::ToggleLog,
+            GameEvent::Quit,
+        ] {
+            assert_eq!(Message::Event(Event::Game(event)).encode(), None);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        for line in [
+            "",
+            "welcome",
+            "welcome x",
+            "welcome 256",
+            "join -1",
+            "0 move 1",
+            "0 move 1 2 3",
+            "0 move x 2",
+            "0 move NaN 0",
+            "0 aim inf 0",
+            "0 ability fly",
+            "shoot",
+            "x shoot",
+            "0 shoot now",
+            "\u{0}\u{ff}",
+        ] {
+            assert_eq!(
+                line.parse::<Message>(),
+                Err(ProtocolError(line.to_string())),
+                "{line:?}"
+            );
+        }
+    }
+}
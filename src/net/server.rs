@@ -0,0 +1,275 @@
+use std::{
+    collections::BTreeSet,
+    io::{self, BufReader},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        mpsc::{channel, sync_channel, Receiver, Sender, SyncSender, TrySendError},
+        Arc, Mutex, PoisonError,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    entity::player::PlayerId,
+    event::{Event, GameEvent, PlayerEvent},
+    game::TICK,
+    map::{levels, MapError},
+    Game,
+};
+
+use super::{
+    snapshot::Snapshot,
+    wire::{read_frame, write_frame, ClientMessage, ServerMessage, PROTOCOL_VERSION},
+};
+
+/// Clients receive the state every this many ticks and interpolate in between.
+pub const SNAPSHOT_EVERY: u64 = 8;
+
+/// Every this many snapshots, clients get a full snapshot instead of a delta.
+pub const KEYFRAME_EVERY: u64 = 64;
+
+/// Players a server takes at once, later clients are told it is full.
+pub const MAX_PLAYERS: PlayerId = 8;
+
+/// Snapshots a client may fall behind on before it is dropped.
+const BACKLOG: usize = 16;
+
+/// How long writing to a client may block before it is dropped.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+enum Incoming {
+    Connected(PlayerId, TcpStream),
+    Event(PlayerId, PlayerEvent),
+    Disconnected(PlayerId),
+}
+
+struct Remote {
+    id: PlayerId,
+    /// Frames for the thread that writes to the client, so that a slow
+    /// client never holds up the game.
+    frames: SyncSender<Arc<[u8]>>,
+    synced: bool,
+}
+
+/// The player ids of the connected clients. New clients get the lowest
+/// id that is free, so the ids of clients that left are handed out again.
+#[derive(Debug, Clone, Default)]
+struct Ids(Arc<Mutex<BTreeSet<PlayerId>>>);
+
+/// An id taken by a client, freed once the client is gone.
+#[derive(Debug)]
+struct Claim {
+    id: PlayerId,
+    ids: Ids,
+}
+
+impl Ids {
+    /// Takes the lowest free id, if there is one left.
+    fn claim(&self) -> Option<Claim> {
+        let mut taken = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        let id = (0..MAX_PLAYERS).find(|id| !taken.contains(id))?;
+        taken.insert(id);
+
+        Some(Claim {
+            id,
+            ids: self.clone(),
+        })
+    }
+}
+
+impl Drop for Claim {
+    fn drop(&mut self) {
+        let mut taken = self.ids.0.lock().unwrap_or_else(PoisonError::into_inner);
+        taken.remove(&self.id);
+    }
+}
+
+/// Runs the game without a terminal and keeps every connected client in sync with it.
+#[derive(Debug)]
+pub struct Server {
+    listener: TcpListener,
+    game: Game,
+}
+
+impl Server {
+    /**
+    Binds the server to `addr`
+
+    # Errors
+    If the address cannot be bound
+    */
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            game: Game::lobby(levels::LEVELS[0].1, 0)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?,
+        })
+    }
+
+    /// Plays `level` from `seed` instead of the first built in level.
+    ///
+    /// # Errors
+    ///
+    /// If `level` cannot be read as a map.
+    pub fn with_level(self, level: &str, seed: u64) -> Result<Self, MapError> {
+        Ok(Self {
+            game: Game::lobby(level, seed)?,
+            ..self
+        })
+    }
+
+    /**
+    The address the server ended up listening on
+
+    # Errors
+    If the socket has been closed
+    */
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Simulates the game forever, one `TICK` at a time.
+    pub fn run(self) {
+        let (tx, rx) = channel::<Incoming>();
+        let Self { listener, mut game } = self;
+        thread::spawn(move || accept(&listener, &tx));
+
+        let mut remotes: Vec<Remote> = vec![];
+        let mut last = Snapshot::default();
+
+        loop {
+            let started = Instant::now();
+            let mut inputs = vec![];
+
+            for incoming in rx.try_iter() {
+                match incoming {
+                    Incoming::Connected(id, stream) => {
+                        println!("player {id} connected");
+                        inputs.push(Event::Game(GameEvent::Join(id)));
+                        let (frames, rx) = sync_channel(BACKLOG);
+                        thread::spawn(move || write_frames(stream, &rx));
+                        remotes.push(Remote {
+                            id,
+                            frames,
+                            synced: false,
+                        });
+                    }
+                    Incoming::Event(id, event) => inputs.push(Event::Player(id, event)),
+                    Incoming::Disconnected(id) => {
+                        println!("player {id} disconnected");
+                        inputs.push(Event::Game(GameEvent::Leave(id)));
+                        remotes.retain(|remote| remote.id != id);
+                    }
+                }
+            }
+
+            game.step(inputs);
+            let tick = game.tick();
+
+            if tick.is_multiple_of(SNAPSHOT_EVERY) {
+                let snapshot = game.state().snapshot(tick);
+                let keyframe = (tick / SNAPSHOT_EVERY).is_multiple_of(KEYFRAME_EVERY);
+                let full: Arc<[u8]> = ServerMessage::Snapshot(snapshot.clone()).encode().into();
+                let delta: Arc<[u8]> = ServerMessage::Delta(snapshot.delta_from(&last))
+                    .encode()
+                    .into();
+
+                remotes.retain_mut(|remote| {
+                    let payload = if keyframe || !remote.synced {
+                        &full
+                    } else {
+                        &delta
+                    };
+                    remote.synced = true;
+                    match remote.frames.try_send(payload.clone()) {
+                        Ok(()) => true,
+                        Err(TrySendError::Full(_)) => {
+                            println!("player {} fell behind", remote.id);
+                            false
+                        }
+                        Err(TrySendError::Disconnected(_)) => false,
+                    }
+                });
+                last = snapshot;
+            }
+
+            thread::sleep(TICK.saturating_sub(started.elapsed()));
+        }
+    }
+}
+
+/// Writes frames to a client until it cannot keep up or the game drops
+/// it, and then closes the connection, which ends the thread reading from it.
+fn write_frames(mut stream: TcpStream, frames: &Receiver<Arc<[u8]>>) {
+    if stream.set_write_timeout(Some(WRITE_TIMEOUT)).is_ok() {
+        for frame in frames {
+            if write_frame(&mut stream, &frame).is_err() {
+                break;
+            }
+        }
+    }
+    stream.shutdown(Shutdown::Both).ok();
+}
+
+fn accept(listener: &TcpListener, tx: &Sender<Incoming>) {
+    let ids = Ids::default();
+
+    for stream in listener.incoming().flatten() {
+        let (tx, ids) = (tx.clone(), ids.clone());
+        thread::spawn(move || {
+            let addr = stream
+                .peer_addr()
+                .map_or("a client".to_string(), |addr| addr.to_string());
+            if let Err(err) = serve(&ids, stream, &tx) {
+                println!("{addr}: {err}");
+            }
+        });
+    }
+}
+
+fn serve(ids: &Ids, mut stream: TcpStream, tx: &Sender<Incoming>) -> io::Result<()> {
+    stream.set_nodelay(true)?;
+
+    let reject = |stream: &mut TcpStream, reason: String| {
+        write_frame(stream, &ServerMessage::Reject(reason.clone()).encode())?;
+        Err(io::Error::new(io::ErrorKind::InvalidData, reason))
+    };
+    match ClientMessage::decode(&read_frame(&mut stream)?)? {
+        ClientMessage::Hello {
+            version: PROTOCOL_VERSION,
+        } => {}
+        ClientMessage::Hello { version } => {
+            let reason = format!("server speaks version {PROTOCOL_VERSION}, client {version}");
+            return reject(&mut stream, reason);
+        }
+        ClientMessage::Event(_) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "client did not say hello",
+            ))
+        }
+    }
+    let Some(claim) = ids.claim() else {
+        return reject(&mut stream, "the server is full".to_string());
+    };
+    let id = claim.id;
+
+    let welcome = ServerMessage::Welcome {
+        version: PROTOCOL_VERSION,
+        id,
+    };
+    write_frame(&mut stream, &welcome.encode())?;
+    tx.send(Incoming::Connected(id, stream.try_clone()?)).ok();
+
+    let mut reader = BufReader::new(stream);
+    while let Ok(frame) = read_frame(&mut reader) {
+        if let Ok(ClientMessage::Event(event)) = ClientMessage::decode(&frame) {
+            tx.send(Incoming::Event(id, event)).ok();
+        }
+    }
+    tx.send(Incoming::Disconnected(id)).ok();
+    drop(claim);
+
+    Ok(())
+}
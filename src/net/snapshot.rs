@@ -0,0 +1,164 @@
+use crate::{
+    entity::{
+        components::Pose,
+        enemies, pickups,
+        player::{self, Player},
+        projectiles,
+        sprites::Facing,
+        EntityId, EntityKind,
+    },
+    geometry::Pos,
+    render::{Frame, Render},
+};
+
+/// What a client needs to know about an entity to draw it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EntityState {
+    pub id: EntityId,
+    pub kind: EntityKind,
+    pub pos: Pos,
+    pub angle: f32,
+}
+
+impl EntityState {
+    #[must_use]
+    pub fn new(id: EntityId, kind: EntityKind, pos: Pos, angle: f32) -> Self {
+        Self {
+            id,
+            kind,
+            pos,
+            angle,
+        }
+    }
+
+    /// The player this state describes, if it describes one.
+    #[must_use]
+    pub fn player(&self) -> Option<Player> {
+        let EntityKind::Player(id) = self.kind else {
+            return None;
+        };
+        let mut player = Player::new(id);
+        player.aim = self.angle;
+
+        Some(player)
+    }
+}
+
+impl Render for EntityState {
+    fn render(&self, frame: &mut Frame) {
+        let renderable = match self.kind {
+            EntityKind::Player(id) => Some(player::renderable(id)),
+            EntityKind::Goblo => enemies::goblo(self.pos).renderable,
+            EntityKind::Bullet => projectiles::bullet(self.pos, self.angle).renderable,
+            EntityKind::Grenade => projectiles::grenade(self.pos, self.angle).renderable,
+            EntityKind::Pickup(kind) => pickups::bundle(kind, self.pos).renderable,
+            EntityKind::Effect => None,
+        };
+        if let Some(renderable) = renderable {
+            let pose = Pose {
+                facing: Facing::from_angle(self.angle),
+                ..Pose::default()
+            };
+            renderable.draw(frame, self.pos, pose);
+        }
+        if let Some(player) = self.player() {
+            player.render_crosshair(self.pos, frame);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Snapshot {
+    pub tick: u64,
+    pub entities: Vec<EntityState>,
+}
+
+/// The changes needed to turn the snapshot taken at `base` into the one taken at `tick`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delta {
+    pub base: u64,
+    pub tick: u64,
+    pub changed: Vec<EntityState>,
+    pub removed: Vec<EntityId>,
+}
+
+impl Snapshot {
+    #[must_use]
+    pub fn get(&self, id: EntityId) -> Option<&EntityState> {
+        self.entities.iter().find(|entity| entity.id == id)
+    }
+
+    #[must_use]
+    pub fn delta_from(&self, base: &Snapshot) -> Delta {
+        Delta {
+            base: base.tick,
+            tick: self.tick,
+            changed: self
+                .entities
+                .iter()
+                .filter(|entity| base.get(entity.id) != Some(entity))
+                .copied()
+                .collect(),
+            removed: base
+                .entities
+                .iter()
+                .filter(|entity| self.get(entity.id).is_none())
+                .map(|entity| entity.id)
+                .collect(),
+        }
+    }
+
+    /// Applies a delta that was made against this snapshot, or returns `None` if it was made against another one.
+    #[must_use]
+    pub fn apply(&self, delta: &Delta) -> Option<Snapshot> {
+        if delta.base != self.tick {
+            return None;
+        }
+
+        let mut entities: Vec<EntityState> = self
+            .entities
+            .iter()
+            .filter(|entity| !delta.removed.contains(&entity.id))
+            .map(|entity| {
+                delta
+                    .changed
+                    .iter()
+                    .find(|changed| changed.id == entity.id)
+                    .copied()
+                    .unwrap_or(*entity)
+            })
+            .collect();
+
+        entities.extend(
+            delta
+                .changed
+                .iter()
+                .filter(|changed| self.get(changed.id).is_none()),
+        );
+
+        Some(Snapshot {
+            tick: delta.tick,
+            entities,
+        })
+    }
+
+    /// The entities of `next`, moved `t` of the way from where they were in this snapshot.
+    #[must_use]
+    pub fn interpolate(&self, next: &Snapshot, t: f32) -> Vec<EntityState> {
+        let t = t.clamp(0., 1.);
+
+        next.entities
+            .iter()
+            .map(|entity| match self.get(entity.id) {
+                Some(prev) => EntityState {
+                    pos: Pos(
+                        prev.pos.0 + (entity.pos.0 - prev.pos.0) * t,
+                        prev.pos.1 + (entity.pos.1 - prev.pos.1) * t,
+                    ),
+                    ..*entity
+                },
+                None => *entity,
+            })
+            .collect()
+    }
+}
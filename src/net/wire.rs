@@ -0,0 +1,334 @@
+use std::{
+    fmt::Display,
+    io::{self, Read, Write},
+};
+
+use crate::{
+    entity::{
+        pickups::PickupKind,
+        player::{Ability, PlayerId},
+        EntityId, EntityKind,
+    },
+    event::PlayerEvent,
+    geometry::Pos,
+};
+
+use super::snapshot::{Delta, EntityState, Snapshot};
+
+/// Bumped whenever the encoding of any message changes. Clients and
+/// servers refuse to talk to each other unless their versions match.
+pub const PROTOCOL_VERSION: u16 = 3;
+
+/// Frames larger than this are treated as a broken stream rather than allocated.
+const MAX_FRAME_LEN: usize = 1 << 20;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WireError {
+    UnexpectedEnd,
+    UnknownTag(&'static str, u8),
+    InvalidText,
+    /// A number that is infinite or not a number at all.
+    NonFinite,
+}
+
+impl Display for WireError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WireError::UnexpectedEnd => write!(f, "message ended unexpectedly"),
+            WireError::UnknownTag(what, tag) => write!(f, "unknown {what} tag {tag}"),
+            WireError::InvalidText => write!(f, "text is not valid utf-8"),
+            WireError::NonFinite => write!(f, "number is not finite"),
+        }
+    }
+}
+
+impl std::error::Error for WireError {}
+
+impl From<WireError> for io::Error {
+    fn from(value: WireError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClientMessage {
+    Hello { version: u16 },
+    Event(PlayerEvent),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServerMessage {
+    Welcome { version: u16, id: PlayerId },
+    Reject(String),
+    Snapshot(Snapshot),
+    Delta(Delta),
+}
+
+#[derive(Debug, Default)]
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn u8(&mut self, v: u8) -> &mut Self {
+        self.0.push(v);
+        self
+    }
+
+    fn u16(&mut self, v: u16) -> &mut Self {
+        self.0.extend(v.to_le_bytes());
+        self
+    }
+
+    fn u32(&mut self, v: u32) -> &mut Self {
+        self.0.extend(v.to_le_bytes());
+        self
+    }
+
+    fn u64(&mut self, v: u64) -> &mut Self {
+        self.0.extend(v.to_le_bytes());
+        self
+    }
+
+    fn f32(&mut self, v: f32) -> &mut Self {
+        self.0.extend(v.to_le_bytes());
+        self
+    }
+
+    fn str(&mut self, v: &str) -> &mut Self {
+        self.u32(v.len() as u32);
+        self.0.extend(v.as_bytes());
+        self
+    }
+
+    fn entity(&mut self, entity: &EntityState) -> &mut Self {
+        self.u32(entity.id.0);
+        match entity.kind {
+            EntityKind::Player(id) => self.u8(0).u8(id),
+            EntityKind::Goblo => self.u8(1),
+            EntityKind::Bullet => self.u8(2),
+            EntityKind::Effect => self.u8(3),
+            EntityKind::Grenade => self.u8(4),
+            EntityKind::Pickup(kind) => self.u8(5).u8(match kind {
+                PickupKind::Health => 0,
+                PickupKind::Ammo => 1,
+                PickupKind::Weapon => 2,
+                PickupKind::Charge => 3,
+            }),
+        };
+        self.f32(entity.pos.0).f32(entity.pos.1).f32(entity.angle)
+    }
+
+    fn entities(&mut self, entities: &[EntityState]) -> &mut Self {
+        self.u32(entities.len() as u32);
+        for entity in entities {
+            self.entity(entity);
+        }
+        self
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], WireError> {
+        let (head, rest) = self.0.split_at_checked(N).ok_or(WireError::UnexpectedEnd)?;
+        self.0 = rest;
+        head.try_into().map_err(|_| WireError::UnexpectedEnd)
+    }
+
+    fn u8(&mut self) -> Result<u8, WireError> {
+        Ok(u8::from_le_bytes(self.take()?))
+    }
+
+    fn u16(&mut self) -> Result<u16, WireError> {
+        Ok(u16::from_le_bytes(self.take()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, WireError> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, WireError> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+
+    fn f32(&mut self) -> Result<f32, WireError> {
+        let v = f32::from_le_bytes(self.take()?);
+        v.is_finite().then_some(v).ok_or(WireError::NonFinite)
+    }
+
+    fn str(&mut self) -> Result<String, WireError> {
+        let len = self.u32()? as usize;
+        let (head, rest) = self
+            .0
+            .split_at_checked(len)
+            .ok_or(WireError::UnexpectedEnd)?;
+        self.0 = rest;
+        String::from_utf8(head.to_vec()).map_err(|_| WireError::InvalidText)
+    }
+
+    fn entity(&mut self) -> Result<EntityState, WireError> {
+        let id = EntityId(self.u32()?);
+        let kind = match self.u8()? {
+            0 => EntityKind::Player(self.u8()?),
+            1 => EntityKind::Goblo,
+            2 => EntityKind::Bullet,
+            3 => EntityKind::Effect,
+            4 => EntityKind::Grenade,
+            5 => EntityKind::Pickup(match self.u8()? {
+                0 => PickupKind::Health,
+                1 => PickupKind::Ammo,
+                2 => PickupKind::Weapon,
+                3 => PickupKind::Charge,
+                tag => return Err(WireError::UnknownTag("pickup", tag)),
+            }),
+            tag => return Err(WireError::UnknownTag("entity", tag)),
+        };
+        let pos = Pos(self.f32()?, self.f32()?);
+
+        Ok(EntityState::new(id, kind, pos, self.f32()?))
+    }
+
+    fn entities(&mut self) -> Result<Vec<EntityState>, WireError> {
+        (0..self.u32()?).map(|_| self.entity()).collect()
+    }
+
+    fn ids(&mut self) -> Result<Vec<EntityId>, WireError> {
+        (0..self.u32()?).map(|_| self.u32().map(EntityId)).collect()
+    }
+}
+
+impl ClientMessage {
+    #[must_use]
+    pub fn encode(&self) -> Vec<u8> {
+        let mut w = Writer::default();
+        match self {
+            ClientMessage::Hello { version } => w.u8(0).u16(*version),
+            ClientMessage::Event(event) => match event {
+                PlayerEvent::Move(x, y) => w.u8(1).f32(*x).f32(*y),
+                PlayerEvent::Aim(Pos(x, y)) => w.u8(2).f32(*x).f32(*y),
+                PlayerEvent::Shoot => w.u8(3),
+                PlayerEvent::Ability(Ability::Blink) => w.u8(4).u8(0),
+                PlayerEvent::Ability(Ability::Grenade) => w.u8(4).u8(1),
+                PlayerEvent::UseMedkit => w.u8(5),
+                PlayerEvent::SwitchWeapon => w.u8(6),
+            },
+        };
+        w.0
+    }
+
+    /**
+    Decodes a message sent by a client
+
+    # Errors
+    If the bytes are not a message of the current protocol version
+    */
+    pub fn decode(bytes: &[u8]) -> Result<Self, WireError> {
+        let mut r = Reader(bytes);
+        let message = match r.u8()? {
+            0 => ClientMessage::Hello { version: r.u16()? },
+            1 => ClientMessage::Event(PlayerEvent::Move(r.f32()?, r.f32()?)),
+            2 => ClientMessage::Event(PlayerEvent::Aim(Pos(r.f32()?, r.f32()?))),
+            3 => ClientMessage::Event(PlayerEvent::Shoot),
+            4 => match r.u8()? {
+                0 => ClientMessage::Event(PlayerEvent::Ability(Ability::Blink)),
+                1 => ClientMessage::Event(PlayerEvent::Ability(Ability::Grenade)),
+                tag => return Err(WireError::UnknownTag("ability", tag)),
+            },
+            5 => ClientMessage::Event(PlayerEvent::UseMedkit),
+            6 => ClientMessage::Event(PlayerEvent::SwitchWeapon),
+            tag => return Err(WireError::UnknownTag("client message", tag)),
+        };
+
+        Ok(message)
+    }
+}
+
+impl ServerMessage {
+    #[must_use]
+    pub fn encode(&self) -> Vec<u8> {
+        let mut w = Writer::default();
+        match self {
+            ServerMessage::Welcome { version, id } => w.u8(0).u16(*version).u8(*id),
+            ServerMessage::Reject(reason) => w.u8(1).str(reason),
+            ServerMessage::Snapshot(snapshot) => {
+                w.u8(2).u64(snapshot.tick).entities(&snapshot.entities)
+            }
+            ServerMessage::Delta(delta) => {
+                w.u8(3)
+                    .u64(delta.base)
+                    .u64(delta.tick)
+                    .entities(&delta.changed);
+                w.u32(delta.removed.len() as u32);
+                for id in &delta.removed {
+                    w.u32(id.0);
+                }
+                &mut w
+            }
+        };
+        w.0
+    }
+
+    /**
+    Decodes a message sent by the server
+
+    # Errors
+    If the bytes are not a message of the current protocol version
+    */
+    pub fn decode(bytes: &[u8]) -> Result<Self, WireError> {
+        let mut r = Reader(bytes);
+        let message = match r.u8()? {
+            0 => ServerMessage::Welcome {
+                version: r.u16()?,
+                id: r.u8()?,
+            },
+            1 => ServerMessage::Reject(r.str()?),
+            2 => ServerMessage::Snapshot(Snapshot {
+                tick: r.u64()?,
+                entities: r.entities()?,
+            }),
+            3 => ServerMessage::Delta(Delta {
+                base: r.u64()?,
+                tick: r.u64()?,
+                changed: r.entities()?,
+                removed: r.ids()?,
+            }),
+            tag => return Err(WireError::UnknownTag("server message", tag)),
+        };
+
+        Ok(message)
+    }
+}
+
+/**
+Writes a length prefixed frame
+
+# Errors
+If the stream cannot be written to
+*/
+pub fn write_frame(w: &mut impl Write, payload: &[u8]) -> io::Result<()> {
+    w.write_all(&(payload.len() as u32).to_le_bytes())?;
+    w.write_all(payload)?;
+    w.flush()
+}
+
+/**
+Reads a length prefixed frame
+
+# Errors
+If the stream ends or announces a frame that is too large
+*/
+pub fn read_frame(r: &mut impl Read) -> io::Result<Vec<u8>> {
+    let mut len = [0; 4];
+    r.read_exact(&mut len)?;
+    let len = u32::from_le_bytes(len) as usize;
+    if len > MAX_FRAME_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame of {len} bytes is too large"),
+        ));
+    }
+
+    let mut payload = vec![0; len];
+    r.read_exact(&mut payload)?;
+    Ok(payload)
+}
@@ -1,10 +1,132 @@
-use std::{error::Error, io::Stdout};
+use crate::{geometry::Pos, theme::Role};
 
 pub trait Render {
-    /**
-    Attempts to render an Entity
-    # Errors
-    if the entity cannot be rendered
-    */
-    fn render(&self, stdout: &mut Stdout) -> Result<(), Box<dyn Error>>;
+    /// Draws the entity into the frame
+    fn render(&self, frame: &mut Frame);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Rgb(u8, u8, u8),
+    /// Whatever the theme says this role looks like.
+    Role(Role),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            ch: ' ',
+            fg: None,
+            bg: None,
+        }
+    }
+}
+
+/// A grid of cells that the game is drawn into before being shown by a
+/// terminal, or not shown at all when running headless. Anything drawn
+/// outside of the frame is clipped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub w: u16,
+    pub h: u16,
+    cells: Vec<Cell>,
+}
+
+impl Frame {
+    #[must_use]
+    pub fn new(w: u16, h: u16) -> Self {
+        Self {
+            w,
+            h,
+            cells: vec![Cell::default(); usize::from(w) * usize::from(h)],
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.fill(Cell::default());
+    }
+
+    #[must_use]
+    pub fn get(&self, x: u16, y: u16) -> Option<&Cell> {
+        if x < self.w && y < self.h {
+            self.cells
+                .get(usize::from(y) * usize::from(self.w) + usize::from(x))
+        } else {
+            None
+        }
+    }
+
+    fn get_mut(&mut self, x: f32, y: f32) -> Option<&mut Cell> {
+        let (x, y) = (x.floor(), y.floor());
+        if x < 0. || y < 0. || x >= f32::from(self.w) || y >= f32::from(self.h) {
+            return None;
+        }
+
+        let w = usize::from(self.w);
+        self.cells.get_mut(y as usize * w + x as usize)
+    }
+
+    /// Prints `text` starting at `pos`, replacing whatever was drawn there.
+    pub fn print(&mut self, pos: Pos, text: &str) {
+        self.print_colored(pos, text, None);
+    }
+
+    pub fn print_colored(&mut self, Pos(x, y): Pos, text: &str, fg: impl Into<Option<Color>>) {
+        let fg = fg.into();
+        for (i, ch) in text.chars().enumerate() {
+            if let Some(cell) = self.get_mut(x + i as f32, y) {
+                *cell = Cell { ch, fg, bg: None };
+            }
+        }
+    }
+
+    /// Draws a single character at `pos`.
+    pub fn put(&mut self, Pos(x, y): Pos, ch: char, fg: Option<Color>) {
+        if let Some(cell) = self.get_mut(x, y) {
+            *cell = Cell { ch, fg, bg: None };
+        }
+    }
+
+    /// Moves everything drawn `dx` cells right and `dy` cells down, leaving
+    /// blank cells behind. What is moved past the edge is lost.
+    pub fn shift(&mut self, dx: i32, dy: i32) {
+        let (w, h) = (i32::from(self.w), i32::from(self.h));
+        let blank = vec![Cell::default(); self.cells.len()];
+        let old = std::mem::replace(&mut self.cells, blank);
+
+        for y in 0..h {
+            for x in 0..w {
+                let (from_x, from_y) = (x - dx, y - dy);
+                if (0..w).contains(&from_x) && (0..h).contains(&from_y) {
+                    self.cells[(y * w + x) as usize] = old[(from_y * w + from_x) as usize];
+                }
+            }
+        }
+    }
+
+    /// Clears the cell at `pos` and paints its background.
+    pub fn shade(&mut self, Pos(x, y): Pos, bg: Color) {
+        if let Some(cell) = self.get_mut(x, y) {
+            *cell = Cell {
+                ch: ' ',
+                fg: None,
+                bg: Some(bg),
+            };
+        }
+    }
 }
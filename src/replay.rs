@@ -0,0 +1,214 @@
+//! Recordings of games. Games play out the same from the same level, seed
+//! and inputs, so a replay is only those, with each input stamped with the
+//! step it was given on. Replays are saved as text:
+//!
+//! ```text
+//! breach-replay 1
+//! seed 42
+//! level 3           <- the number of rows of level text that follow
+//! #####
+//! # @ #
+//! #####
+//! 0 resize 80 24    <- the step, then the input
+//! 12 0 move 2 0
+//! end 40            <- how many steps the game ran for
+//! ```
+//!
+//! Player inputs are written the way they are sent over the network.
+
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    event::{Event, GameEvent},
+    map::MapError,
+    net::protocol::Message,
+    Game,
+};
+
+/// The version of the replay format, bumped when old replays can no longer be read.
+pub const VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Replay {
+    /// The text of the level that was played.
+    pub level: String,
+    pub seed: u64,
+    inputs: Vec<(u64, Event)>,
+    /// Steps the game ran for.
+    pub steps: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayError {
+    /// The file does not start with the replay header, or is of another version.
+    NotAReplay,
+    /// A line that could not be read, by its line number.
+    InvalidLine(usize),
+    /// The file ends before the level or the `end` line.
+    Truncated,
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::NotAReplay => write!(f, "not a breach replay of version {VERSION}"),
+            ReplayError::InvalidLine(line) => write!(f, "invalid line {line}"),
+            ReplayError::Truncated => write!(f, "the replay ends too soon"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl Replay {
+    #[must_use]
+    pub fn new(level: &str, seed: u64) -> Self {
+        Self {
+            level: level.to_string(),
+            seed,
+            inputs: vec![],
+            steps: 0,
+        }
+    }
+
+    /// Records the inputs given on step `step`. Inputs that only matter to
+    /// the terminal the game runs in, like opening the message log, are left out.
+    pub fn record<'a>(&mut self, step: u64, inputs: impl IntoIterator<Item = &'a Event>) {
+        for event in inputs {
+            if encode(event).is_some() {
+                self.inputs.push((step, event.clone()));
+            }
+        }
+        self.steps = self.steps.max(step + 1);
+    }
+
+    /// The inputs given on step `step`.
+    pub fn inputs(&self, step: u64) -> impl Iterator<Item = Event> + '_ {
+        let start = self.inputs.partition_point(|(s, _)| *s < step);
+        self.inputs[start..]
+            .iter()
+            .take_while(move |(s, _)| *s == step)
+            .map(|(_, event)| event.clone())
+    }
+
+    /// A new game of the recorded level and seed.
+    ///
+    /// # Errors
+    ///
+    /// If the recorded level cannot be read as a map.
+    pub fn game(&self) -> Result<Game, MapError> {
+        Game::new(&self.level, self.seed)
+    }
+
+    /// Plays the whole replay out without a terminal.
+    ///
+    /// # Errors
+    ///
+    /// If the recorded level cannot be read as a map.
+    pub fn play(&self) -> Result<Game, MapError> {
+        let mut game = self.game()?;
+        while game.tick() < self.steps {
+            game.step(self.inputs(game.tick()));
+        }
+        Ok(game)
+    }
+}
+
+/// How an input is written in a replay, if it is recorded at all.
+fn encode(event: &Event) -> Option<String> {
+    let line = match event {
+        Event::Game(GameEvent::Pause) => "pause".to_string(),
+        Event::Game(GameEvent::Play) => "play".to_string(),
+        Event::Game(GameEvent::Select) => "select".to_string(),
+        Event::Game(GameEvent::Back) => "back".to_string(),
+        Event::Game(GameEvent::Resize(w, h)) => format!("resize {w} {h}"),
+        // By code point, so that binding the space bar survives the line being split.
+        Event::Game(GameEvent::Bind(key)) => format!("bind {}", u32::from(*key)),
+        Event::Game(GameEvent::ToggleLog | GameEvent::ScrollLog(_) | GameEvent::Quit) => {
+            return None
+        }
+        event => Message::Event(event.clone()).encode()?,
+    };
+
+    Some(line)
+}
+
+fn decode(line: &str) -> Option<Event> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    let event = match parts.as_slice() {
+        ["pause"] => Event::Game(GameEvent::Pause),
+        ["play"] => Event::Game(GameEvent::Play),
+        ["select"] => Event::Game(GameEvent::Select),
+        ["back"] => Event::Game(GameEvent::Back),
+        ["resize", w, h] => Event::Game(GameEvent::Resize(w.parse().ok()?, h.parse().ok()?)),
+        ["bind", key] => Event::Game(GameEvent::Bind(char::from_u32(key.parse().ok()?)?)),
+        _ => match line.parse().ok()? {
+            Message::Event(event) => event,
+            Message::Welcome(_) => return None,
+        },
+    };
+
+    Some(event)
+}
+
+impl Display for Replay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "breach-replay {VERSION}")?;
+        writeln!(f, "seed {}", self.seed)?;
+        writeln!(f, "level {}", self.level.lines().count())?;
+        for line in self.level.lines() {
+            writeln!(f, "{line}")?;
+        }
+        for (step, event) in &self.inputs {
+            if let Some(line) = encode(event) {
+                writeln!(f, "{step} {line}")?;
+            }
+        }
+        writeln!(f, "end {}", self.steps)
+    }
+}
+
+impl FromStr for Replay {
+    type Err = ReplayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().enumerate().map(|(n, line)| (n + 1, line));
+        let mut next = || lines.next().ok_or(ReplayError::Truncated);
+
+        let (_, header) = next()?;
+        if header != format!("breach-replay {VERSION}") {
+            return Err(ReplayError::NotAReplay);
+        }
+
+        let (n, line) = next()?;
+        let seed = line
+            .strip_prefix("seed ")
+            .and_then(|seed| seed.parse().ok())
+            .ok_or(ReplayError::InvalidLine(n))?;
+
+        let (n, line) = next()?;
+        let rows: usize = line
+            .strip_prefix("level ")
+            .and_then(|rows| rows.parse().ok())
+            .ok_or(ReplayError::InvalidLine(n))?;
+        let mut level = vec![];
+        for _ in 0..rows {
+            level.push(next()?.1);
+        }
+
+        let mut replay = Replay::new(&level.join("\n"), seed);
+        loop {
+            let (n, line) = next()?;
+            if let Some(steps) = line.strip_prefix("end ") {
+                replay.steps = steps.parse().map_err(|_| ReplayError::InvalidLine(n))?;
+                return Ok(replay);
+            }
+
+            let (step, event) = line
+                .split_once(' ')
+                .and_then(|(step, event)| Some((step.parse().ok()?, decode(event)?)))
+                .ok_or(ReplayError::InvalidLine(n))?;
+            replay.inputs.push((step, event));
+        }
+    }
+}
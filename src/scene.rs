@@ -0,0 +1,371 @@
+//! The screens of the game, kept on a stack. The top scene gets the input
+//! and decides what is drawn: the game itself while playing, and a menu over
+//! or instead of it otherwise. Menus are navigated with the movement keys or
+//! the mouse, and pressing enter or clicking picks the highlighted item.
+
+use crate::{
+    geometry::{Pos, Rect},
+    map::{levels, Map},
+    render::{Color, Frame},
+    score::Stats,
+    scoreboard::Scoreboard,
+    settings::{Bind, Setting, Settings},
+    theme::Role,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scene {
+    MainMenu,
+    LevelSelect,
+    /// The leaderboard of every level that has been cleared.
+    HighScores,
+    Settings,
+    /// Waiting for the key to put an action on, from the settings.
+    Rebind(Bind),
+    Credits,
+    Play,
+    /// The game, frozen under a menu.
+    Pause,
+    /// Every player has died.
+    GameOver,
+    /// Every enemy has been killed, with the stats the level ended on.
+    Cleared(Stats),
+}
+
+/// What picking a menu item does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Goes back to the game that is under the menus.
+    Resume,
+    /// Opens another scene on top of this one.
+    Open(Scene),
+    /// Starts the built in level at this index into `levels::LEVELS`.
+    Level(usize),
+    /// Starts the current level over.
+    Retry,
+    Change(Setting),
+    /// Asks for the key to put an action on.
+    Rebind(Bind),
+    MainMenu,
+    Back,
+    Quit,
+}
+
+/// A line of a menu. Lines without an action are only there to be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Item {
+    pub label: String,
+    pub action: Option<Action>,
+}
+
+impl Item {
+    fn new(label: impl Into<String>, action: Action) -> Self {
+        Self {
+            label: label.into(),
+            action: Some(action),
+        }
+    }
+
+    fn text(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            action: None,
+        }
+    }
+}
+
+/// The keys of the game that cannot be rebound, as listed on the settings screen.
+const FIXED_KEYS: [(&str, &str); 5] = [
+    ("arrows", "move"),
+    ("mouse", "aim"),
+    ("click", "shoot"),
+    ("esc", "back"),
+    ("enter", "pick"),
+];
+
+const CREDITS: [&str; 3] = [
+    "breach",
+    "by Damoon Rashidi and contributors",
+    "made with crossterm",
+];
+
+impl Scene {
+    /// Whether the game is drawn under this scene.
+    #[must_use]
+    pub fn shows_game(self) -> bool {
+        matches!(
+            self,
+            Scene::Play | Scene::Pause | Scene::GameOver | Scene::Cleared(_)
+        )
+    }
+
+    #[must_use]
+    pub fn title(self) -> &'static str {
+        match self {
+            Scene::MainMenu => "BREACH",
+            Scene::LevelSelect => "Select level",
+            Scene::HighScores => "High scores",
+            Scene::Settings => "Settings",
+            Scene::Rebind(_) => "Rebind",
+            Scene::Credits => "Credits",
+            Scene::Play => "",
+            Scene::Pause => "Paused",
+            Scene::GameOver => "Game over",
+            Scene::Cleared(_) => "Level cleared",
+        }
+    }
+
+    /// The lines of the menu of this scene, none while playing.
+    #[must_use]
+    pub fn items(self, settings: &Settings, scoreboard: &Scoreboard) -> Vec<Item> {
+        match self {
+            Scene::Play => vec![],
+            Scene::MainMenu => vec![
+                Item::new("Play", Action::Level(0)),
+                Item::new("Select level", Action::Open(Scene::LevelSelect)),
+                Item::new("High scores", Action::Open(Scene::HighScores)),
+                Item::new("Settings", Action::Open(Scene::Settings)),
+                Item::new("Credits", Action::Open(Scene::Credits)),
+                Item::new("Quit", Action::Quit),
+            ],
+            Scene::LevelSelect => levels::LEVELS
+                .iter()
+                .enumerate()
+                .map(|(i, (name, text))| {
+                    let title = Map::try_from(*text).map(|map| map.name).unwrap_or_default();
+                    let label = if title.is_empty() {
+                        (*name).to_string()
+                    } else {
+                        format!("{} · {title}", i + 1)
+                    };
+                    Item::new(label, Action::Level(i))
+                })
+                .chain([Item::text(""), Item::new("Back", Action::Back)])
+                .collect(),
+            Scene::HighScores => {
+                let mut lines = scoreboard.leaderboard();
+                if lines.is_empty() {
+                    lines.push("No level has been cleared yet".to_string());
+                }
+                lines
+                    .into_iter()
+                    .map(Item::text)
+                    .chain([Item::text(""), Item::new("Back", Action::Back)])
+                    .collect()
+            }
+            Scene::Settings => {
+                let setting = |name, setting| {
+                    let label = format!("{name:<14}< {} >", settings.value(setting));
+                    Item::new(label, Action::Change(setting))
+                };
+                let mut items = vec![
+                    setting("Theme", Setting::Theme),
+                    setting("Screen shake", Setting::ScreenShake),
+                    setting("FOV shading", Setting::FovShading),
+                    Item::text(""),
+                ];
+                items.extend(Bind::ALL.iter().map(|bind| {
+                    let label = format!("{:<16}[ {} ]", bind.name(), settings.keys.label(*bind));
+                    Item::new(label, Action::Rebind(*bind))
+                }));
+                items.push(Item::text(""));
+                items.extend(
+                    FIXED_KEYS
+                        .iter()
+                        .map(|(key, what)| Item::text(format!("{what:<16}{key}"))),
+                );
+                items.extend([Item::text(""), Item::new("Back", Action::Back)]);
+                items
+            }
+            Scene::Rebind(bind) => vec![
+                Item::text(format!("Press the key to {} with", bind.name())),
+                Item::text(""),
+                Item::new("Cancel", Action::Back),
+            ],
+            Scene::Credits => CREDITS
+                .iter()
+                .map(|line| Item::text(*line))
+                .chain([Item::text(""), Item::new("Back", Action::Back)])
+                .collect(),
+            Scene::Pause => vec![
+                Item::new("Resume", Action::Resume),
+                Item::new("Settings", Action::Open(Scene::Settings)),
+                Item::new("Main menu", Action::MainMenu),
+                Item::new("Quit", Action::Quit),
+            ],
+            Scene::GameOver => vec![
+                Item::new("Try again", Action::Retry),
+                Item::new("Select level", Action::Open(Scene::LevelSelect)),
+                Item::new("Main menu", Action::MainMenu),
+                Item::new("Quit", Action::Quit),
+            ],
+            Scene::Cleared(stats) => stats
+                .summary()
+                .into_iter()
+                .map(Item::text)
+                .chain([
+                    Item::text(""),
+                    Item::new("Play again", Action::Retry),
+                    Item::new("Select level", Action::Open(Scene::LevelSelect)),
+                    Item::new("Main menu", Action::MainMenu),
+                    Item::new("Quit", Action::Quit),
+                ])
+                .collect(),
+        }
+    }
+}
+
+/// Where a menu of `items` goes on `canvas`: the box it is drawn in, with
+/// the title on its first row and the items from its third row down.
+#[must_use]
+pub fn layout(items: &[Item], title: &str, canvas: &Rect) -> Rect {
+    let widest = items
+        .iter()
+        .map(|item| item.label.chars().count())
+        .chain([title.chars().count()])
+        .max()
+        .unwrap_or(0);
+    let w = widest as f32 + 8.;
+    let h = items.len() as f32 + 4.;
+    let x = ((canvas.w - w) / 2.).floor().max(0.);
+    let y = ((canvas.h - h) / 2.).floor().max(0.);
+
+    Rect::new(&Pos(x, y), w, h)
+}
+
+/// The scenes that are open, each with the item its menu has highlighted.
+/// There is always at least one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scenes {
+    stack: Vec<(Scene, usize)>,
+}
+
+impl Scenes {
+    #[must_use]
+    pub fn new(scene: Scene) -> Self {
+        Self {
+            stack: vec![(scene, 0)],
+        }
+    }
+
+    #[must_use]
+    pub fn top(&self) -> Scene {
+        self.stack.last().map_or(Scene::Play, |(scene, _)| *scene)
+    }
+
+    #[must_use]
+    pub fn is_playing(&self) -> bool {
+        self.top() == Scene::Play
+    }
+
+    /// Whether `scene` is open, on top or under other scenes.
+    #[must_use]
+    pub fn contains(&self, scene: Scene) -> bool {
+        self.stack.iter().any(|(s, _)| *s == scene)
+    }
+
+    pub fn push(&mut self, scene: Scene) {
+        self.stack.push((scene, 0));
+    }
+
+    /// Closes the top scene, unless it is the only one.
+    pub fn pop(&mut self) {
+        if self.stack.len() > 1 {
+            self.stack.pop();
+        }
+    }
+
+    /// Closes every scene and opens `scene`.
+    pub fn reset(&mut self, scene: Scene) {
+        self.stack = vec![(scene, 0)];
+    }
+
+    /// The highlighted item of the top scene.
+    #[must_use]
+    pub fn selected(&self) -> usize {
+        self.stack.last().map_or(0, |(_, selected)| *selected)
+    }
+
+    pub fn select(&mut self, i: usize) {
+        if let Some((_, selected)) = self.stack.last_mut() {
+            *selected = i;
+        }
+    }
+
+    /// Moves the highlight `by` items down, or up if negative, skipping
+    /// items that cannot be picked and wrapping around at either end.
+    pub fn step(&mut self, items: &[Item], by: isize) {
+        let n = items.len();
+        if n == 0 || items.iter().all(|item| item.action.is_none()) {
+            return;
+        }
+
+        let mut i = self.selected();
+        loop {
+            i = (i as isize + by).rem_euclid(n as isize) as usize;
+            if items[i].action.is_some() {
+                break;
+            }
+        }
+        self.select(i);
+    }
+
+    /// Draws the menu of the top scene in a box in the middle of `canvas`.
+    pub fn render(
+        &self,
+        settings: &Settings,
+        scoreboard: &Scoreboard,
+        canvas: &Rect,
+        frame: &mut Frame,
+    ) {
+        let scene = self.top();
+        let items = scene.items(settings, scoreboard);
+        if items.is_empty() {
+            return;
+        }
+
+        let area = layout(&items, scene.title(), canvas);
+        let fg = Color::Role(Role::Hud);
+        let blank = " ".repeat(area.w as usize);
+        for y in 0..area.h as usize {
+            frame.print_colored(Pos(area.pos.0, area.pos.1 + y as f32), &blank, fg);
+        }
+
+        let title = scene.title();
+        let x = area.pos.0 + ((area.w - title.chars().count() as f32) / 2.).floor();
+        frame.print_colored(Pos(x, area.pos.1 + 1.), title, fg);
+
+        let selected = self.selected();
+        for (i, item) in items.iter().enumerate() {
+            let pos = Pos(area.pos.0 + 2., area.pos.1 + 3. + i as f32);
+            if i == selected && item.action.is_some() {
+                let highlight = Color::Role(Role::Crosshair);
+                frame.print_colored(pos, &format!("> {}", item.label), highlight);
+            } else {
+                frame.print_colored(pos + Pos(2., 0.), &item.label, fg);
+            }
+        }
+    }
+
+    /// The item of the top scene drawn on the row at `pos`, if any.
+    #[must_use]
+    pub fn item_at(
+        &self,
+        settings: &Settings,
+        scoreboard: &Scoreboard,
+        canvas: &Rect,
+        pos: Pos,
+    ) -> Option<usize> {
+        let scene = self.top();
+        let items = scene.items(settings, scoreboard);
+        let area = layout(&items, scene.title(), canvas);
+        let row = pos.1.floor() - area.pos.1 - 3.;
+        let inside = pos.0 >= area.pos.0 && pos.0 < area.pos.0 + area.w;
+        if !inside || row < 0. {
+            return None;
+        }
+
+        let i = row as usize;
+        items.get(i)?.action.is_some().then_some(i)
+    }
+}
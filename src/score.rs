@@ -0,0 +1,88 @@
+//! How well a level is going: points for the enemies killed, multiplied by
+//! a combo for kills in quick succession, and the numbers a playtest is
+//! compared by, which are shown once the level is cleared.
+
+use crate::game::TICK;
+
+/// Frames a kill keeps the combo going for, two seconds.
+pub const COMBO_WINDOW: u32 = 500;
+
+/// The highest the combo multiplier goes.
+pub const MAX_COMBO: u32 = 5;
+
+/// The stats of the level being played, shared by every player in it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub score: u32,
+    pub kills: u32,
+    /// The highest combo multiplier reached.
+    pub best_combo: u32,
+    combo: u32,
+    /// The frame of the latest kill.
+    last_kill: Option<u32>,
+    /// Bullets fired, and how many of them hit something.
+    pub shots: u32,
+    pub hits: u32,
+    /// Health lost by players, to enemies and to what they stand in.
+    pub damage_taken: u32,
+    /// The frame the last enemy was killed on.
+    pub cleared_at: Option<u32>,
+}
+
+impl Stats {
+    /// Scores a kill worth `bounty` points on `frame`. A kill within
+    /// `COMBO_WINDOW` of the one before raises the multiplier by one, any
+    /// later kill starts it over.
+    pub fn kill(&mut self, bounty: u32, frame: u32) {
+        self.combo = if self.combo_at(frame) > 0 {
+            (self.combo + 1).min(MAX_COMBO)
+        } else {
+            1
+        };
+        self.best_combo = self.best_combo.max(self.combo);
+        self.last_kill = Some(frame);
+        self.kills += 1;
+        self.score += bounty * self.combo;
+    }
+
+    /// The multiplier the latest kills have built up by `frame`, 0 once the
+    /// window for the next kill has passed.
+    #[must_use]
+    pub fn combo_at(&self, frame: u32) -> u32 {
+        match self.last_kill {
+            Some(last) if frame.saturating_sub(last) <= COMBO_WINDOW => self.combo,
+            _ => 0,
+        }
+    }
+
+    /// The share of shots that hit, from 0 to 1, once any have been fired.
+    #[must_use]
+    pub fn accuracy(&self) -> Option<f32> {
+        (self.shots > 0).then(|| self.hits.min(self.shots) as f32 / self.shots as f32)
+    }
+
+    /// The lines of the summary shown once the level is cleared.
+    #[must_use]
+    pub fn summary(&self) -> Vec<String> {
+        let time = self.cleared_at.map_or_else(|| "-".to_string(), seconds);
+        let accuracy = self.accuracy().map_or("-".to_string(), |accuracy| {
+            format!("{:.0}% of {}", accuracy * 100., self.shots)
+        });
+        let rows = [
+            ("Score", self.score.to_string()),
+            ("Time", time),
+            ("Kills", self.kills.to_string()),
+            ("Best combo", format!("x{}", self.best_combo)),
+            ("Accuracy", accuracy),
+            ("Damage taken", self.damage_taken.to_string()),
+        ];
+
+        rows.iter()
+            .map(|(name, value)| format!("{name:<14}{value}"))
+            .collect()
+    }
+}
+
+fn seconds(frames: u32) -> String {
+    format!("{:.1}s", (TICK * frames).as_secs_f32())
+}
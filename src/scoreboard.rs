@@ -0,0 +1,272 @@
+//! The runs played on this computer, kept across games for a leaderboard
+//! of each level. Scoreboards are saved as text, one run per line, with
+//! the fields of a run separated by tabs:
+//!
+//! ```text
+//! breach-scores 1
+//! # date  level  seed  outcome  score  time  player  replay
+//! 1760870400  First contact  0  cleared  300  2750  ada  /home/ada/run.replay
+//! ```
+//!
+//! The date is in seconds since the Unix epoch, the time in frames, and a
+//! run without a replay has `-` for one. Later versions only add fields at
+//! the end of a line, so scoreboards of any version can be read. They are
+//! only written back by a version at least as new as theirs, which keeps
+//! older builds from dropping fields they do not know of.
+
+use std::{env, fmt::Display, path::PathBuf, str::FromStr};
+
+use crate::game::TICK;
+
+/// The version of the scoreboard format, bumped when fields are added.
+pub const VERSION: u32 = 1;
+
+/// Runs shown for each level on the leaderboard.
+pub const TOP: usize = 5;
+
+/// A game of a level, from when it was started until it was cleared or lost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    /// When the run ended, in seconds since the Unix epoch.
+    pub date: u64,
+    /// The name of the level.
+    pub level: String,
+    /// The seed the game was started with.
+    pub seed: u64,
+    pub cleared: bool,
+    pub score: u32,
+    /// Frames the run took.
+    pub time: u32,
+    pub player: String,
+    /// Where the game was recorded, if it was.
+    pub replay: Option<PathBuf>,
+}
+
+/// Every run played, in the order they ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scoreboard {
+    /// The version the scoreboard was written with.
+    pub version: u32,
+    pub runs: Vec<Run>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScoreboardError {
+    /// The file does not start with the scoreboard header.
+    NotAScoreboard,
+    /// A line that could not be read, by its line number.
+    InvalidLine(usize),
+}
+
+impl Display for ScoreboardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScoreboardError::NotAScoreboard => write!(f, "not a breach scoreboard"),
+            ScoreboardError::InvalidLine(line) => write!(f, "invalid line {line}"),
+        }
+    }
+}
+
+impl std::error::Error for ScoreboardError {}
+
+impl Run {
+    /// How long the run took, in seconds.
+    #[must_use]
+    pub fn seconds(&self) -> f32 {
+        (TICK * self.time).as_secs_f32()
+    }
+}
+
+impl Default for Scoreboard {
+    fn default() -> Self {
+        Self {
+            version: VERSION,
+            runs: vec![],
+        }
+    }
+}
+
+impl Scoreboard {
+    pub fn add(&mut self, run: Run) {
+        self.runs.push(run);
+    }
+
+    /// Whether this build can save the scoreboard without losing anything
+    /// a newer build wrote to it.
+    #[must_use]
+    pub fn is_writable(&self) -> bool {
+        self.version <= VERSION
+    }
+
+    /// The levels that have been cleared, in the order they first were.
+    #[must_use]
+    pub fn levels(&self) -> Vec<&str> {
+        let mut levels: Vec<&str> = vec![];
+        for run in self.runs.iter().filter(|run| run.cleared) {
+            if !levels.contains(&run.level.as_str()) {
+                levels.push(&run.level);
+            }
+        }
+        levels
+    }
+
+    /// The runs that cleared `level`, best first: by score, then by the
+    /// quickest, then by the earliest.
+    #[must_use]
+    pub fn best(&self, level: &str) -> Vec<&Run> {
+        let mut runs: Vec<_> = self
+            .runs
+            .iter()
+            .filter(|run| run.cleared && run.level == level)
+            .collect();
+        runs.sort_by_key(|run| (std::cmp::Reverse(run.score), run.time, run.date));
+        runs
+    }
+
+    /// The lines of the leaderboard, with the best runs of each level under
+    /// its name.
+    #[must_use]
+    pub fn leaderboard(&self) -> Vec<String> {
+        let mut lines = vec![];
+        for level in self.levels() {
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            lines.push(level.to_string());
+            lines.extend(
+                self.best(level)
+                    .iter()
+                    .take(TOP)
+                    .enumerate()
+                    .map(|(i, run)| {
+                        format!(
+                            "{:>2}. {:>6}  {:>6.1}s  {:<12}  {}  seed {}",
+                            i + 1,
+                            run.score,
+                            run.seconds(),
+                            run.player,
+                            date(run.date),
+                            run.seed
+                        )
+                    }),
+            );
+        }
+        lines
+    }
+}
+
+/// The day `secs` seconds after the Unix epoch falls on, as year-month-day.
+#[must_use]
+pub fn date(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Where the scoreboard is kept by default: `breach/scores.txt` in the data
+/// directory of the user, if it can be found from the environment.
+#[must_use]
+pub fn default_path() -> Option<PathBuf> {
+    let var = |name| env::var_os(name).filter(|value| !value.is_empty());
+    let dir = if cfg!(windows) {
+        var("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        var("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        var("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| var("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+    }?;
+
+    Some(dir.join("breach").join("scores.txt"))
+}
+
+/// `field` with the tabs and line breaks that would split it replaced by spaces.
+fn clean(field: &str) -> String {
+    field.replace(['\t', '\n', '\r'], " ")
+}
+
+impl Display for Scoreboard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "breach-scores {VERSION}")?;
+        writeln!(
+            f,
+            "# date\tlevel\tseed\toutcome\tscore\ttime\tplayer\treplay"
+        )?;
+        for run in &self.runs {
+            let replay = run
+                .replay
+                .as_ref()
+                .map_or("-".to_string(), |path| clean(&path.display().to_string()));
+            writeln!(
+                f,
+                "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{replay}",
+                run.date,
+                clean(&run.level),
+                run.seed,
+                if run.cleared { "cleared" } else { "lost" },
+                run.score,
+                run.time,
+                clean(&run.player),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Scoreboard {
+    type Err = ScoreboardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().enumerate().map(|(n, line)| (n + 1, line));
+        let version = lines
+            .next()
+            .and_then(|(_, header)| header.strip_prefix("breach-scores "))
+            .and_then(|version| version.parse().ok())
+            .ok_or(ScoreboardError::NotAScoreboard)?;
+
+        let mut runs = vec![];
+        for (n, line) in lines {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            runs.push(run(line).ok_or(ScoreboardError::InvalidLine(n))?);
+        }
+
+        Ok(Self { version, runs })
+    }
+}
+
+/// The run on a line of a scoreboard, leaving out any fields after those
+/// this version knows of.
+fn run(line: &str) -> Option<Run> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    let [date, level, seed, outcome, score, time, player, replay, ..] = fields.as_slice() else {
+        return None;
+    };
+    let cleared = match *outcome {
+        "cleared" => true,
+        "lost" => false,
+        _ => return None,
+    };
+
+    Some(Run {
+        date: date.parse().ok()?,
+        level: (*level).to_string(),
+        seed: seed.parse().ok()?,
+        cleared,
+        score: score.parse().ok()?,
+        time: time.parse().ok()?,
+        player: (*player).to_string(),
+        replay: (*replay != "-").then(|| PathBuf::from(replay)),
+    })
+}
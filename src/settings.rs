@@ -0,0 +1,155 @@
+use crate::theme::Theme;
+
+/// Options the player can change from the settings screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settings {
+    pub theme: Theme,
+    /// Whether explosions shake the screen.
+    pub screen_shake: bool,
+    /// Whether the cone each player is looking in is shaded.
+    pub fov_shading: bool,
+    /// The letters the actions of the game are on.
+    pub keys: Keys,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            theme: Theme::default(),
+            screen_shake: true,
+            fov_shading: true,
+            keys: Keys::default(),
+        }
+    }
+}
+
+/// A single option in `Settings`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Setting {
+    Theme,
+    ScreenShake,
+    FovShading,
+}
+
+impl Settings {
+    /// Moves `setting` on to its next value, or its previous one if not `forward`.
+    pub fn change(&mut self, setting: Setting, forward: bool) {
+        match setting {
+            Setting::Theme => {
+                let themes = Theme::ALL;
+                let i = themes.iter().position(|t| *t == self.theme).unwrap_or(0);
+                let next = if forward { i + 1 } else { i + themes.len() - 1 };
+                self.theme = themes[next % themes.len()];
+            }
+            Setting::ScreenShake => self.screen_shake = !self.screen_shake,
+            Setting::FovShading => self.fov_shading = !self.fov_shading,
+        }
+    }
+
+    /// The value of `setting`, the way it is shown on the settings screen.
+    #[must_use]
+    pub fn value(&self, setting: Setting) -> &'static str {
+        let on_off = |on| if on { "on" } else { "off" };
+
+        match setting {
+            Setting::Theme => self.theme.name,
+            Setting::ScreenShake => on_off(self.screen_shake),
+            Setting::FovShading => on_off(self.fov_shading),
+        }
+    }
+}
+
+/// What a key can be bound to. The arrows, escape, enter and the mouse
+/// always do what they do, on top of the keys bound to their actions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bind {
+    Up,
+    Left,
+    Down,
+    Right,
+    Blink,
+    Grenade,
+    Medkit,
+    SwitchWeapon,
+    Log,
+    Pause,
+    Quit,
+}
+
+impl Bind {
+    pub const ALL: [Bind; 11] = [
+        Bind::Up,
+        Bind::Left,
+        Bind::Down,
+        Bind::Right,
+        Bind::Blink,
+        Bind::Grenade,
+        Bind::Medkit,
+        Bind::SwitchWeapon,
+        Bind::Log,
+        Bind::Pause,
+        Bind::Quit,
+    ];
+
+    /// What the bound key does, the way it is shown on the settings screen.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Bind::Up => "move up",
+            Bind::Left => "move left",
+            Bind::Down => "move down",
+            Bind::Right => "move right",
+            Bind::Blink => "blink",
+            Bind::Grenade => "grenade",
+            Bind::Medkit => "use a medkit",
+            Bind::SwitchWeapon => "switch weapons",
+            Bind::Log => "message log",
+            Bind::Pause => "pause, back",
+            Bind::Quit => "quit",
+        }
+    }
+}
+
+/// The key each `Bind` is on, in the order of `Bind::ALL`. No key is on
+/// more than one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Keys([char; 11]);
+
+impl Default for Keys {
+    fn default() -> Self {
+        Self(['w', 'a', 's', 'd', 'e', 'g', 'h', 'x', 'l', 'p', 'q'])
+    }
+}
+
+impl Keys {
+    #[must_use]
+    pub fn key(&self, bind: Bind) -> char {
+        self.0[bind as usize]
+    }
+
+    /// What `key` is bound to, if anything.
+    #[must_use]
+    pub fn bind_of(&self, key: char) -> Option<Bind> {
+        let i = self.0.iter().position(|k| *k == key)?;
+        Some(Bind::ALL[i])
+    }
+
+    /// Puts `bind` on `key`. Whatever was on `key` before takes the key
+    /// `bind` was on, so that no key does two things.
+    pub fn bind(&mut self, bind: Bind, key: char) {
+        let old = self.key(bind);
+        if let Some(other) = self.bind_of(key) {
+            self.0[other as usize] = old;
+        }
+        self.0[bind as usize] = key;
+    }
+
+    /// How the key `bind` is on is shown on the settings screen.
+    #[must_use]
+    pub fn label(&self, bind: Bind) -> String {
+        match self.key(bind) {
+            ' ' => "space".to_string(),
+            key => key.to_string(),
+        }
+    }
+}
@@ -1,193 +1,594 @@
-use std::{cell::RefCell, error::Error, fmt::Display, io::Write};
-
-use crossterm::{
-    cursor::MoveTo,
-    style::{Print, ResetColor, SetBackgroundColor, SetForegroundColor},
-    terminal::Clear,
+use std::{
+    collections::{BTreeSet, VecDeque},
+    fmt::Display,
 };
 
 use crate::{
     entity::{
-        effect::Effect, enemies::Goblo, enemy::Enemy, player::Player, projectile::Projectile,
-        Entity,
+        components::{Health, Position, Velocity},
+        effects::{self, blink, muzzle},
+        enemies,
+        particles::Emitter,
+        pickups,
+        player::{self, Ability, PlayerId, MEDKIT_HP},
+        projectiles,
+        status::StatusKind,
+        EntityId, EntityKind,
     },
-    geometry::Pos,
-    map::Map,
-    render::Render,
+    event::{Event, GameEvent, PlayerEvent},
+    geometry::{Pos, Rect},
+    hud::Hud,
+    log::{Category, MessageLog},
+    map::{levels, Map},
+    net::snapshot::{EntityState, Snapshot},
+    render::{Color, Frame},
+    rng::Rng,
+    scene::{Action, Scene, Scenes},
+    score::Stats,
+    scoreboard::Scoreboard,
+    settings::Settings,
+    systems::{self, collision::Broadphase},
+    theme::Role,
+    world::{Commands, World},
 };
 
-#[derive(Debug, PartialEq)]
-pub enum GameMode {
-    Play,
-    Pause,
+/// Spawns the enemies and pickups placed in `map`.
+fn populate(world: &mut World, map: &Map) {
+    for spawn in &map.spawns {
+        match spawn.kind {
+            EntityKind::Goblo => {
+                world.spawn(enemies::goblo(spawn.pos));
+            }
+            EntityKind::Pickup(kind) => {
+                world.spawn(pickups::bundle(kind, spawn.pos));
+            }
+            _ => {}
+        }
+    }
 }
+
+/// How far off, in radians, a shot may go from where the player is aiming.
+const SPREAD: f32 = 0.05;
+
+/// How far, in rows, a blink takes the player.
+const BLINK_DISTANCE: f32 = 5.;
+
+/// The furthest a single `PlayerEvent::Move` goes on either axis, a step
+/// of one cell across and one row down.
+const MAX_STEP: Pos = Pos(2., 1.);
+
 #[derive(Debug)]
 pub struct State {
-    pub mode: RefCell<GameMode>,
-    pub map: RefCell<Map>,
-    pub canvas: crate::geometry::Rect,
-    pub player: RefCell<crate::entity::player::Player>,
-    pub enemies: RefCell<Vec<RefCell<Box<dyn crate::entity::enemy::Enemy>>>>,
-    pub projectiles: RefCell<Vec<RefCell<Box<dyn crate::entity::projectile::Projectile>>>>,
-    pub effects: RefCell<Vec<RefCell<Box<dyn crate::entity::effect::Effect>>>>,
-    pub log: RefCell<Option<String>>,
+    pub scenes: Scenes,
+    pub settings: Settings,
+    /// Set once a player picks quit from a menu, for the caller to act on.
+    pub quit: bool,
+    /// Whether other players run the same game elsewhere. A networked game
+    /// cannot stop for one of them, so menus are drawn over it while it
+    /// goes on, and every player event is applied to the world.
+    pub networked: bool,
+    pub map: Map,
+    /// The map as it was loaded, to start the level over from.
+    level: Map,
+    pub canvas: Rect,
+    pub world: World,
+    pub log: MessageLog,
+    pub hud: Hud,
+    /// How the level is going, from when it was loaded.
+    pub stats: Stats,
+    /// The runs played before, for the high scores menu.
+    pub scoreboard: Scoreboard,
+    pub rng: Rng,
+    broadphase: Broadphase,
 }
 
 impl State {
     #[must_use]
-    pub fn new(canvas: crate::geometry::Rect) -> Self {
-        let map = Map::from(include_str!("map/levels/level_1.txt"));
-        let player_pos = map.start_pos;
+    pub fn new(map: Map, canvas: Rect, seed: u64) -> Self {
+        let mut world = World::default();
+        populate(&mut world, &map);
 
         Self {
-            mode: RefCell::new(GameMode::Play),
-            map: RefCell::new(map),
+            scenes: Scenes::new(Scene::Play),
+            settings: Settings::default(),
+            quit: false,
+            networked: false,
+            broadphase: Broadphase::new(&map),
+            world,
+            level: map.clone(),
+            map,
             canvas,
-            player: RefCell::new(Player::new(player_pos)),
-            enemies: RefCell::new(vec![RefCell::new(Box::new(Goblo::new(Pos(10., 10.))))]),
-            projectiles: RefCell::new(vec![]),
-            effects: RefCell::new(vec![]),
-            log: RefCell::new(None),
+            log: MessageLog::default(),
+            hud: Hud::default(),
+            stats: Stats::default(),
+            scoreboard: Scoreboard::default(),
+            rng: Rng::new(seed),
         }
     }
 
+    /// Closes the pause menu and anything opened from it.
     pub fn play(&mut self) {
-        self.mode = RefCell::new(GameMode::Play);
+        if self.scenes.contains(Scene::Pause) {
+            self.scenes.reset(Scene::Play);
+        }
     }
 
+    /// Opens the pause menu while playing.
     pub fn pause(&mut self) {
-        self.mode = RefCell::new(GameMode::Pause);
+        if self.scenes.is_playing() {
+            self.open(Scene::Pause);
+        }
     }
 
-    pub fn log(&self, msg: impl Display) {
-        *self.log.borrow_mut() = Some(msg.to_string());
+    /// Opens `scene` on top of the others, highlighting the first item that can be picked.
+    pub fn open(&mut self, scene: Scene) {
+        self.scenes.push(scene);
+        let items = scene.items(&self.settings, &self.scoreboard);
+        if items.first().is_some_and(|item| item.action.is_none()) {
+            self.scenes.step(&items, 1);
+        }
     }
 
-    pub fn spawn_enemy(&self, enemy: impl Enemy + 'static) {
-        self.enemies
-            .borrow_mut()
-            .push(RefCell::new(Box::new(enemy)));
+    /// Replaces the level with `map`, bringing along the players in it.
+    pub fn load(&mut self, map: Map) {
+        let players: Vec<_> = self.world.players.values().map(|p| p.id).collect();
+
+        self.world.clear();
+        populate(&mut self.world, &map);
+        self.stats = Stats::default();
+        self.broadphase = Broadphase::new(&map);
+        self.level = map.clone();
+        self.map = map;
+        for id in players {
+            self.spawn_player(id);
+        }
+        if !self.map.name.is_empty() {
+            self.log(Category::System, format!("entered {}", self.map.name));
+        }
     }
 
-    pub fn spawn_projectile(&self, projectile: impl Projectile + 'static) {
-        self.projectiles
-            .borrow_mut()
-            .push(RefCell::new(Box::new(projectile)));
-    }
+    pub fn add_player(&mut self, id: PlayerId) {
+        if self.world.player(id).is_some() {
+            return;
+        }
 
-    pub fn spawn_effect(&self, effect: impl Effect + 'static) {
-        self.effects
-            .borrow_mut()
-            .push(RefCell::new(Box::new(effect)));
+        self.spawn_player(id);
+        self.log(Category::System, format!("player {id} joined"));
     }
 
-    pub fn frame(&self) {
-        self.player.borrow_mut().update(self);
+    fn spawn_player(&mut self, id: PlayerId) {
+        let mut bundle = player::bundle(id, self.map.start_pos);
+        if let Some(hitbox) = bundle.hitbox {
+            let pos = self.spawn_point(hitbox.w, hitbox.h);
+            bundle.position = Some(Position(pos));
+        }
+        self.world.spawn(bundle);
+    }
 
-        for enemy in self.enemies.borrow_mut().iter() {
-            enemy.borrow_mut().update(self);
+    /// The spot closest to the start, walking from it one cell at a time,
+    /// where a `w` by `h` player fits without touching a wall, the edge of
+    /// the map or another player. The start itself if there is none.
+    fn spawn_point(&self, w: f32, h: f32) -> Pos {
+        let start = self.map.start_pos;
+        let bounds = &self.map.bounds;
+        let at = |(dx, dy): (i32, i32)| Rect::new(&(start + Pos(dx as f32, dy as f32)), w, h);
+        let fits = |rect: &Rect| {
+            rect.pos.0 >= 0.
+                && rect.pos.1 >= 0.
+                && rect.pos.0 + rect.w <= bounds.w
+                && rect.pos.1 + rect.h <= bounds.h
+                && !self.map.blocks(rect)
+        };
+        let taken = |rect: &Rect| {
+            self.world
+                .players
+                .keys()
+                .filter_map(|id| self.world.hitbox(*id))
+                .any(|other| other.intersects(rect))
+        };
+
+        let mut seen = BTreeSet::from([(0, 0)]);
+        let mut queue = VecDeque::from([(0, 0)]);
+        while let Some((dx, dy)) = queue.pop_front() {
+            let rect = at((dx, dy));
+            if fits(&rect) && !taken(&rect) {
+                return rect.pos;
+            }
+            for next in [(dx + 1, dy), (dx - 1, dy), (dx, dy + 1), (dx, dy - 1)] {
+                if fits(&at(next)) && seen.insert(next) {
+                    queue.push_back(next);
+                }
+            }
         }
 
-        for projectile in self.projectiles.borrow_mut().iter() {
-            projectile.borrow_mut().update(self);
+        start
+    }
+
+    pub fn remove_player(&mut self, id: PlayerId) {
+        if let Some(entity) = self.world.player(id) {
+            self.world.despawn(entity);
         }
+        self.log(Category::System, format!("player {id} left"));
+    }
 
-        for enemy in self.enemies.borrow_mut().iter() {
-            let mut enemy = enemy.borrow_mut();
-            let enemy_hitbox = enemy.hitbox();
+    /// Applies an event to the state. `GameEvent::Quit` is left to the caller.
+    /// While a menu is open, the events of the player the HUD is showing
+    /// navigate it, and those of other players are dropped. Networked games
+    /// apply player events either way, and leave menus to `navigate`.
+    pub fn handle(&mut self, event: Event) {
+        match event {
+            Event::Player(id, player_event) if self.scenes.is_playing() || self.networked => {
+                self.handle_player_event(id, &player_event);
+            }
+            Event::Player(id, player_event) => {
+                if id == self.hud.player {
+                    self.navigate(&player_event);
+                }
+            }
+            Event::Game(game_event) => match game_event {
+                GameEvent::Pause => self.pause(),
+                GameEvent::Play => self.play(),
+                GameEvent::Select => {
+                    let items = self.scenes.top().items(&self.settings, &self.scoreboard);
+                    if let Some(action) = items.get(self.scenes.selected()).and_then(|i| i.action) {
+                        self.act(action);
+                    }
+                }
+                GameEvent::Back => self.back(),
+                GameEvent::Resize(w, h) => {
+                    self.canvas = Rect::new(&Pos(0., 0.), f32::from(w), f32::from(h));
+                    if self.is_too_small() && !self.networked {
+                        self.pause();
+                    }
+                }
+                GameEvent::Join(id) => self.add_player(id),
+                GameEvent::Leave(id) => self.remove_player(id),
+                GameEvent::ToggleLog => self.log.toggle(),
+                GameEvent::ScrollLog(by) => self.log.scroll(by),
+                GameEvent::Bind(key) => {
+                    if let Scene::Rebind(bind) = self.scenes.top() {
+                        self.settings.keys.bind(bind, key);
+                        self.scenes.pop();
+                    }
+                }
+                GameEvent::Quit => {}
+            },
+        }
+    }
 
-            for projectile in self.projectiles.borrow_mut().iter() {
-                let mut projectile = projectile.borrow_mut();
-                if enemy_hitbox.intersects(&projectile.hitbox()) {
-                    enemy.on_hit(Box::new(projectile.as_ref()), self);
-                    projectile.on_hit(Box::new(enemy.as_ref()), self);
+    /// Moves the highlight of the open menu with the movement keys or the
+    /// mouse, changes settings with left and right, and picks on click.
+    pub fn navigate(&mut self, event: &PlayerEvent) {
+        let items = self.scenes.top().items(&self.settings, &self.scoreboard);
+        let action = items
+            .get(self.scenes.selected())
+            .and_then(|item| item.action);
+
+        match *event {
+            PlayerEvent::Move(_, y) if y != 0. => self.scenes.step(&items, y.signum() as isize),
+            PlayerEvent::Move(x, _) => {
+                if let Some(Action::Change(setting)) = action {
+                    self.settings.change(setting, x > 0.);
                 }
             }
+            PlayerEvent::Aim(pos) => {
+                if let Some(i) =
+                    self.scenes
+                        .item_at(&self.settings, &self.scoreboard, &self.canvas, pos)
+                {
+                    self.scenes.select(i);
+                }
+            }
+            PlayerEvent::Shoot => {
+                if let Some(action) = action {
+                    self.act(action);
+                }
+            }
+            PlayerEvent::Ability(_) | PlayerEvent::UseMedkit | PlayerEvent::SwitchWeapon => {}
         }
+    }
 
-        for effect in self.effects.borrow_mut().iter() {
-            effect.borrow_mut().update(self);
+    fn act(&mut self, action: Action) {
+        match action {
+            Action::Resume => self.play(),
+            Action::Open(scene) => self.open(scene),
+            Action::Level(i) => {
+                if let Some(Ok(map)) = levels::LEVELS.get(i).map(|(_, text)| Map::try_from(*text)) {
+                    self.load(map);
+                    self.scenes.reset(Scene::Play);
+                }
+            }
+            Action::Retry => {
+                self.load(self.level.clone());
+                self.scenes.reset(Scene::Play);
+            }
+            Action::Change(setting) => self.settings.change(setting, true),
+            Action::Rebind(bind) => self.open(Scene::Rebind(bind)),
+            Action::MainMenu => self.scenes.reset(Scene::MainMenu),
+            Action::Back => self.back(),
+            Action::Quit => self.quit = true,
         }
+    }
 
-        self.enemies
-            .borrow_mut()
-            .retain_mut(|enemy| enemy.borrow().is_alive());
+    /// Goes back a scene. Playing, that opens the pause menu, and there is
+    /// nothing to go back to from the main menu or once the game is over.
+    fn back(&mut self) {
+        match self.scenes.top() {
+            Scene::Play => self.pause(),
+            Scene::Pause => self.play(),
+            Scene::MainMenu | Scene::GameOver | Scene::Cleared(_) => {}
+            Scene::LevelSelect
+            | Scene::HighScores
+            | Scene::Settings
+            | Scene::Rebind(_)
+            | Scene::Credits => {
+                self.scenes.pop();
+            }
+        }
+    }
 
-        self.effects
-            .borrow_mut()
-            .retain_mut(|effect| !effect.borrow().is_done());
+    /// Whether the canvas is too small to show the whole level. The game is
+    /// paused when the canvas shrinks below that, unless it is networked and
+    /// cannot stop, which only shows the note asking for more room.
+    #[must_use]
+    pub fn is_too_small(&self) -> bool {
+        self.canvas.w < self.map.bounds.w || self.canvas.h < self.map.bounds.h
+    }
 
-        self.projectiles
-            .borrow_mut()
-            .retain_mut(|projectile| self.canvas.contains(projectile.borrow().pos()));
+    /// Whether every player has died.
+    #[must_use]
+    pub fn is_lost(&self) -> bool {
+        let world = &self.world;
+        !world.players.is_empty()
+            && world.players.keys().all(|id| {
+                world
+                    .healths
+                    .get(id)
+                    .is_none_or(|health| !health.is_alive())
+            })
     }
 
-    /**
-    Renders the entire game state for the current frame
+    /// Whether every enemy in the level has been killed.
+    #[must_use]
+    pub fn is_cleared(&self) -> bool {
+        self.world.ais.is_empty()
+    }
 
-    # Errors
-    Will error out if any of the objects cannot be rendered
-    */
-    pub fn render(&self) -> Result<(), Box<dyn Error>> {
-        let mut stdout = std::io::stdout();
+    fn handle_player_event(&mut self, id: PlayerId, event: &PlayerEvent) {
+        let Some(entity) = self.world.player(id) else {
+            return;
+        };
+        if !self
+            .world
+            .healths
+            .get(&entity)
+            .is_some_and(Health::is_alive)
+        {
+            return;
+        }
+        let Some(hitbox) = self.world.hitbox(entity) else {
+            return;
+        };
+        let aim = self.world.players.get(&entity).map_or(0., |p| p.aim);
+        let stunned = self
+            .world
+            .statuses
+            .get(&entity)
+            .is_some_and(|s| s.has(StatusKind::Stun));
+        if stunned && !matches!(event, PlayerEvent::Aim(_)) {
+            return;
+        }
+        let Some(player) = self.world.players.get_mut(&entity) else {
+            return;
+        };
+        let hurt = self
+            .world
+            .healths
+            .get(&entity)
+            .is_some_and(|health| health.hp < health.max);
+        let ready = match event {
+            PlayerEvent::Shoot => player.fire(),
+            PlayerEvent::Ability(ability) => player.use_ability(*ability),
+            PlayerEvent::UseMedkit => hurt && player.inventory.medkits > 0,
+            PlayerEvent::SwitchWeapon => player.switch_weapon(),
+            PlayerEvent::Move(x, y) | PlayerEvent::Aim(Pos(x, y)) => x.is_finite() && y.is_finite(),
+        };
+        if !ready {
+            return;
+        }
 
-        crossterm::queue!(stdout, Clear(crossterm::terminal::ClearType::All))?;
+        match *event {
+            PlayerEvent::Move(x, y) => {
+                let (x, y) = (
+                    x.clamp(-MAX_STEP.0, MAX_STEP.0),
+                    y.clamp(-MAX_STEP.1, MAX_STEP.1),
+                );
+                let target = Pos(
+                    (hitbox.pos.0 + x).clamp(0.0, self.canvas.w - hitbox.w),
+                    (hitbox.pos.1 + y).clamp(0.0, self.canvas.h - hitbox.h),
+                );
+                let (moved, _) = systems::movement::slide(&self.map, &hitbox, target - hitbox.pos);
+                self.world
+                    .positions
+                    .insert(entity, Position(hitbox.pos + moved));
+            }
+            PlayerEvent::Aim(pos) => {
+                if let Some(player) = self.world.players.get_mut(&entity) {
+                    player.aim = hitbox.pos.angle(&pos);
+                }
+            }
+            PlayerEvent::Shoot => {
+                self.stats.shots += 1;
+                let spread = self.rng.range(-SPREAD, SPREAD);
+                self.world
+                    .spawn(projectiles::bullet(hitbox.center(), aim + spread));
+                self.spawn_effect(hitbox.center(), muzzle::flash(aim + spread));
+            }
+            PlayerEvent::Ability(Ability::Grenade) => {
+                self.world.spawn(projectiles::grenade(hitbox.center(), aim));
+            }
+            PlayerEvent::Ability(Ability::Blink) => {
+                let delta = (Pos::from_angle(aim) * BLINK_DISTANCE).to_cells();
+                let moved = systems::movement::reach(&self.map, &hitbox, delta);
+                let c = hitbox.center();
+                for bundle in blink::bundles(c, c + moved) {
+                    self.world.spawn(bundle);
+                }
+                let Pos(x, y) = hitbox.pos + moved;
+                let pos = Pos(x.clamp(0., self.canvas.w), y.clamp(0., self.canvas.h));
+                self.world.positions.insert(entity, Position(pos));
+            }
+            PlayerEvent::UseMedkit => {
+                if let Some(player) = self.world.players.get_mut(&entity) {
+                    player.inventory.medkits -= 1;
+                }
+                if let Some(health) = self.world.healths.get_mut(&entity) {
+                    health.heal(MEDKIT_HP);
+                }
+            }
+            PlayerEvent::SwitchWeapon => {}
+        }
+    }
 
-        let player = self.player.borrow();
-        let fov = player.fov as i32;
-        let aim = player.aim;
+    /// Starts a particle effect at `pos`, which runs until its emitter is done.
+    pub fn spawn_effect(&mut self, pos: Pos, emitter: Emitter) -> EntityId {
+        self.world.spawn(effects::bundle(pos, emitter))
+    }
 
-        for a in (-fov / 2..fov / 2).step_by(2) {
-            let ray_angle = aim + (a as f32).to_radians();
+    /// Adds a message to the log, stamped with the current frame.
+    pub fn log(&mut self, category: Category, msg: impl Display) {
+        self.log.push(self.world.frames, category, msg);
+    }
 
-            for p in 1..=20 {
-                let x = player.pos.0 + ray_angle.cos() * p as f32;
-                let y = player.pos.1 + ray_angle.sin() * p as f32;
+    /// Runs the systems for a frame of play. Nothing happens while a menu is
+    /// open, unless the game is networked. The game over screen opens once
+    /// every player has died, and the summary of the level once the last of
+    /// its enemies is killed.
+    pub fn frame(&mut self) {
+        if !self.scenes.is_playing() && !self.networked {
+            return;
+        }
+        let mut commands = Commands::default();
+
+        systems::ai::chase(&mut self.world);
+        systems::physics::integrate(&mut self.world);
+        systems::movement::movement(&mut self.world, &self.map);
+        systems::physics::fuses(&mut self.world, &mut commands);
+        systems::combat::cooldowns(&mut self.world);
+        systems::status::tiles(&mut self.world, &self.map);
+        systems::status::tick(&mut self.world, &mut self.stats, &mut commands);
+        self.broadphase.update(&self.world);
+        let collisions = systems::collision::detect(&self.world, &self.broadphase);
+        systems::combat::resolve(&mut self.world, &collisions, &mut self.stats, &mut commands);
+        systems::pickups::collect(&mut self.world, &collisions, &mut commands);
+        systems::combat::deaths(&self.world, &mut self.rng, &mut self.stats, &mut commands);
+        systems::lifetime::age(&mut self.world, &mut commands);
+        systems::particles::emit(&mut self.world, &mut self.rng, &mut commands);
+        systems::movement::leave(&self.world, &self.canvas, &mut commands);
+
+        for (category, msg) in commands.take_messages() {
+            self.log(category, msg);
+        }
+        self.world.apply(commands);
+        self.world.frames += 1;
+        self.world.shake = self.world.shake.saturating_sub(1);
 
-                crossterm::queue!(
-                    stdout,
-                    MoveTo(x as u16, y as u16),
-                    SetBackgroundColor(crossterm::style::Color::Black),
-                    Print(' '),
-                    ResetColor,
-                )?;
+        if self.is_lost() {
+            if !self.scenes.contains(Scene::GameOver) {
+                self.open(Scene::GameOver);
             }
+        } else if self.is_cleared() && self.stats.cleared_at.is_none() && self.had_enemies() {
+            self.stats.cleared_at = Some(self.world.frames);
+            self.log(
+                Category::System,
+                format!("cleared with {} points", self.stats.score),
+            );
+            self.open(Scene::Cleared(self.stats));
         }
+    }
 
-        for enemy in self.enemies.borrow().iter() {
-            enemy.borrow().render(&mut stdout)?;
-        }
+    /// Whether the level started out with enemies to clear it of.
+    fn had_enemies(&self) -> bool {
+        self.level
+            .spawns
+            .iter()
+            .any(|spawn| spawn.kind == EntityKind::Goblo)
+    }
 
-        for projectile in self.projectiles.borrow().iter() {
-            projectile.borrow().render(&mut stdout)?;
+    /// Captures everything a remote client needs to draw the game at `tick`.
+    #[must_use]
+    pub fn snapshot(&self, tick: u64) -> Snapshot {
+        let entities = self
+            .world
+            .kinds
+            .iter()
+            .filter(|(_, kind)| **kind != EntityKind::Effect)
+            .filter_map(|(id, kind)| {
+                let Position(pos) = self.world.positions.get(id)?;
+                let angle = match (self.world.players.get(id), self.world.velocities.get(id)) {
+                    (Some(player), _) => player.aim,
+                    (None, Some(Velocity(Pos(x, y)))) if *kind == EntityKind::Bullet => y.atan2(*x),
+                    _ => 0.,
+                };
+
+                Some(EntityState::new(*id, *kind, *pos, angle))
+            });
+
+        Snapshot {
+            tick,
+            entities: entities.collect(),
         }
+    }
 
-        for effect in self.effects.borrow().iter() {
-            effect.borrow().render(&mut stdout)?;
+    /// Asks for a bigger terminal, in the middle of the canvas.
+    fn render_too_small(&self, frame: &mut Frame) {
+        let lines = [
+            "Terminal too small".to_string(),
+            format!(
+                "need {}x{}, have {}x{}",
+                self.map.bounds.w, self.map.bounds.h, self.canvas.w, self.canvas.h
+            ),
+        ];
+        let top = ((self.canvas.h - lines.len() as f32) / 2.).floor().max(0.);
+        for (i, line) in lines.iter().enumerate() {
+            let x = ((self.canvas.w - line.chars().count() as f32) / 2.)
+                .floor()
+                .max(0.);
+            frame.print_colored(Pos(x, top + i as f32), line, Color::Role(Role::Hud));
         }
+    }
 
-        self.player.borrow().render(&mut stdout)?;
-
-        let player = self.player.borrow();
-
-        let c_x = player.pos.0 + player.aim.cos() * 10.;
-        let c_y = player.pos.1 + player.aim.sin() * 5.;
-
-        let (c_x, c_y) = Pos(c_x, c_y).into();
-
-        crossterm::queue!(
-            stdout,
-            MoveTo(c_x, c_y),
-            SetForegroundColor(crossterm::style::Color::Magenta),
-            Print('⌖'),
-            ResetColor
-        )?;
-
-        let msg = self.log.borrow().clone().unwrap_or_default();
-        crossterm::queue!(stdout, MoveTo(0, (self.canvas.h - 1.0) as u16), Print(msg))?;
+    /// Draws the entire game state for the current frame, with the HUD on top
+    /// and the latest messages just above its bottom bar, or the full message
+    /// log over everything while it is open. The menu of the top scene goes
+    /// over the game, or on its own for scenes that hide the game. When the
+    /// canvas cannot fit the level, only a note asking for more room is drawn.
+    pub fn render(&self, frame: &mut Frame) {
+        frame.clear();
+
+        let shows_game = self.scenes.top().shows_game();
+        if shows_game && self.is_too_small() {
+            self.render_too_small(frame);
+            return;
+        }
+        if shows_game {
+            systems::render::render(&self.world, self.settings.fov_shading, frame);
+            if self.settings.screen_shake && self.world.shake > 0 {
+                let dx = if (self.world.shake / 4).is_multiple_of(2) {
+                    1
+                } else {
+                    -1
+                };
+                frame.shift(dx, 0);
+            }
 
-        stdout.flush()?;
+            self.log.render(self.world.frames, &self.canvas, frame);
+            if !self.log.open {
+                self.hud.render(self, frame);
+            }
+        }
 
-        Ok(())
+        self.scenes
+            .render(&self.settings, &self.scoreboard, &self.canvas, frame);
     }
 }
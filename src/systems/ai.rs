@@ -0,0 +1,37 @@
+use crate::{
+    entity::components::{Ai, Health, Position, Velocity},
+    geometry::Pos,
+    world::World,
+};
+
+/// Center of the hitbox of the living player closest to `pos`, if there are any.
+#[must_use]
+pub fn closest_player(world: &World, pos: &Pos) -> Option<Pos> {
+    world
+        .players
+        .keys()
+        .filter(|id| world.healths.get(id).is_some_and(Health::is_alive))
+        .filter_map(|id| world.hitbox(*id))
+        .map(|hitbox| hitbox.center())
+        .min_by(|a, b| a.distance(pos).total_cmp(&b.distance(pos)))
+}
+
+/// Points every chasing entity at the closest living player, or stops it if there are none.
+pub fn chase(world: &mut World) {
+    let velocities: Vec<_> = world
+        .ais
+        .iter()
+        .filter_map(|(id, ai)| {
+            let Ai::Chase { speed } = ai;
+            let Position(pos) = world.positions.get(id)?;
+            let velocity = match closest_player(world, pos) {
+                Some(target) => Pos::from_angle(pos.angle(&target)).to_cells() * *speed,
+                None => Pos::default(),
+            };
+
+            Some((*id, Velocity(velocity)))
+        })
+        .collect();
+
+    world.velocities.extend(velocities);
+}
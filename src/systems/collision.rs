@@ -0,0 +1,203 @@
+use crate::{
+    entity::{
+        components::{Collider, Layers, Moved},
+        EntityId,
+    },
+    geometry::{grid::Grid, Hit, Pos, Rect},
+    map::Map,
+    world::World,
+};
+
+/// Size of the cells of the broad-phase grid, about the size of the largest entities.
+const CELL: f32 = 4.;
+
+/// What the tiles of the map collide as.
+const WALL: Collider = Collider {
+    layer: Layers::WALL,
+    mask: Layers::NONE,
+};
+
+/// Something that can be collided with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Body {
+    Entity(EntityId),
+    /// A tile of the map, by its index in `Map::level`.
+    Tile(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionKind {
+    /// A projectile hit its target.
+    Projectile,
+    /// Something fighting up close touched its target.
+    Melee,
+    /// The two touched without either being able to hurt the other.
+    Contact,
+}
+
+/// `source` touched `target`, which is on a layer that `source` acts on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CollisionEvent {
+    pub source: EntityId,
+    pub target: Body,
+    pub kind: CollisionKind,
+    pub dmg: u32,
+    /// Where a moving source first touched the target, if it was moving.
+    pub contact: Option<Hit>,
+}
+
+/// Finds the bodies that might be touching a collider, so that only those
+/// have to be checked. Tiles never move and are put in the grid once, while
+/// entities are put in it again every frame. Only walls are solid, so other
+/// tiles are left out.
+#[derive(Debug, Clone)]
+pub struct Broadphase {
+    tiles: Vec<Rect>,
+    tile_grid: Grid<usize>,
+    entity_grid: Grid<EntityId>,
+}
+
+impl Broadphase {
+    #[must_use]
+    pub fn new(map: &Map) -> Self {
+        Self::with_cell(map, CELL)
+    }
+
+    /// A broadphase with a single cell, which makes every body a candidate
+    /// for every collider, to measure the grid against.
+    #[must_use]
+    pub fn brute_force(map: &Map) -> Self {
+        Self::with_cell(map, map.bounds.w.max(map.bounds.h).max(1.))
+    }
+
+    fn with_cell(map: &Map, cell: f32) -> Self {
+        let tiles: Vec<Rect> = map.level.iter().map(|tile| tile.rect.clone()).collect();
+        let mut tile_grid = Grid::new(map.bounds.clone(), cell);
+        for (i, tile) in tiles.iter().enumerate() {
+            if map.is_wall(&tile.pos) {
+                tile_grid.insert(tile, i);
+            }
+        }
+
+        Self {
+            tiles,
+            tile_grid,
+            entity_grid: Grid::new(map.bounds.clone(), cell),
+        }
+    }
+
+    pub fn update(&mut self, world: &World) {
+        self.entity_grid.clear();
+
+        for id in world.colliders.keys() {
+            if let Some(hitbox) = world.hitbox(*id) {
+                self.entity_grid.insert(&hitbox, *id);
+            }
+        }
+    }
+}
+
+/// Every collision this frame between an entity and what its collider acts on.
+/// Projectiles are swept along the path they took this frame, so that they
+/// cannot skip over thin walls, and only collide with the first thing on it.
+#[must_use]
+pub fn detect(world: &World, broadphase: &Broadphase) -> Vec<CollisionEvent> {
+    let mut events = vec![];
+
+    for (source, collider) in &world.colliders {
+        let Some(hitbox) = world.hitbox(*source) else {
+            continue;
+        };
+
+        if world.projectiles.contains_key(source) {
+            events.extend(sweep(world, broadphase, *source, *collider, &hitbox));
+        } else {
+            for (target, rect) in candidates(world, broadphase, *source, *collider, &hitbox) {
+                if rect.intersects(&hitbox) {
+                    events.push(event(world, *source, target, None));
+                }
+            }
+        }
+    }
+
+    events
+}
+
+/// The first thing hit by `source` on its way from where it was last frame to `hitbox`.
+fn sweep(
+    world: &World,
+    broadphase: &Broadphase,
+    source: EntityId,
+    collider: Collider,
+    hitbox: &Rect,
+) -> Option<CollisionEvent> {
+    let Moved(moved) = world
+        .moves
+        .get(&source)
+        .copied()
+        .unwrap_or(Moved(Pos::default()));
+    let from = hitbox.pos - moved;
+    let path = Rect::new(&from, hitbox.w, hitbox.h).union(hitbox);
+
+    candidates(world, broadphase, source, collider, &path)
+        .into_iter()
+        .filter_map(|(target, rect)| {
+            let hit = rect
+                .expand(hitbox.w, hitbox.h)
+                .raycast(&from, &hitbox.pos)?;
+            Some((target, hit))
+        })
+        .min_by(|(_, a), (_, b)| a.t.total_cmp(&b.t))
+        .map(|(target, hit)| event(world, source, target, Some(hit)))
+}
+
+/// Bodies around `area` that `source` acts on, together with the space they take up.
+fn candidates(
+    world: &World,
+    broadphase: &Broadphase,
+    source: EntityId,
+    collider: Collider,
+    area: &Rect,
+) -> Vec<(Body, Rect)> {
+    let entities = broadphase
+        .entity_grid
+        .query(area)
+        .into_iter()
+        .filter(|target| *target != source)
+        .filter(|target| {
+            world
+                .colliders
+                .get(target)
+                .is_some_and(|target_collider| collider.acts_on(target_collider))
+        })
+        .filter_map(|target| Some((Body::Entity(target), world.hitbox(target)?)));
+
+    let tiles = if collider.acts_on(&WALL) {
+        broadphase.tile_grid.query(area)
+    } else {
+        vec![]
+    };
+    let tiles = tiles
+        .into_iter()
+        .map(|tile| (Body::Tile(tile), broadphase.tiles[tile].clone()));
+
+    entities.chain(tiles).collect()
+}
+
+fn event(world: &World, source: EntityId, target: Body, contact: Option<Hit>) -> CollisionEvent {
+    let (kind, dmg) = if let Some(projectile) = world.projectiles.get(&source) {
+        (CollisionKind::Projectile, projectile.dmg)
+    } else if let Some(melee) = world.melees.get(&source) {
+        (CollisionKind::Melee, melee.dmg)
+    } else {
+        (CollisionKind::Contact, 0)
+    };
+
+    CollisionEvent {
+        source,
+        target,
+        kind,
+        dmg,
+        contact,
+    }
+}
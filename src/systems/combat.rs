@@ -0,0 +1,153 @@
+use std::collections::BTreeSet;
+
+use super::{
+    collision::{Body, CollisionEvent, CollisionKind},
+    status,
+};
+use crate::{
+    entity::{
+        components::{Position, Velocity},
+        effects::hit,
+        pickups,
+        status::StatusKind,
+        EntityId, EntityKind,
+    },
+    geometry::Pos,
+    log::Category,
+    rng::Rng,
+    score::Stats,
+    world::{Commands, World},
+};
+
+/// Counts down melee cooldowns, and the reloads and ability cooldowns of players.
+pub fn cooldowns(world: &mut World) {
+    for melee in world.melees.values_mut() {
+        melee.ready_in = melee.ready_in.saturating_sub(1);
+    }
+    for player in world.players.values_mut() {
+        player.tick();
+    }
+}
+
+/// Applies the damage, knockback and statuses of the collisions this frame.
+/// Projectiles are spent on what they hit, and push it the way they were
+/// flying. Melee attackers shove away what they hit, and go on cooldown once
+/// they land a hit. Stunned attackers cannot land hits, and vulnerable
+/// targets take more damage. Bullets that hit and the health players lose
+/// are tallied in `score`.
+pub fn resolve(
+    world: &mut World,
+    collisions: &[CollisionEvent],
+    score: &mut Stats,
+    commands: &mut Commands,
+) {
+    let mut hits = BTreeSet::new();
+
+    for collision in collisions {
+        let Body::Entity(target) = collision.target else {
+            if collision.kind == CollisionKind::Projectile {
+                commands.despawn(collision.source);
+            }
+            continue;
+        };
+
+        let (knockback, status) = match collision.kind {
+            CollisionKind::Projectile => {
+                commands.despawn(collision.source);
+                let Some(projectile) = world.projectiles.get(&collision.source) else {
+                    continue;
+                };
+                if world.kinds.get(&collision.source) == Some(&EntityKind::Bullet)
+                    && hits.insert(collision.source)
+                {
+                    score.hits += 1;
+                }
+                let direction = world
+                    .velocities
+                    .get(&collision.source)
+                    .map_or(Pos::default(), |Velocity(v)| v.normalize());
+                (direction * projectile.knockback, projectile.status)
+            }
+            CollisionKind::Melee => {
+                if is_stunned(world, collision.source) {
+                    continue;
+                }
+                let Some(melee) = world.melees.get_mut(&collision.source) else {
+                    continue;
+                };
+                if !melee.is_ready() {
+                    continue;
+                }
+                melee.ready_in = melee.cooldown;
+                let knockback = melee.knockback;
+                (away(world, collision.source, target) * knockback, None)
+            }
+            CollisionKind::Contact => continue,
+        };
+
+        if let Some(physics) = world.physics.get_mut(&target) {
+            physics.push(knockback);
+        }
+        let dmg = world
+            .statuses
+            .get(&target)
+            .map_or(collision.dmg, |s| s.damage(collision.dmg));
+        if let Some(health) = world.healths.get_mut(&target) {
+            let (was_alive, hp) = (health.is_alive(), health.hp);
+            health.damage(dmg);
+            if let Some(kind @ EntityKind::Player(_)) = world.kinds.get(&target) {
+                score.damage_taken += hp - health.hp;
+                commands.log(Category::Combat, format!("{kind} took {dmg} damage"));
+                if was_alive && !health.is_alive() {
+                    commands.log(Category::Combat, format!("{kind} died"));
+                }
+            }
+        }
+        if let Some(status) = status {
+            status::afflict(world, target, status);
+        }
+        if let Some(Position(pos)) = world.positions.get(&target) {
+            if !world.players.contains_key(&target) {
+                for bundle in hit::bundles(*pos, dmg) {
+                    commands.spawn(bundle);
+                }
+            }
+        }
+    }
+}
+
+fn is_stunned(world: &World, id: EntityId) -> bool {
+    world
+        .statuses
+        .get(&id)
+        .is_some_and(|s| s.has(StatusKind::Stun))
+}
+
+/// The direction from the center of `from` to the center of `to`, stretched into cells.
+fn away(world: &World, from: EntityId, to: EntityId) -> Pos {
+    match (world.hitbox(from), world.hitbox(to)) {
+        (Some(from), Some(to)) => Pos::from_angle(from.center().angle(&to.center())).to_cells(),
+        _ => Pos::default(),
+    }
+}
+
+/// Despawns enemies that have run out of health, leaving behind whatever
+/// their drops roll and scoring their bounty in `score`. Players stay
+/// around when they die.
+pub fn deaths(world: &World, rng: &mut Rng, score: &mut Stats, commands: &mut Commands) {
+    for (id, health) in &world.healths {
+        if !health.is_alive() && !world.players.contains_key(id) {
+            commands.despawn(*id);
+            if let Some(bounty) = world.bounties.get(id) {
+                score.kill(*bounty, world.frames);
+            }
+            if let Some(kind) = world.kinds.get(id) {
+                commands.log(Category::Combat, format!("{kind} was killed"));
+            }
+            let drop = world.drops.get(id).and_then(|drops| drops.roll(rng));
+            if let (Some(kind), Some(rect)) = (drop, world.hitbox(*id)) {
+                commands.spawn(pickups::bundle(kind, rect.center()));
+            }
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use crate::world::{Commands, World};
+
+pub fn age(world: &mut World, commands: &mut Commands) {
+    for (id, lifetime) in &mut world.lifetimes {
+        lifetime.age += 1;
+        if lifetime.age >= lifetime.ttl {
+            commands.despawn(*id);
+        }
+    }
+}
@@ -0,0 +1,14 @@
+//! Systems advance the world by one frame. They get the parts of the world
+//! they need, and queue spawns and despawns on `Commands` instead of changing
+//! which entities exist while others are looking at them.
+
+pub mod ai;
+pub mod collision;
+pub mod combat;
+pub mod lifetime;
+pub mod movement;
+pub mod particles;
+pub mod physics;
+pub mod pickups;
+pub mod render;
+pub mod status;
@@ -0,0 +1,114 @@
+use crate::{
+    entity::{
+        components::{Layers, Moved, Position, Velocity},
+        status::Statuses,
+    },
+    geometry::{Pos, Rect},
+    map::Map,
+    world::{Commands, World},
+};
+
+/// Moves every entity by its own velocity, slowed down by its statuses, and
+/// by how fast it has been pushed. Entities that act on walls slide along
+/// them instead of going through them, except for projectiles, which are
+/// swept into them when collisions are checked. How far each entity got is
+/// kept as its `Moved`.
+pub fn movement(world: &mut World, map: &Map) {
+    let ids: Vec<_> = world.positions.keys().copied().collect();
+    world.moves.clear();
+
+    for id in ids {
+        let speed = world.statuses.get(&id).map_or(1., Statuses::speed);
+        let own = world
+            .velocities
+            .get(&id)
+            .map_or(Pos::default(), |Velocity(v)| *v * speed);
+        let pushed = world
+            .physics
+            .get(&id)
+            .map_or(Pos::default(), |physics| physics.velocity);
+        let delta = own + pushed;
+        if delta == Pos::default() {
+            continue;
+        }
+
+        let solid = !world.projectiles.contains_key(&id)
+            && world
+                .colliders
+                .get(&id)
+                .is_some_and(|collider| collider.mask.intersects(Layers::WALL));
+        let hitbox = world.hitbox(id);
+
+        let (moved, blocked) = match hitbox {
+            Some(hitbox) if solid => slide(map, &hitbox, delta),
+            _ => (delta, (false, false)),
+        };
+
+        if let Some(Position(pos)) = world.positions.get_mut(&id) {
+            *pos += moved;
+            world.moves.insert(id, Moved(moved));
+        }
+        if let Some(physics) = world.physics.get_mut(&id) {
+            if blocked.0 {
+                physics.velocity.0 = 0.;
+            }
+            if blocked.1 {
+                physics.velocity.1 = 0.;
+            }
+        }
+    }
+}
+
+/// How far `hitbox` gets when moved by `delta`, one axis at a time so that it
+/// slides along walls, and whether it was stopped on either axis. A rect that
+/// already overlaps a wall moves freely until it is out of it.
+#[must_use]
+pub fn slide(map: &Map, hitbox: &Rect, delta: Pos) -> (Pos, (bool, bool)) {
+    let stuck = map.blocks(hitbox);
+    let mut rect = hitbox.clone();
+
+    rect.pos.0 += delta.0;
+    let blocked_x = !stuck && map.blocks(&rect);
+    if blocked_x {
+        rect.pos.0 = hitbox.pos.0;
+    }
+
+    rect.pos.1 += delta.1;
+    let blocked_y = !stuck && map.blocks(&rect);
+    if blocked_y {
+        rect.pos.1 = hitbox.pos.1;
+    }
+
+    (rect.pos - hitbox.pos, (blocked_x, blocked_y))
+}
+
+/// How far `hitbox` gets when moved straight by `delta` before it runs into
+/// a wall. The way there is checked every half cell, so that thin walls are
+/// not jumped over.
+#[must_use]
+pub fn reach(map: &Map, hitbox: &Rect, delta: Pos) -> Pos {
+    let steps = (delta.length() * 2.).ceil().max(1.);
+    let step = delta / steps;
+    let mut rect = hitbox.clone();
+
+    for _ in 0..steps as u32 {
+        let (moved, (blocked_x, blocked_y)) = slide(map, &rect, step);
+        if blocked_x || blocked_y {
+            break;
+        }
+        rect.pos += moved;
+    }
+
+    rect.pos - hitbox.pos
+}
+
+/// Despawns projectiles that have left the canvas.
+pub fn leave(world: &World, canvas: &Rect, commands: &mut Commands) {
+    for id in world.projectiles.keys() {
+        if let Some(Position(pos)) = world.positions.get(id) {
+            if !canvas.contains(pos) {
+                commands.despawn(*id);
+            }
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use crate::{
+    entity::components::Position,
+    rng::Rng,
+    world::{Commands, World},
+};
+
+/// Runs every emitter for a frame, and despawns the ones that are done.
+pub fn emit(world: &mut World, rng: &mut Rng, commands: &mut Commands) {
+    for (id, emitter) in &mut world.emitters {
+        let Some(Position(pos)) = world.positions.get(id) else {
+            continue;
+        };
+        emitter.update(*pos, rng);
+
+        if emitter.is_done() {
+            commands.despawn(*id);
+        }
+    }
+}
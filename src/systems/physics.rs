@@ -0,0 +1,98 @@
+use super::status;
+use crate::{
+    entity::{
+        components::Explosive,
+        effects::{explosion, hit},
+    },
+    geometry::Pos,
+    log::Category,
+    world::{Commands, World},
+};
+
+/// Frames an explosion shakes the screen for.
+const SHAKE: u32 = 60;
+
+/// Speeds up entities by their acceleration and slows them down by their friction.
+pub fn integrate(world: &mut World) {
+    for physics in world.physics.values_mut() {
+        physics.velocity += physics.acceleration;
+        physics.acceleration = Pos::default();
+        physics.velocity = physics.velocity * (1. - physics.friction);
+
+        if physics.velocity.length() < 0.001 {
+            physics.velocity = Pos::default();
+        }
+    }
+}
+
+/// Burns down fuses and blows up what has run out of fuse.
+pub fn fuses(world: &mut World, commands: &mut Commands) {
+    let mut explosions = vec![];
+
+    for (id, explosive) in &mut world.explosives {
+        explosive.fuse = explosive.fuse.saturating_sub(1);
+        if explosive.fuse == 0 {
+            explosions.push((*id, *explosive));
+        }
+    }
+
+    for (id, explosive) in explosions {
+        commands.despawn(id);
+        let Some(center) = world.hitbox(id).map(|hitbox| hitbox.center()) else {
+            continue;
+        };
+        explode(world, center, &explosive, commands);
+    }
+}
+
+/// Pushes everything within the radius of `explosive` straight away from
+/// `center`, harder the closer it is, and hurts and afflicts everything but
+/// players in the radius.
+pub fn explode(world: &mut World, center: Pos, explosive: &Explosive, commands: &mut Commands) {
+    let Explosive {
+        radius,
+        force,
+        dmg,
+        status,
+        ..
+    } = *explosive;
+    let ids: Vec<_> = world.positions.keys().copied().collect();
+
+    for id in ids {
+        let Some(hitbox) = world.hitbox(id) else {
+            continue;
+        };
+        let away = (hitbox.center() - center).from_cells();
+        let distance = away.length();
+        if distance > radius {
+            continue;
+        }
+
+        if let Some(physics) = world.physics.get_mut(&id) {
+            let strength = force * (1. - distance / radius);
+            physics.push(away.normalize().to_cells() * strength);
+        }
+        if world.players.contains_key(&id) {
+            continue;
+        }
+        if let Some(status) = status {
+            status::afflict(world, id, status);
+        }
+        let dmg = world.statuses.get(&id).map_or(dmg, |s| s.damage(dmg));
+        if let Some(health) = world.healths.get_mut(&id) {
+            health.damage(dmg);
+            for bundle in hit::bundles(hitbox.pos, dmg) {
+                commands.spawn(bundle);
+            }
+        }
+    }
+
+    for bundle in explosion::bundles(center, radius) {
+        commands.spawn(bundle);
+    }
+    world.shake = SHAKE;
+    commands.log(
+        Category::Debug,
+        format!("explosion at {:.0},{:.0}", center.0, center.1),
+    );
+}
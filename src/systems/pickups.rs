@@ -0,0 +1,43 @@
+use std::collections::BTreeSet;
+
+use super::collision::{Body, CollisionEvent};
+use crate::{
+    entity::EntityKind,
+    log::Category,
+    world::{Commands, World},
+};
+
+/// Hands the pickups players walked over this frame to them. A pickup goes
+/// to the first player touching it who has room for it, and stays where it
+/// is if none of them do.
+pub fn collect(world: &mut World, collisions: &[CollisionEvent], commands: &mut Commands) {
+    let mut taken = BTreeSet::new();
+
+    for collision in collisions {
+        let Body::Entity(target) = collision.target else {
+            continue;
+        };
+        let Some(&kind) = world.pickups.get(&target) else {
+            continue;
+        };
+        if taken.contains(&target) {
+            continue;
+        }
+        let Some(player) = world.players.get_mut(&collision.source) else {
+            continue;
+        };
+
+        if player.pick_up(kind) {
+            taken.insert(target);
+            commands.despawn(target);
+            commands.log(
+                Category::Pickup,
+                format!(
+                    "{} picked up the {}",
+                    EntityKind::Player(player.id),
+                    kind.name()
+                ),
+            );
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{
+    entity::{
+        components::{Layer, Lifetime, Pose, Position, Velocity},
+        sprites::Facing,
+        status::Statuses,
+        EntityId,
+    },
+    geometry::Pos,
+    render::Frame,
+    world::World,
+};
+
+/// Draws the fov of every player if `fov` is on, then every entity layer by
+/// layer, tinted by its statuses, with particles on the effect layer, then
+/// the crosshairs on top.
+pub fn render(world: &World, fov: bool, frame: &mut Frame) {
+    for (id, player) in &world.players {
+        if let (true, Some(Position(pos))) = (fov, world.positions.get(id)) {
+            player.render_fov(*pos, frame);
+        }
+    }
+
+    let mut renderables: Vec<_> = world
+        .renderables
+        .iter()
+        .filter_map(|(id, renderable)| {
+            let Position(pos) = world.positions.get(id)?;
+            let tint = world.statuses.get(id).and_then(Statuses::color);
+            Some((renderable, *pos, pose(world, *id), tint))
+        })
+        .collect();
+    renderables.sort_by_key(|(renderable, ..)| renderable.layer);
+
+    // Particles go on the effect layer, below players.
+    let mut particles = Some(&world.emitters);
+    for (renderable, pos, pose, tint) in renderables {
+        if renderable.layer > Layer::Effect {
+            for emitter in particles.take().into_iter().flat_map(|e| e.values()) {
+                emitter.draw(frame);
+            }
+        }
+        match tint {
+            Some(tint) => renderable.clone().fg(tint).draw(frame, pos, pose),
+            None => renderable.draw(frame, pos, pose),
+        }
+    }
+
+    for emitter in particles.into_iter().flat_map(|e| e.values()) {
+        emitter.draw(frame);
+    }
+
+    for (id, player) in &world.players {
+        if let Some(Position(pos)) = world.positions.get(id) {
+            player.render_crosshair(*pos, frame);
+        }
+    }
+}
+
+/// Entities that live for a while are as old as they are, while the rest
+/// animate off the clock of the world, each a bit out of step with the others.
+/// Players face where they aim, and everything else where it is walking.
+fn pose(world: &World, id: EntityId) -> Pose {
+    let age = world.lifetimes.get(&id).map_or(
+        world.frames.wrapping_add(id.0 * 7),
+        |Lifetime { age, .. }| *age,
+    );
+    let velocity = world
+        .velocities
+        .get(&id)
+        .map_or(Pos::default(), |Velocity(v)| *v);
+    let moving = velocity != Pos::default();
+    let facing = match world.players.get(&id) {
+        Some(player) => Facing::from_angle(player.aim),
+        None if moving => Facing::from_angle(velocity.1.atan2(velocity.0)),
+        None => Facing::default(),
+    };
+
+    Pose {
+        age,
+        facing,
+        moving,
+    }
+}
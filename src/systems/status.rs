@@ -0,0 +1,74 @@
+use crate::{
+    entity::{
+        effects::hit,
+        status::{Status, DOT_EVERY},
+        EntityId,
+    },
+    map::{Map, TileType},
+    score::Stats,
+    world::{Commands, World},
+};
+
+/// Afflicts an entity with a status. Only entities with health can have statuses.
+pub fn afflict(world: &mut World, id: EntityId, status: Status) {
+    if world.healths.contains_key(&id) {
+        world.statuses.entry(id).or_default().apply(status);
+    }
+}
+
+/// Afflicts entities with the statuses of the tiles they stand on, unless
+/// they already have that status, so that standing in sludge does not pile
+/// up poison every frame.
+pub fn tiles(world: &mut World, map: &Map) {
+    let afflictions: Vec<_> = world
+        .healths
+        .keys()
+        .filter_map(|id| Some((*id, world.hitbox(*id)?)))
+        .flat_map(|(id, hitbox)| {
+            map.tiles_under(&hitbox)
+                .filter_map(TileType::status)
+                .map(move |status| (id, status))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    for (id, status) in afflictions {
+        if !world.statuses.get(&id).is_some_and(|s| s.has(status.kind)) {
+            afflict(world, id, status);
+        }
+    }
+}
+
+/// Hurts entities with damage over time every `DOT_EVERY` frames, tallying
+/// what players lose in `score`, and wears statuses off.
+pub fn tick(world: &mut World, score: &mut Stats, commands: &mut Commands) {
+    for (id, statuses) in &mut world.statuses {
+        let mut dmg = 0;
+        for status in &mut statuses.0 {
+            status.ticks = status.ticks.saturating_sub(1);
+            if status.ticks % DOT_EVERY == 0 {
+                dmg += status.dmg();
+            }
+        }
+        statuses.0.retain(|status| status.ticks > 0);
+
+        if dmg == 0 {
+            continue;
+        }
+        let dmg = statuses.damage(dmg);
+        if let Some(health) = world.healths.get_mut(id) {
+            let hp = health.hp;
+            health.damage(dmg);
+            if world.players.contains_key(id) {
+                score.damage_taken += hp - health.hp;
+            }
+        }
+        if !world.players.contains_key(id) {
+            if let Some(pos) = world.positions.get(id) {
+                for bundle in hit::bundles(pos.0, dmg) {
+                    commands.spawn(bundle);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,283 @@
+//! Semantic colors and the themes that give them a look. The game draws with
+//! `Color::Role`, and the terminal resolves roles through the active theme,
+//! then brings the result down to what the terminal can show.
+
+use crate::{entity::player::PlayerId, render::Color};
+
+/// What a color is used for, rather than what it looks like.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// The color of a player, by their id.
+    Player(PlayerId),
+    Enemy,
+    Wall,
+    /// The background of the cone a player is looking in.
+    Fov,
+    Crosshair,
+    Hud,
+    Damage,
+    Burning,
+    Poison,
+    Slow,
+    Stun,
+    Vulnerable,
+    /// Things lying around for players to pick up.
+    Pickup,
+}
+
+/// A named set of colors for every role. Themes are told apart by name.
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    pub name: &'static str,
+    colors: fn(Role) -> Color,
+}
+
+impl Theme {
+    /// The colors the game started out with, using only the 16 basic colors.
+    pub const CLASSIC: Self = Self {
+        name: "classic",
+        colors: |role| match role {
+            Role::Player(id) => {
+                [Color::Red, Color::Cyan, Color::Green, Color::Yellow][usize::from(id) % 4]
+            }
+            Role::Enemy | Role::Wall | Role::Hud => Color::White,
+            Role::Fov => Color::Black,
+            Role::Crosshair | Role::Vulnerable => Color::Magenta,
+            Role::Damage | Role::Burning => Color::Red,
+            Role::Poison => Color::Green,
+            Role::Slow => Color::Cyan,
+            Role::Stun | Role::Pickup => Color::Yellow,
+        },
+    };
+
+    /// Softer colors on a dark blue background, for terminals with true color.
+    pub const DUSK: Self = Self {
+        name: "dusk",
+        colors: |role| match role {
+            Role::Player(id) => [
+                Color::Rgb(243, 139, 168),
+                Color::Rgb(137, 220, 235),
+                Color::Rgb(166, 227, 161),
+                Color::Rgb(249, 226, 175),
+            ][usize::from(id) % 4],
+            Role::Enemy => Color::Rgb(250, 179, 135),
+            Role::Wall => Color::Rgb(108, 112, 134),
+            Role::Fov => Color::Rgb(30, 30, 46),
+            Role::Crosshair | Role::Vulnerable => Color::Rgb(203, 166, 247),
+            Role::Hud => Color::Rgb(205, 214, 244),
+            Role::Damage | Role::Burning => Color::Rgb(243, 139, 168),
+            Role::Poison => Color::Rgb(166, 227, 161),
+            Role::Slow => Color::Rgb(137, 220, 235),
+            Role::Stun => Color::Rgb(249, 226, 175),
+            Role::Pickup => Color::Rgb(148, 226, 213),
+        },
+    };
+
+    /// The Okabe-Ito palette, which stays apart for the common kinds of color
+    /// blindness. Red and green never have to be told apart.
+    pub const COLORBLIND: Self = Self {
+        name: "colorblind",
+        colors: |role| match role {
+            Role::Player(id) => [
+                Color::Rgb(230, 159, 0),
+                Color::Rgb(86, 180, 233),
+                Color::Rgb(240, 228, 66),
+                Color::Rgb(204, 121, 167),
+            ][usize::from(id) % 4],
+            Role::Enemy | Role::Burning | Role::Damage => Color::Rgb(213, 94, 0),
+            Role::Wall | Role::Hud => Color::Rgb(255, 255, 255),
+            Role::Fov => Color::Rgb(40, 40, 40),
+            Role::Crosshair | Role::Vulnerable => Color::Rgb(204, 121, 167),
+            Role::Poison => Color::Rgb(0, 114, 178),
+            Role::Slow => Color::Rgb(86, 180, 233),
+            Role::Stun => Color::Rgb(240, 228, 66),
+            Role::Pickup => Color::Rgb(0, 158, 115),
+        },
+    };
+
+    /// Every theme that ships with the game.
+    pub const ALL: [Self; 3] = [Self::CLASSIC, Self::DUSK, Self::COLORBLIND];
+
+    #[must_use]
+    pub fn get(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|theme| theme.name == name)
+    }
+
+    /// What `color` looks like in this theme. Colors that are not roles are left as they are.
+    #[must_use]
+    pub fn color(&self, color: Color) -> Color {
+        match color {
+            Color::Role(role) => (self.colors)(role),
+            color => color,
+        }
+    }
+}
+
+impl PartialEq for Theme {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for Theme {}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::CLASSIC
+    }
+}
+
+/// How many colors a terminal can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    /// Colors are turned off, as asked for by `NO_COLOR`.
+    None,
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// The depth a terminal supports, going by the values of `NO_COLOR`,
+    /// `COLORTERM` and `TERM`, see <https://no-color.org>.
+    #[must_use]
+    pub fn from_vars(no_color: Option<&str>, colorterm: Option<&str>, term: Option<&str>) -> Self {
+        if no_color.is_some_and(|value| !value.is_empty()) {
+            return ColorDepth::None;
+        }
+        if matches!(colorterm, Some("truecolor" | "24bit")) {
+            return ColorDepth::TrueColor;
+        }
+
+        match term {
+            Some("dumb") => ColorDepth::None,
+            Some(term) if term.contains("truecolor") || term.contains("direct") => {
+                ColorDepth::TrueColor
+            }
+            Some(term) if term.contains("256color") => ColorDepth::Ansi256,
+            _ => ColorDepth::Ansi16,
+        }
+    }
+
+    /// The depth of the terminal the game is running in.
+    #[must_use]
+    pub fn detect() -> Self {
+        let var = |name| std::env::var(name).ok();
+        Self::from_vars(
+            var("NO_COLOR").as_deref(),
+            var("COLORTERM").as_deref(),
+            var("TERM").as_deref(),
+        )
+    }
+}
+
+/// A color the way it is sent to a terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TermColor {
+    /// One of the 256 indexed colors, the first 16 of which are the basic ones.
+    Ansi(u8),
+    Rgb(u8, u8, u8),
+}
+
+/// The 16 basic colors, in the RGB most terminals show them as.
+const ANSI16: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Levels of each channel in the 6x6x6 color cube of the 256 colors.
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// A theme on a terminal of a given depth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    pub theme: Theme,
+    pub depth: ColorDepth,
+}
+
+impl Palette {
+    #[must_use]
+    pub fn new(theme: Theme, depth: ColorDepth) -> Self {
+        Self { theme, depth }
+    }
+
+    /// The theme named in `BREACH_THEME`, or the classic one, on the terminal the game is running in.
+    #[must_use]
+    pub fn detect() -> Self {
+        let theme = std::env::var("BREACH_THEME")
+            .ok()
+            .and_then(|name| Theme::get(&name))
+            .unwrap_or_default();
+
+        Self::new(theme, ColorDepth::detect())
+    }
+
+    /// What to send to the terminal for `color`, or nothing if colors are off.
+    #[must_use]
+    pub fn resolve(&self, color: Color) -> Option<TermColor> {
+        let ansi = |n| Some(TermColor::Ansi(n));
+
+        match (self.theme.color(color), self.depth) {
+            // Themes never map a role to another role.
+            (_, ColorDepth::None) | (Color::Role(_), _) => None,
+            (Color::Black, _) => ansi(0),
+            (Color::Red, _) => ansi(9),
+            (Color::Green, _) => ansi(10),
+            (Color::Yellow, _) => ansi(11),
+            (Color::Blue, _) => ansi(12),
+            (Color::Magenta, _) => ansi(13),
+            (Color::Cyan, _) => ansi(14),
+            (Color::White, _) => ansi(15),
+            (Color::Rgb(r, g, b), ColorDepth::TrueColor) => Some(TermColor::Rgb(r, g, b)),
+            (Color::Rgb(r, g, b), ColorDepth::Ansi256) => ansi(to_256(r, g, b)),
+            (Color::Rgb(r, g, b), ColorDepth::Ansi16) => ansi(to_16(r, g, b)),
+        }
+    }
+}
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> i32 {
+    let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2);
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+/// The closest of the 256 colors, either in the color cube or on the gray ramp.
+fn to_256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |c: u8| {
+        (0..CUBE.len())
+            .min_by_key(|i| (i32::from(CUBE[*i]) - i32::from(c)).abs())
+            .unwrap_or(0) as u8
+    };
+    let (lr, lg, lb) = (level(r), level(g), level(b));
+    let cube = (CUBE[lr as usize], CUBE[lg as usize], CUBE[lb as usize]);
+
+    let avg = ((u16::from(r) + u16::from(g) + u16::from(b)) / 3) as u8;
+    let step = (avg.saturating_sub(3) / 10).min(23);
+    let gray = 8 + step * 10;
+
+    if distance((gray, gray, gray), (r, g, b)) < distance(cube, (r, g, b)) {
+        232 + step
+    } else {
+        16 + 36 * lr + 6 * lg + lb
+    }
+}
+
+/// The closest of the 16 basic colors.
+fn to_16(r: u8, g: u8, b: u8) -> u8 {
+    (0..ANSI16.len())
+        .min_by_key(|i| distance(ANSI16[*i], (r, g, b)))
+        .unwrap_or(0) as u8
+}
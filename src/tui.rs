@@ -0,0 +1,220 @@
+use std::{
+    error::Error,
+    io::{Stdout, Write},
+    panic,
+    sync::mpsc::Sender,
+};
+
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{DisableMouseCapture, EnableMouseCapture},
+    style::{Print, ResetColor, SetBackgroundColor, SetForegroundColor},
+    terminal::{
+        Clear, ClearType, DisableLineWrap, EnableLineWrap, EnterAlternateScreen,
+        LeaveAlternateScreen,
+    },
+};
+use signal_hook::{
+    consts::{SIGINT, SIGTERM},
+    iterator::Signals,
+};
+
+use crate::{
+    render::{Cell, Frame},
+    theme::{Palette, TermColor},
+};
+
+/// The terminal set up for the game: in raw mode, capturing the mouse if
+/// asked to, and drawing on the alternate screen with the cursor hidden and
+/// lines not wrapping.
+/// Dropping the session puts the terminal back the way it was, and so does a
+/// panic, before the panic message is written, so that it can be read.
+#[derive(Debug)]
+pub struct Session {
+    _private: (),
+}
+
+impl Session {
+    /// Sets up the terminal and makes sure a panic restores it.
+    ///
+    /// # Errors
+    ///
+    /// If the terminal cannot be set up.
+    pub fn start(mouse: bool) -> std::io::Result<Self> {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            restore();
+            hook(info);
+        }));
+
+        let session = Self { _private: () };
+        crossterm::terminal::enable_raw_mode()?;
+        crossterm::execute!(
+            std::io::stdout(),
+            EnterAlternateScreen,
+            Hide,
+            DisableLineWrap
+        )?;
+        if mouse {
+            crossterm::execute!(std::io::stdout(), EnableMouseCapture)?;
+        }
+
+        Ok(session)
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        restore();
+    }
+}
+
+/// Takes the terminal out of raw mode, stops capturing the mouse, wraps
+/// lines again, shows the cursor and leaves the alternate screen. Restoring a terminal that was
+/// never set up does nothing, so this is safe to call more than once.
+pub fn restore() {
+    crossterm::terminal::disable_raw_mode().ok();
+    crossterm::execute!(
+        std::io::stdout(),
+        DisableMouseCapture,
+        ResetColor,
+        EnableLineWrap,
+        Show,
+        LeaveAlternateScreen
+    )
+    .ok();
+}
+
+/// Sends `quit` on `tx` when the game is interrupted or asked to stop, so
+/// that it shuts down the same way as when the player quits.
+///
+/// # Errors
+///
+/// If the signal handlers cannot be registered.
+pub fn quit_on_signals<T: Clone + Send + 'static>(tx: Sender<T>, quit: T) -> std::io::Result<()> {
+    let mut signals = Signals::new([SIGINT, SIGTERM])?;
+    std::thread::spawn(move || {
+        for _ in signals.forever() {
+            if tx.send(quit.clone()).is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(())
+}
+
+impl From<TermColor> for crossterm::style::Color {
+    fn from(value: TermColor) -> Self {
+        match value {
+            TermColor::Ansi(0) => Self::Black,
+            TermColor::Ansi(1) => Self::DarkRed,
+            TermColor::Ansi(2) => Self::DarkGreen,
+            TermColor::Ansi(3) => Self::DarkYellow,
+            TermColor::Ansi(4) => Self::DarkBlue,
+            TermColor::Ansi(5) => Self::DarkMagenta,
+            TermColor::Ansi(6) => Self::DarkCyan,
+            TermColor::Ansi(7) => Self::Grey,
+            TermColor::Ansi(8) => Self::DarkGrey,
+            TermColor::Ansi(9) => Self::Red,
+            TermColor::Ansi(10) => Self::Green,
+            TermColor::Ansi(11) => Self::Yellow,
+            TermColor::Ansi(12) => Self::Blue,
+            TermColor::Ansi(13) => Self::Magenta,
+            TermColor::Ansi(14) => Self::Cyan,
+            TermColor::Ansi(15) => Self::White,
+            TermColor::Ansi(n) => Self::AnsiValue(n),
+            TermColor::Rgb(r, g, b) => Self::Rgb { r, g, b },
+        }
+    }
+}
+
+/// Shows frames on the terminal, only redrawing the cells that changed since
+/// the last frame. Colors are resolved through `palette` as they are drawn.
+#[derive(Debug)]
+pub struct Terminal {
+    stdout: Stdout,
+    last: Option<Frame>,
+    palette: Palette,
+}
+
+impl Default for Terminal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Terminal {
+    /// A terminal drawing in the theme and with the colors picked up from the environment.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_palette(Palette::detect())
+    }
+
+    #[must_use]
+    pub fn with_palette(palette: Palette) -> Self {
+        Self {
+            stdout: std::io::stdout(),
+            last: None,
+            palette,
+        }
+    }
+
+    #[must_use]
+    pub fn palette(&self) -> Palette {
+        self.palette
+    }
+
+    /// Switches to another palette, redrawing everything on the next frame.
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.palette = palette;
+        self.last = None;
+    }
+
+    /**
+    Draws the frame on the terminal
+
+    # Errors
+    If the terminal cannot be written to
+    */
+    pub fn draw(&mut self, frame: &Frame) -> Result<(), Box<dyn Error>> {
+        let last = self
+            .last
+            .take()
+            .filter(|last| (last.w, last.h) == (frame.w, frame.h));
+
+        if last.is_none() {
+            crossterm::queue!(self.stdout, Clear(ClearType::All))?;
+        }
+
+        for y in 0..frame.h {
+            for x in 0..frame.w {
+                let Some(cell) = frame.get(x, y) else {
+                    continue;
+                };
+                let unchanged = last.as_ref().and_then(|last| last.get(x, y)) == Some(cell);
+                if !unchanged {
+                    self.draw_cell(x, y, *cell)?;
+                }
+            }
+        }
+
+        self.stdout.flush()?;
+        self.last = Some(frame.clone());
+
+        Ok(())
+    }
+
+    fn draw_cell(&mut self, x: u16, y: u16, cell: Cell) -> Result<(), Box<dyn Error>> {
+        crossterm::queue!(self.stdout, MoveTo(x, y))?;
+        if let Some(fg) = cell.fg.and_then(|fg| self.palette.resolve(fg)) {
+            crossterm::queue!(self.stdout, SetForegroundColor(fg.into()))?;
+        }
+        if let Some(bg) = cell.bg.and_then(|bg| self.palette.resolve(bg)) {
+            crossterm::queue!(self.stdout, SetBackgroundColor(bg.into()))?;
+        }
+        crossterm::queue!(self.stdout, Print(cell.ch), ResetColor)?;
+
+        Ok(())
+    }
+}
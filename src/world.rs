@@ -0,0 +1,243 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+};
+
+use crate::{
+    entity::{
+        components::{
+            Ai, Collider, Explosive, Health, Hitbox, Lifetime, Melee, Moved, Physics, Position,
+            Projectile, Renderable, Velocity,
+        },
+        particles::Emitter,
+        pickups::{Drops, PickupKind},
+        player::{Player, PlayerId},
+        status::Statuses,
+        EntityId, EntityKind,
+    },
+    geometry::Rect,
+    log::Category,
+};
+
+/// Components of one type, by the entity they belong to. Ordered by id so
+/// that systems visit entities in the same order every time a game is played.
+pub type Components<T> = BTreeMap<EntityId, T>;
+
+macro_rules! world {
+    ($($storage:ident, $field:ident: $component:ty),* $(,)?) => {
+        /// Every entity in the game, stored as the components it is made of.
+        #[derive(Debug, Default)]
+        pub struct World {
+            next_id: u32,
+            /// Frames the world has been running for.
+            pub frames: u32,
+            /// Frames left of shaking the screen, after an explosion.
+            pub shake: u32,
+            alive: BTreeSet<EntityId>,
+            $(pub $storage: Components<$component>,)*
+        }
+
+        /// The components of an entity that is yet to be spawned.
+        #[derive(Debug, Clone, Default)]
+        pub struct Bundle {
+            $(pub $field: Option<$component>,)*
+        }
+
+        impl Bundle {
+            $(
+                #[must_use]
+                pub fn $field(self, $field: $component) -> Self {
+                    Self {
+                        $field: Some($field),
+                        ..self
+                    }
+                }
+            )*
+        }
+
+        impl World {
+            /// Adds an entity to the world, returning the id it will have for as long as it lives.
+            pub fn spawn(&mut self, bundle: Bundle) -> EntityId {
+                let id = EntityId(self.next_id);
+                self.next_id += 1;
+                self.alive.insert(id);
+
+                $(if let Some(component) = bundle.$field {
+                    self.$storage.insert(id, component);
+                })*
+
+                id
+            }
+
+            pub fn despawn(&mut self, id: EntityId) {
+                if self.alive.remove(&id) {
+                    $(self.$storage.remove(&id);)*
+                }
+            }
+
+            /// How many kinds of components `id` has.
+            #[cfg(test)]
+            fn components(&self, id: EntityId) -> usize {
+                [$(self.$storage.contains_key(&id)),*]
+                    .into_iter()
+                    .filter(|has| *has)
+                    .count()
+            }
+        }
+    };
+}
+
+world! {
+    kinds, kind: EntityKind,
+    positions, position: Position,
+    velocities, velocity: Velocity,
+    moves, moved: Moved,
+    physics, physics: Physics,
+    hitboxes, hitbox: Hitbox,
+    colliders, collider: Collider,
+    healths, health: Health,
+    statuses, statuses: Statuses,
+    renderables, renderable: Renderable,
+    ais, ai: Ai,
+    players, player: Player,
+    projectiles, projectile: Projectile,
+    explosives, explosive: Explosive,
+    melees, melee: Melee,
+    lifetimes, lifetime: Lifetime,
+    emitters, emitter: Emitter,
+    pickups, pickup: PickupKind,
+    drops, drops: Drops,
+    bounties, bounty: u32,
+}
+
+impl World {
+    #[must_use]
+    pub fn is_alive(&self, id: EntityId) -> bool {
+        self.alive.contains(&id)
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = EntityId> + '_ {
+        self.alive.iter().copied()
+    }
+
+    /// Despawns every entity and starts the clock over. Ids keep counting up
+    /// from where they were, so that none is ever handed out twice.
+    pub fn clear(&mut self) {
+        *self = Self {
+            next_id: self.next_id,
+            ..Self::default()
+        };
+    }
+
+    /// The area an entity takes up in the world, if it has a position and a hitbox.
+    #[must_use]
+    pub fn hitbox(&self, id: EntityId) -> Option<Rect> {
+        let Position(pos) = self.positions.get(&id)?;
+        let hitbox = self.hitboxes.get(&id)?;
+
+        Some(Rect::new(pos, hitbox.w, hitbox.h))
+    }
+
+    /// The entity controlled by the player with the id `player`.
+    #[must_use]
+    pub fn player(&self, player: PlayerId) -> Option<EntityId> {
+        self.players
+            .iter()
+            .find(|(_, p)| p.id == player)
+            .map(|(id, _)| *id)
+    }
+
+    pub fn apply(&mut self, commands: Commands) {
+        for id in commands.despawns {
+            self.despawn(id);
+        }
+        for bundle in commands.spawns {
+            self.spawn(bundle);
+        }
+    }
+}
+
+/// Spawns and despawns requested while systems run, applied to the world
+/// once they are done, along with the messages systems post for the log.
+#[derive(Debug, Default)]
+pub struct Commands {
+    spawns: Vec<Bundle>,
+    despawns: Vec<EntityId>,
+    messages: Vec<(Category, String)>,
+}
+
+impl Commands {
+    pub fn spawn(&mut self, bundle: Bundle) {
+        self.spawns.push(bundle);
+    }
+
+    pub fn despawn(&mut self, id: EntityId) {
+        self.despawns.push(id);
+    }
+
+    pub fn log(&mut self, category: Category, msg: impl Display) {
+        self.messages.push((category, msg.to_string()));
+    }
+
+    /// The messages posted so far, leaving none behind.
+    pub fn take_messages(&mut self) -> Vec<(Category, String)> {
+        std::mem::take(&mut self.messages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{entity::enemies, geometry::Pos};
+
+    #[test]
+    fn applies_commands_only_once_systems_are_done() {
+        let mut world = World::default();
+        let id = world.spawn(enemies::goblo(Pos(1., 1.)));
+        let mut commands = Commands::default();
+        commands.despawn(id);
+        commands.spawn(enemies::goblo(Pos(5., 1.)));
+        commands.log(Category::System, "done");
+
+        assert!(world.is_alive(id));
+        assert_eq!(world.ids().count(), 1);
+        assert_eq!(
+            commands.take_messages(),
+            [(Category::System, "done".to_string())]
+        );
+        assert!(commands.take_messages().is_empty());
+
+        world.apply(commands);
+        assert!(!world.is_alive(id));
+        assert_eq!(world.ids().collect::<Vec<_>>(), [EntityId(id.0 + 1)]);
+    }
+
+    #[test]
+    fn despawning_removes_every_component() {
+        let mut world = World::default();
+        let id = world.spawn(enemies::goblo(Pos(1., 1.)));
+        let other = world.spawn(enemies::goblo(Pos(5., 1.)));
+        let components = world.components(other);
+        assert!(components > 5);
+
+        world.despawn(id);
+        world.despawn(id);
+        assert_eq!(world.components(id), 0);
+        assert_eq!(world.components(other), components);
+        assert!(world.is_alive(other));
+    }
+
+    #[test]
+    fn never_hands_out_an_id_twice() {
+        let mut world = World::default();
+        let first = world.spawn(Bundle::default());
+        world.despawn(first);
+        let second = world.spawn(Bundle::default());
+        world.clear();
+        let third = world.spawn(Bundle::default());
+
+        assert!(first < second && second < third);
+        assert_eq!(world.ids().collect::<Vec<_>>(), [third]);
+        assert_eq!(world.frames, 0);
+    }
+}
@@ -0,0 +1,80 @@
+use breach::bot::{
+    self,
+    runner::{self, Outcome, MAX_TICKS},
+};
+
+const LEVEL: &str = "\
+####################
+#                  #
+# @          G     #
+#                  #
+#                  #
+#                  #
+####################";
+
+/// The enemy is walled in, so neither side can ever get to the other.
+const STUCK: &str = "\
+####################
+#        ########  #
+# @      #      #  #
+#        #  G   #  #
+#        #      #  #
+#        ########  #
+#                  #
+####################";
+
+fn make(name: &str) -> impl Fn() -> Box<dyn bot::Bot> + '_ {
+    move || bot::by_name(name).unwrap()
+}
+
+#[test]
+fn reports_how_the_hunter_clears_a_level() {
+    let report = runner::run(make("hunter"), ("tiny", LEVEL), 3, MAX_TICKS).unwrap();
+
+    assert_eq!(
+        (report.bot.as_str(), report.level.as_str()),
+        ("hunter", "tiny")
+    );
+    assert_eq!(report.outcomes.len(), 3);
+    assert!(report.outcomes.iter().all(|outcome| outcome.cleared));
+    assert!((report.clear_rate() - 1.).abs() < f32::EPSILON);
+    assert!((report.mean_score() - 100.).abs() < f32::EPSILON);
+    assert!(report.mean_time_to_clear().is_some());
+    assert!(report
+        .outcomes
+        .iter()
+        .all(|outcome| outcome.ticks < MAX_TICKS));
+    assert!(report
+        .to_string()
+        .starts_with("hunter on tiny: cleared 3/3 (100%)"));
+    assert_eq!(
+        runner::run(make("hunter"), ("tiny", LEVEL), 3, MAX_TICKS).unwrap(),
+        report
+    );
+}
+
+#[test]
+fn gives_up_on_games_that_go_nowhere() {
+    let outcome = runner::play(bot::by_name("idle").unwrap().as_mut(), STUCK, 0, MAX_TICKS);
+
+    assert_eq!(
+        outcome,
+        Ok(Outcome {
+            cleared: false,
+            ticks: MAX_TICKS,
+            score: 0,
+            damage_taken: 0,
+        })
+    );
+}
+
+#[test]
+fn reports_games_that_were_not_cleared() {
+    let report = runner::run(make("idle"), ("stuck", STUCK), 2, 100).unwrap();
+
+    assert!(report.clear_rate().abs() < f32::EPSILON);
+    assert_eq!(report.mean_time_to_clear(), None);
+    assert!(report
+        .to_string()
+        .contains("cleared 0/2 (0%), time to clear -"));
+}
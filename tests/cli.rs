@@ -0,0 +1,154 @@
+use std::path::PathBuf;
+
+use breach::{
+    cli::{Cli, CliError, Command, Options},
+    theme::Theme,
+};
+
+fn parse(line: &str) -> Result<Cli, CliError> {
+    Cli::parse(line.split_whitespace().map(String::from))
+}
+
+#[test]
+fn opens_the_menu_without_a_command() {
+    let cli = parse("").unwrap();
+
+    assert_eq!(cli.command, Command::Menu);
+    assert_eq!(cli.options, Options::default());
+}
+
+#[test]
+fn reads_commands_with_their_options() {
+    assert_eq!(
+        parse("play --seed 7 --level maps/a.txt").unwrap().command,
+        Command::Play {
+            level: Some(PathBuf::from("maps/a.txt")),
+            seed: Some(7),
+            record: None,
+        }
+    );
+    assert_eq!(
+        parse("bot hunter --games 3").unwrap().command,
+        Command::Bot {
+            name: "hunter".to_string(),
+            games: 3
+        }
+    );
+    assert_eq!(
+        parse("edit a.txt").unwrap().command,
+        Command::Edit(PathBuf::from("a.txt"))
+    );
+    assert_eq!(
+        parse("validate a.txt b.txt").unwrap().command,
+        Command::Validate(vec![PathBuf::from("a.txt"), PathBuf::from("b.txt")])
+    );
+    assert_eq!(
+        parse("validate-level a.txt").unwrap().command,
+        Command::Validate(vec![PathBuf::from("a.txt")])
+    );
+}
+
+#[test]
+fn takes_the_older_flags_for_commands() {
+    assert_eq!(
+        parse("--host 0.0.0.0:4000").unwrap().command,
+        Command::Host("0.0.0.0:4000".to_string())
+    );
+    assert_eq!(
+        parse("--join 10.0.0.2:4000").unwrap().command,
+        Command::Join("10.0.0.2:4000".to_string())
+    );
+    assert_eq!(
+        parse("--connect 10.0.0.2:4000").unwrap().command,
+        Command::Connect("10.0.0.2:4000".to_string())
+    );
+    assert_eq!(
+        parse("--bot hunter --games 3 --fps").unwrap(),
+        Cli {
+            command: Command::Bot {
+                name: "hunter".to_string(),
+                games: 3
+            },
+            options: Options {
+                fps: Some(true),
+                ..Options::default()
+            }
+        }
+    );
+    assert_eq!(parse("--host"), Err(CliError::MissingValue("host")));
+}
+
+#[test]
+fn takes_global_options_anywhere() {
+    let cli = parse("--fps play --no-mouse --theme dusk").unwrap();
+
+    assert_eq!(cli.options.fps, Some(true));
+    assert_eq!(cli.options.mouse, Some(false));
+    assert_eq!(cli.options.theme, Some(Theme::DUSK));
+    assert!(matches!(cli.command, Command::Play { .. }));
+}
+
+#[test]
+fn reads_where_and_as_whom_scores_are_kept() {
+    let cli = parse("--name ada scores --history").unwrap();
+    assert_eq!(cli.command, Command::Scores { history: true });
+    assert_eq!(cli.options.name.as_deref(), Some("ada"));
+
+    let config = Options::from_config("name = grace hopper\nscores = /tmp/scores.txt").unwrap();
+    let options = parse("--scores mine.txt scores")
+        .unwrap()
+        .options
+        .or(config);
+    assert_eq!(options.name.as_deref(), Some("grace hopper"));
+    assert_eq!(options.scores, Some(PathBuf::from("mine.txt")));
+    assert_eq!(
+        parse("scores --all"),
+        Err(CliError::Unexpected("--all".to_string()))
+    );
+}
+
+#[test]
+fn prints_help_whatever_else_is_given() {
+    assert_eq!(parse("play --bogus --help").unwrap().command, Command::Help);
+}
+
+#[test]
+fn rejects_what_it_does_not_know() {
+    assert_eq!(
+        parse("dance"),
+        Err(CliError::UnknownCommand("dance".to_string()))
+    );
+    assert_eq!(
+        parse("list-levels now"),
+        Err(CliError::Unexpected("now".to_string()))
+    );
+    assert_eq!(parse("replay"), Err(CliError::MissingValue("replay")));
+    assert_eq!(
+        parse("play --seed many"),
+        Err(CliError::InvalidValue {
+            name: "--seed".to_string(),
+            value: "many".to_string()
+        })
+    );
+    assert_eq!(
+        parse("--theme neon"),
+        Err(CliError::UnknownTheme("neon".to_string()))
+    );
+}
+
+#[test]
+fn fills_in_options_from_the_config_file() {
+    let config =
+        Options::from_config("# mine\ntheme = colorblind\nfps = true\nscreen_shake = false\n")
+            .unwrap();
+    let options = parse("--theme dusk").unwrap().options.or(config);
+
+    assert_eq!(options.theme, Some(Theme::DUSK));
+    assert_eq!(options.fps, Some(true));
+    assert_eq!(options.screen_shake, Some(false));
+    assert_eq!(options.mouse, None);
+    assert_eq!(
+        Options::from_config("fps = true\nvolume = 11"),
+        Err(CliError::InvalidConfig(2))
+    );
+}
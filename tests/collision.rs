@@ -0,0 +1,153 @@
+use breach::{
+    entity::{
+        components::{Collider, Layers, Moved},
+        enemies::goblo,
+        pickups::{self, PickupKind},
+        player::{self, HOST_ID},
+        projectiles, EntityId,
+    },
+    geometry::Pos,
+    map::Map,
+    systems::collision::{self, Body, Broadphase, CollisionEvent, CollisionKind},
+    world::{Bundle, World},
+};
+
+/// An open room cut in two by a wall one cell thick.
+const LEVEL: &str = "\
+####################
+#        #         #
+#        #         #
+#        #         #
+#        #         #
+####################";
+
+fn map() -> Map {
+    Map::try_from(LEVEL).unwrap()
+}
+
+fn detect(world: &World, map: &Map) -> Vec<CollisionEvent> {
+    let mut broadphase = Broadphase::new(map);
+    broadphase.update(world);
+    collision::detect(world, &broadphase)
+}
+
+fn from(events: &[CollisionEvent], source: EntityId) -> Vec<CollisionEvent> {
+    events
+        .iter()
+        .filter(|event| event.source == source)
+        .copied()
+        .collect()
+}
+
+/// A bullet resting at `center`, as if it had not moved this frame.
+fn bullet(world: &mut World, center: Pos) -> EntityId {
+    let id = world.spawn(projectiles::bullet(center, 0.));
+    world.moves.insert(id, Moved(Pos(0., 0.)));
+    id
+}
+
+/// There are no enemy projectiles yet, so this is what a bullet
+/// shot by an enemy would be.
+fn enemy_bullet(center: Pos) -> Bundle {
+    projectiles::bullet(center, 0.).collider(Collider::new(
+        Layers::PROJECTILE,
+        Layers::PLAYER | Layers::WALL,
+    ))
+}
+
+#[test]
+fn bullets_of_players_go_through_players_and_hit_enemies() {
+    let map = map();
+    let mut world = World::default();
+    let player = world.spawn(player::bundle(HOST_ID, Pos(2., 2.)));
+    let enemy = world.spawn(goblo(Pos(5., 2.)));
+    let through_player = bullet(&mut world, Pos(3., 3.));
+    let into_enemy = bullet(&mut world, Pos(6., 3.));
+
+    let events = detect(&world, &map);
+
+    assert!(from(&events, through_player).is_empty());
+    let hits = from(&events, into_enemy);
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].target, Body::Entity(enemy));
+    assert!(events
+        .iter()
+        .all(|event| event.target != Body::Entity(player) || event.source == enemy));
+}
+
+#[test]
+fn bullets_of_enemies_go_through_enemies_and_hit_players() {
+    let map = map();
+    let mut world = World::default();
+    let player = world.spawn(player::bundle(HOST_ID, Pos(2., 2.)));
+    world.spawn(goblo(Pos(5., 2.)));
+    let through_enemy = world.spawn(enemy_bullet(Pos(6., 3.)));
+    let into_player = world.spawn(enemy_bullet(Pos(3., 3.)));
+    for id in [through_enemy, into_player] {
+        world.moves.insert(id, Moved(Pos(0., 0.)));
+    }
+
+    let events = detect(&world, &map);
+
+    assert!(from(&events, through_enemy).is_empty());
+    let hits = from(&events, into_player);
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].target, Body::Entity(player));
+}
+
+#[test]
+fn tells_what_kind_of_collision_it_was_and_how_much_it_hurts() {
+    let map = map();
+    let mut world = World::default();
+    let player = world.spawn(player::bundle(HOST_ID, Pos(2., 2.)));
+    let pickup = world.spawn(pickups::bundle(PickupKind::Ammo, Pos(3., 2.)));
+    let enemy = world.spawn(goblo(Pos(4., 3.)));
+    let shot = bullet(&mut world, Pos(5.5, 4.5));
+
+    let events = detect(&world, &map);
+
+    let kinds: Vec<_> = [player, enemy, shot]
+        .into_iter()
+        .map(|source| {
+            let events = from(&events, source);
+            assert_eq!(events.len(), 1, "{source:?} touches one thing");
+            (events[0].target, events[0].kind, events[0].dmg)
+        })
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            (Body::Entity(pickup), CollisionKind::Contact, 0),
+            (Body::Entity(player), CollisionKind::Melee, 5),
+            (Body::Entity(enemy), CollisionKind::Projectile, 1),
+        ]
+    );
+    assert!(from(&events, pickup).is_empty());
+    assert!(from(&events, shot)[0].contact.is_some());
+    assert!(from(&events, enemy)[0].contact.is_none());
+}
+
+#[test]
+fn fast_bullets_stop_at_the_first_wall_on_their_way() {
+    let map = map();
+    let mut world = World::default();
+    let behind_the_wall = world.spawn(goblo(Pos(14., 2.)));
+    // Flew from (3, 2) to past the wall in a single frame, missing it at both ends.
+    let shot = world.spawn(projectiles::bullet(Pos(15.5, 2.5), 0.));
+    world.moves.insert(shot, Moved(Pos(12., 0.)));
+
+    let events = detect(&world, &map);
+
+    let hits = from(&events, shot);
+    assert_eq!(hits.len(), 1);
+    let Body::Tile(tile) = hits[0].target else {
+        panic!("the bullet hit {:?} rather than the wall", hits[0].target);
+    };
+    assert_eq!(map.level[tile].rect.pos, Pos(9., 2.));
+    let contact = hits[0].contact.unwrap();
+    assert_eq!(contact.point, Pos(8., 2.));
+    assert_eq!(contact.normal, Pos(-1., 0.));
+    assert!(events
+        .iter()
+        .all(|event| event.target != Body::Entity(behind_the_wall)));
+}
@@ -0,0 +1,192 @@
+use breach::{
+    editor::{self, Brush, Editor, EditorEvent},
+    geometry::Pos,
+    map::Map,
+    render::Frame,
+};
+
+const LEVEL: &str = "\
+:name Test
+:room Hall 0 0 10 5
+##########
+#        #
+# @    G #
+#        #
+##########";
+
+fn editor() -> Editor {
+    Editor::new(LEVEL)
+}
+
+#[test]
+fn keeps_the_level_text_as_it_was() {
+    let editor = editor();
+
+    assert_eq!(editor.text(), LEVEL);
+    assert_eq!(editor.cursor, (2, 2));
+    assert!(!editor.is_modified());
+}
+
+#[test]
+fn pads_rows_to_the_widest() {
+    let editor = Editor::new("###\n#@\n###");
+
+    assert_eq!(editor.text(), "###\n#@ \n###");
+}
+
+#[test]
+fn opens_blank_files_as_an_empty_room() {
+    for text in ["", "\n", ":name Nothing\n\n"] {
+        let mut editor = Editor::new(text);
+        editor.handle(EditorEvent::Move(-3, 50));
+        assert_eq!(editor.cursor.1, 11);
+        editor.handle(EditorEvent::PaintCursor);
+        editor.handle(EditorEvent::Paint(Pos(1., 1.)));
+        editor.render(&mut Frame::new(120, 20));
+
+        assert_eq!(editor.size(), (40, 12));
+        assert!(Map::try_from(editor.test_level().as_str()).is_ok());
+    }
+}
+
+#[test]
+fn paints_with_the_brush_and_erases_with_the_right_button() {
+    let mut editor = editor();
+    editor.handle(EditorEvent::Brush(Brush::Fire));
+    editor.handle(EditorEvent::Paint(Pos(4., 1.)));
+    editor.handle(EditorEvent::Erase(Pos(0., 0.)));
+
+    assert_eq!(editor.glyph(4, 1), Some('^'));
+    assert_eq!(editor.glyph(0, 0), Some(' '));
+    assert!(editor.is_modified());
+}
+
+#[test]
+fn ignores_painting_outside_of_the_level() {
+    let mut editor = editor();
+    editor.handle(EditorEvent::Paint(Pos(30., 1.)));
+    editor.handle(EditorEvent::Undo);
+
+    assert_eq!(editor.text(), LEVEL);
+}
+
+#[test]
+fn undoes_a_dragged_stroke_at_once() {
+    let mut editor = editor();
+    editor.handle(EditorEvent::Paint(Pos(3., 1.)));
+    editor.handle(EditorEvent::Drag(Pos(4., 1.)));
+    editor.handle(EditorEvent::Drag(Pos(5., 1.)));
+    editor.handle(EditorEvent::PaintCursor);
+    editor.handle(EditorEvent::Move(0, 2));
+    editor.handle(EditorEvent::PaintCursor);
+
+    editor.handle(EditorEvent::Undo);
+    assert_eq!(editor.glyph(5, 3), Some(' '));
+    assert_eq!(editor.glyph(5, 1), Some('#'));
+
+    editor.handle(EditorEvent::Undo);
+    assert_eq!(editor.text(), LEVEL);
+
+    editor.handle(EditorEvent::Redo);
+    assert_eq!(editor.glyph(4, 1), Some('#'));
+    assert_eq!(editor.glyph(5, 3), Some(' '));
+}
+
+#[test]
+fn painting_after_undo_forgets_the_redo() {
+    let mut editor = editor();
+    editor.handle(EditorEvent::Paint(Pos(3., 1.)));
+    editor.handle(EditorEvent::Undo);
+    editor.handle(EditorEvent::Paint(Pos(4., 1.)));
+    editor.handle(EditorEvent::Redo);
+
+    assert_eq!(editor.glyph(3, 1), Some(' '));
+    assert_eq!(editor.glyph(4, 1), Some('#'));
+}
+
+#[test]
+fn keeps_a_single_start_marker() {
+    let mut editor = editor();
+    editor.handle(EditorEvent::Brush(Brush::Start));
+    editor.handle(EditorEvent::Paint(Pos(5., 3.)));
+
+    assert_eq!(editor.glyph(2, 2), Some(' '));
+    assert_eq!(
+        Map::try_from(editor.text().as_str()).unwrap().start_pos,
+        Pos(5., 3.)
+    );
+}
+
+#[test]
+fn saves_only_a_valid_level() {
+    let mut editor = editor();
+    editor.handle(EditorEvent::Erase(Pos(2., 2.)));
+    let mut saved = None;
+    editor
+        .save(|text| {
+            saved = Some(text.to_string());
+            Ok(())
+        })
+        .unwrap();
+
+    assert_eq!(saved, None);
+    assert!(editor.status.contains("no start marker"));
+
+    editor.handle(EditorEvent::Undo);
+    editor.handle(EditorEvent::Paint(Pos(3., 1.)));
+    editor
+        .save(|text| {
+            saved = Some(text.to_string());
+            Ok(())
+        })
+        .unwrap();
+
+    assert_eq!(saved, Some(editor.text()));
+    assert!(!editor.is_modified());
+}
+
+#[test]
+fn test_plays_from_the_cursor() {
+    let mut editor = editor();
+    editor.handle(EditorEvent::Hover(Pos(7., 3.)));
+    let map = Map::try_from(editor.test_level().as_str()).unwrap();
+
+    assert_eq!(map.start_pos, Pos(7., 3.));
+    assert_eq!(map.spawns.len(), 1);
+    assert_eq!(editor.text(), LEVEL);
+}
+
+#[test]
+fn asks_before_quitting_with_unsaved_changes() {
+    let mut editor = editor();
+    editor.handle(EditorEvent::Quit);
+    assert!(editor.quit);
+
+    let mut editor = self::editor();
+    editor.handle(EditorEvent::PaintCursor);
+    editor.handle(EditorEvent::Quit);
+    assert!(!editor.quit);
+    editor.handle(EditorEvent::Quit);
+    assert!(editor.quit);
+}
+
+#[test]
+fn draws_the_level_and_the_brush() {
+    let mut frame = Frame::new(120, 10);
+    editor().render(&mut frame);
+
+    assert_eq!(frame.get(0, 0).unwrap().ch, '#');
+    assert_eq!(frame.get(7, 2).unwrap().ch, 'G');
+    assert!(frame.get(2, 2).unwrap().bg.is_some());
+    let status: String = (0..120).map(|x| frame.get(x, 9).unwrap().ch).collect();
+    assert!(status.contains("[1 wall]"));
+    assert!(status.contains("Hall"));
+}
+
+#[test]
+fn makes_new_levels_that_validate() {
+    let text = editor::blank(20, 8);
+
+    assert!(breach::map::validate::validate(&text).is_empty());
+    assert_eq!(Editor::new(&text).size(), (20, 8));
+}
@@ -0,0 +1,347 @@
+use breach::{
+    entity::{
+        components::{Physics, Velocity},
+        player::{Ability, HOST_ID},
+        projectiles,
+    },
+    event::{Event, GameEvent, PlayerEvent},
+    geometry::Pos,
+    map::{levels, Map},
+    net::server::MAX_PLAYERS,
+    render::Frame,
+    state::State,
+    systems::ai::closest_player,
+    Game,
+};
+
+const LEVEL: &str = "\
+####################
+#                  #
+# @          G     #
+#                  #
+#                  #
+#                  #
+####################";
+
+fn shoot() -> Event {
+    Event::Player(HOST_ID, PlayerEvent::Shoot)
+}
+
+#[test]
+fn starts_the_player_on_the_start_marker() {
+    let game = Game::new(LEVEL, 0).unwrap();
+    let observation = game.observe();
+
+    assert_eq!(observation.players.len(), 1);
+    assert_eq!(observation.players[0].hitbox.pos, Pos(2., 2.));
+    assert_eq!(observation.enemies.len(), 1);
+}
+
+#[test]
+fn moves_the_player() {
+    let mut game = Game::new(LEVEL, 0).unwrap();
+    game.step([Event::Player(HOST_ID, PlayerEvent::Move(2., 1.))]);
+
+    let player = game.observe().players[0].clone();
+    assert_eq!(player.hitbox.pos, Pos(4., 3.));
+    assert_eq!(game.tick(), 1);
+}
+
+#[test]
+fn plays_out_the_same_from_the_same_seed() {
+    let mut a = Game::new(LEVEL, 7).unwrap();
+    let mut b = Game::new(LEVEL, 7).unwrap();
+
+    for _ in 0..200 {
+        a.step([shoot()]);
+        b.step([shoot()]);
+    }
+
+    assert_eq!(a.observe(), b.observe());
+}
+
+#[test]
+fn clears_the_level_by_shooting_the_enemy() {
+    let mut game = Game::new(LEVEL, 0).unwrap();
+
+    while !game.is_cleared() && game.tick() < 5_000 {
+        game.step([shoot()]);
+    }
+
+    assert!(game.is_cleared());
+}
+
+#[test]
+fn slides_the_player_along_walls() {
+    let mut game = Game::new(LEVEL, 0).unwrap();
+    game.step([Event::Player(HOST_ID, PlayerEvent::Move(-2., -1.))]);
+
+    let player = game.observe().players[0].clone();
+    assert_eq!(player.hitbox.pos, Pos(2., 1.));
+}
+
+#[test]
+fn standing_in_sludge_poisons_the_player() {
+    let level = LEVEL.replacen("# @ ", "# @~", 1);
+    let mut game = Game::new(&level, 0).unwrap();
+    let hp = game.observe().players[0].hp;
+
+    for _ in 0..100 {
+        game.step([]);
+    }
+
+    assert!(game.observe().players[0].hp < hp);
+}
+
+fn row(frame: &Frame, y: u16) -> String {
+    (0..frame.w)
+        .filter_map(|x| frame.get(x, y))
+        .map(|cell| cell.ch)
+        .collect()
+}
+
+#[test]
+fn draws_the_hud_over_the_canvas() {
+    let level = format!(":name Test\n:room Start 0 0 10 7\n{LEVEL}");
+    let mut game = Game::new(&level, 0).unwrap();
+    game.step([Event::Game(GameEvent::Resize(60, 7))]);
+
+    let mut frame = Frame::new(60, 7);
+    game.render(&mut frame);
+
+    let top = row(&frame, 0);
+    assert!(top.starts_with("Test · Start"));
+    assert!(top.trim_end().ends_with("1 enemy left"));
+    let bottom = row(&frame, 6);
+    assert!(bottom.starts_with("HP [██████████] 100/100  pistol 12/12"));
+}
+
+#[test]
+fn reloads_once_the_magazine_is_empty() {
+    let mut game = Game::new(LEVEL, 0).unwrap();
+    for _ in 0..12 {
+        game.step([shoot()]);
+    }
+    let bullets = game.observe().projectiles.len();
+    game.step([shoot()]);
+
+    assert!(game.observe().projectiles.len() <= bullets);
+    let player = game.state().world.players.values().next().unwrap();
+    assert!(player.weapon.reloading > 0);
+}
+
+#[test]
+fn abilities_wait_for_their_cooldown() {
+    let mut game = Game::new(&LEVEL.replace('G', " "), 0).unwrap();
+    let blink = || Event::Player(HOST_ID, PlayerEvent::Ability(Ability::Blink));
+    game.step([blink()]);
+    let pos = game.observe().players[0].hitbox.pos;
+    game.step([blink()]);
+
+    assert_eq!(game.observe().players[0].hitbox.pos, pos);
+}
+
+#[test]
+fn logs_kills_for_the_on_screen_tail() {
+    let mut game = Game::new(LEVEL, 0).unwrap();
+    while !game.is_cleared() && game.tick() < 5_000 {
+        game.step([shoot()]);
+    }
+
+    let log = &game.state().log;
+    assert_eq!(log.entries().next().unwrap().text, "player 0 joined");
+    assert!(log
+        .tail(game.state().world.frames)
+        .any(|e| e.text == "goblo was killed"));
+}
+
+#[test]
+fn toggles_the_full_message_log() {
+    let mut game = Game::new(LEVEL, 0).unwrap();
+    game.step([
+        Event::Game(GameEvent::Resize(60, 7)),
+        Event::Game(GameEvent::ToggleLog),
+    ]);
+
+    let mut frame = Frame::new(60, 7);
+    game.render(&mut frame);
+    assert!(row(&frame, 0).starts_with("Message log"));
+
+    game.step([Event::Game(GameEvent::ToggleLog)]);
+    game.render(&mut frame);
+    assert!(!row(&frame, 0).starts_with("Message log"));
+}
+
+fn downed(level: &str) -> State {
+    let map = Map::try_from(level).unwrap();
+    let mut state = State::new(map.clone(), map.bounds, 0);
+    state.add_player(HOST_ID);
+    let id = state.world.player(HOST_ID).unwrap();
+    state.world.healths.get_mut(&id).unwrap().damage(1_000);
+    state.world.players.get_mut(&id).unwrap().inventory.medkits = 1;
+    state
+}
+
+#[test]
+fn downed_players_cannot_act() {
+    let mut state = downed(&LEVEL.replace('G', " "));
+    let id = state.world.player(HOST_ID).unwrap();
+    let pos = state.world.hitbox(id).unwrap().pos;
+
+    for event in [
+        PlayerEvent::Move(2., 0.),
+        PlayerEvent::Shoot,
+        PlayerEvent::Ability(Ability::Blink),
+        PlayerEvent::UseMedkit,
+    ] {
+        state.handle(Event::Player(HOST_ID, event));
+    }
+
+    assert_eq!(state.world.hitbox(id).unwrap().pos, pos);
+    assert!(state.world.projectiles.is_empty());
+    assert_eq!(state.world.players[&id].inventory.medkits, 1);
+    assert_eq!(state.world.healths[&id].hp, 0);
+}
+
+#[test]
+fn enemies_chase_living_players_past_downed_ones() {
+    let mut state = downed(LEVEL);
+    state.add_player(1);
+    let living = state.world.player(1).unwrap();
+    let target = state.world.hitbox(living).unwrap().center();
+
+    assert_eq!(closest_player(&state.world, &Pos(1., 2.)), Some(target));
+    state.remove_player(1);
+    assert_eq!(closest_player(&state.world, &Pos(1., 2.)), None);
+}
+
+#[test]
+fn fast_bullets_do_not_tunnel_through_thin_walls() {
+    let level = "\
+####################
+#         #        #
+####################";
+    let map = Map::try_from(level).unwrap();
+    let mut pushed = Physics::new(1., 0.);
+    pushed.velocity = Pos(4., 0.);
+    let bullets = [
+        projectiles::bullet(Pos(8.5, 1.5), 0.).velocity(Velocity(Pos(4., 0.))),
+        projectiles::bullet(Pos(8.5, 1.5), 0.)
+            .velocity(Velocity(Pos(0.01, 0.)))
+            .physics(pushed),
+    ];
+
+    for bullet in bullets {
+        let mut state = State::new(map.clone(), map.bounds.clone(), 0);
+        let id = state.world.spawn(bullet);
+        state.frame();
+
+        assert!(!state.world.is_alive(id));
+    }
+}
+
+#[test]
+fn blinks_stop_short_of_walls() {
+    let level = "\
+##############
+#            #
+#  @    #    #
+#       #    #
+#       #    #
+#       #    #
+##############";
+    let mut game = Game::new(level, 0).unwrap();
+    let start = game.observe().players[0].hitbox.pos;
+    game.step([
+        Event::Player(HOST_ID, PlayerEvent::Aim(Pos(12., 2.))),
+        Event::Player(HOST_ID, PlayerEvent::Ability(Ability::Blink)),
+    ]);
+
+    let hitbox = &game.observe().players[0].hitbox;
+    assert!(!game.map().blocks(hitbox));
+    assert!(hitbox.pos.0 > start.0 && hitbox.pos.0 + hitbox.w <= 8.);
+}
+
+#[test]
+fn bullets_fly_twice_as_far_across_as_down() {
+    let bullet = projectiles::bullet(Pos(5., 5.), std::f32::consts::FRAC_PI_4);
+    let Some(Velocity(Pos(x, y))) = bullet.velocity else {
+        panic!("bullets move");
+    };
+
+    assert!((x - 2. * y).abs() < 1e-6);
+}
+
+#[test]
+fn ignores_moves_that_are_not_finite() {
+    let mut game = Game::new(LEVEL, 0).unwrap();
+    let start = game.observe().players[0].hitbox.pos;
+    game.step([
+        Event::Player(HOST_ID, PlayerEvent::Move(f32::NAN, 0.)),
+        Event::Player(HOST_ID, PlayerEvent::Aim(Pos(f32::INFINITY, 1.))),
+    ]);
+    for _ in 0..50 {
+        game.step([]);
+    }
+
+    let observation = game.observe();
+    assert_eq!(observation.players[0].hitbox.pos, start);
+    assert!(observation
+        .enemies
+        .iter()
+        .all(|enemy| enemy.hitbox.pos.0.is_finite() && enemy.hitbox.pos.1.is_finite()));
+}
+
+#[test]
+fn moves_at_most_one_step_per_event() {
+    let level = "\
+####################
+#                  #
+#  @    #          #
+#       #          #
+#       #          #
+#                  #
+####################";
+    let mut game = Game::new(level, 0).unwrap();
+    let start = game.observe().players[0].hitbox.pos;
+    let run = || Event::Player(HOST_ID, PlayerEvent::Move(20., 0.));
+    game.step([run()]);
+    assert_eq!(game.observe().players[0].hitbox.pos, start + Pos(2., 0.));
+
+    for _ in 0..5 {
+        game.step([run()]);
+    }
+    let hitbox = &game.observe().players[0].hitbox;
+    assert!(hitbox.pos.0 + hitbox.w <= 8.);
+}
+
+#[test]
+fn spawns_players_apart_in_open_cells_near_the_start() {
+    let map = Map::try_from(levels::LEVELS[0].1).unwrap();
+    let mut state = State::new(map.clone(), map.bounds.clone(), 0);
+    let ids: Vec<_> = (0..MAX_PLAYERS).chain([20, 40, 255]).collect();
+    for id in &ids {
+        state.add_player(*id);
+    }
+
+    let hitboxes: Vec<_> = ids
+        .iter()
+        .map(|id| {
+            state
+                .world
+                .hitbox(state.world.player(*id).unwrap())
+                .unwrap()
+        })
+        .collect();
+    let start = hitboxes[0].clone();
+    for (i, hitbox) in hitboxes.iter().enumerate() {
+        assert!(!map.blocks(hitbox), "player {} is in a wall", ids[i]);
+        assert!(
+            hitbox.pos.0 >= 0. && hitbox.pos.0 + hitbox.w <= map.bounds.w,
+            "player {} is off the map",
+            ids[i]
+        );
+        assert!(hitbox.center().distance(&start.center()) < 12.);
+        assert!(hitboxes[..i].iter().all(|other| !other.intersects(hitbox)));
+    }
+}
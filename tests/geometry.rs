@@ -0,0 +1,311 @@
+//! Property tests: every property is checked against a few thousand shapes
+//! drawn from a seeded `Rng`, so a failure can be replayed from its seed.
+
+use std::f32::consts::PI;
+
+use breach::{
+    geometry::{grid::Grid, Circle, Pos, Rect, Segment},
+    rng::Rng,
+};
+
+const CASES: u64 = 2_000;
+const EPSILON: f32 = 1e-3;
+
+fn check(property: impl Fn(&mut Rng) -> bool) {
+    for seed in 0..CASES {
+        let mut rng = Rng::new(seed);
+        assert!(property(&mut rng), "property does not hold for seed {seed}");
+    }
+}
+
+fn close(a: f32, b: f32) -> bool {
+    (a - b).abs() <= EPSILON * a.abs().max(b.abs()).max(1.)
+}
+
+fn close_pos(a: Pos, b: Pos) -> bool {
+    close(a.0, b.0) && close(a.1, b.1)
+}
+
+fn pos(rng: &mut Rng) -> Pos {
+    Pos(rng.range(-100., 100.), rng.range(-100., 100.))
+}
+
+fn rect(rng: &mut Rng) -> Rect {
+    Rect::new(&pos(rng), rng.range(0.5, 20.), rng.range(0.5, 20.))
+}
+
+fn circle(rng: &mut Rng) -> Circle {
+    Circle::new(pos(rng), rng.range(0.5, 20.))
+}
+
+#[test]
+fn adding_and_subtracting_cancel_out() {
+    check(|rng| {
+        let (a, b) = (pos(rng), pos(rng));
+        close_pos(a + b - b, a) && a - a == Pos(0., 0.) && -(-a) == a && close_pos(a * 2., a + a)
+    });
+}
+
+#[test]
+fn normalized_vectors_have_length_one() {
+    check(|rng| {
+        let a = pos(rng);
+        a == Pos(0., 0.) || close(a.normalize().length(), 1.)
+    });
+    assert_eq!(Pos(0., 0.).normalize(), Pos(0., 0.));
+}
+
+#[test]
+fn dot_product_is_symmetric_and_gives_the_length() {
+    check(|rng| {
+        let (a, b) = (pos(rng), pos(rng));
+        close(a.dot(b), b.dot(a)) && close(a.dot(a).sqrt(), a.length())
+    });
+}
+
+#[test]
+fn rotating_keeps_the_length_and_can_be_undone() {
+    check(|rng| {
+        let a = pos(rng);
+        let angle = rng.range(-PI, PI);
+        let rotated = a.rotate(angle);
+
+        close(rotated.length(), a.length())
+            && close_pos(rotated.rotate(-angle), a)
+            && close(a.rotate(PI / 2.).dot(a) / a.length().max(1.), 0.)
+    });
+}
+
+#[test]
+fn lerp_goes_from_one_point_to_the_other() {
+    check(|rng| {
+        let (a, b) = (pos(rng), pos(rng));
+        let t = rng.f32();
+        let between = a.lerp(b, t);
+
+        close_pos(a.lerp(b, 0.), a)
+            && close_pos(a.lerp(b, 1.), b)
+            && close(a.distance(&between) + between.distance(&b), a.distance(&b))
+    });
+}
+
+#[test]
+fn cells_are_twice_as_wide_as_rows() {
+    check(|rng| {
+        let a = pos(rng);
+        close_pos(a.to_cells().from_cells(), a) && close(a.to_cells().0, a.0 * 2.)
+    });
+}
+
+#[test]
+fn rects_contain_their_corner_and_center_but_not_the_far_corner() {
+    check(|rng| {
+        let r = rect(rng);
+        let far = r.pos + Pos(r.w, r.h);
+        r.contains(&r.pos) && r.contains(&r.center()) && !r.contains(&far)
+    });
+}
+
+#[test]
+fn rects_contain_points_by_both_coordinates() {
+    let r = Rect::new(&Pos(0., 10.), 4., 4.);
+
+    assert!(r.contains(&Pos(1., 12.)));
+    assert!(!r.contains(&Pos(1., 2.)));
+}
+
+#[test]
+fn intersection_is_symmetric() {
+    check(|rng| {
+        let (a, b) = (rect(rng), rect(rng));
+        a.intersects(&b) == b.intersects(&a) && a.intersects(&a)
+    });
+    check(|rng| {
+        let (a, b) = (circle(rng), circle(rng));
+        a.intersects(&b) == b.intersects(&a)
+    });
+}
+
+#[test]
+fn unions_cover_both_rects() {
+    check(|rng| {
+        let (a, b) = (rect(rng), rect(rng));
+        let union = a.union(&b);
+        union.contains(&a.pos) && union.contains(&b.pos) && union.intersects(&a)
+    });
+}
+
+#[test]
+fn rays_into_a_rect_stop_at_its_edge_facing_back() {
+    check(|rng| {
+        let r = rect(rng);
+        let from = pos(rng);
+        if r.contains(&from) {
+            return r.raycast(&from, &r.center()).is_some_and(|hit| hit.t == 0.);
+        }
+        let Some(hit) = r.raycast(&from, &r.center()) else {
+            return false;
+        };
+        let Pos(x, y) = hit.point;
+        let on_edge = close(x, r.pos.0)
+            || close(x, r.pos.0 + r.w)
+            || close(y, r.pos.1)
+            || close(y, r.pos.1 + r.h);
+
+        (0. ..=1.).contains(&hit.t) && on_edge && hit.normal.dot(r.center() - from) < 0.
+    });
+}
+
+#[test]
+fn rays_that_stop_short_miss() {
+    check(|rng| {
+        let r = rect(rng);
+        let from = r.pos - Pos(rng.range(1., 10.), 0.);
+        let short = from.lerp(r.pos, rng.range(0., 0.99));
+        r.raycast(&from, &short).is_none()
+    });
+}
+
+#[test]
+fn rays_hit_the_side_they_come_from() {
+    let r = Rect::new(&Pos(4., 2.), 2., 2.);
+
+    let hit = r.raycast(&Pos(0., 3.), &Pos(8., 3.)).unwrap();
+    assert_eq!(
+        (hit.t, hit.point, hit.normal),
+        (0.5, Pos(4., 3.), Pos(-1., 0.))
+    );
+
+    let hit = r.raycast(&Pos(5., 8.), &Pos(5., 0.)).unwrap();
+    assert_eq!(
+        (hit.t, hit.point, hit.normal),
+        (0.5, Pos(5., 4.), Pos(0., 1.))
+    );
+
+    let hit = r.raycast(&Pos(5., 3.), &Pos(9., 3.)).unwrap();
+    assert_eq!((hit.t, hit.normal), (0., Pos(0., 0.)));
+}
+
+#[test]
+fn rays_past_or_away_from_a_rect_miss() {
+    let r = Rect::new(&Pos(4., 2.), 2., 2.);
+
+    assert_eq!(r.raycast(&Pos(0., 0.), &Pos(9., 0.)), None);
+    assert_eq!(r.raycast(&Pos(3., 3.), &Pos(0., 3.)), None);
+    assert_eq!(r.raycast(&Pos(0., 2.), &Pos(9., 2.)), None);
+}
+
+#[test]
+fn circles_touch_rects_they_are_centered_in() {
+    check(|rng| {
+        let r = rect(rng);
+        let c = Circle::new(r.center(), rng.range(0.1, 5.));
+        c.intersects_rect(&r) && c.contains(&c.center)
+    });
+}
+
+#[test]
+fn the_closest_point_on_a_segment_is_no_further_than_its_ends() {
+    check(|rng| {
+        let s = Segment::new(pos(rng), pos(rng));
+        let p = pos(rng);
+        let d = p.distance(&s.closest_point(&p));
+        d <= p.distance(&s.from) + EPSILON && d <= p.distance(&s.to) + EPSILON
+    });
+}
+
+#[test]
+fn segments_cross_on_both_of_them() {
+    check(|rng| {
+        let (a, b) = (
+            Segment::new(pos(rng), pos(rng)),
+            Segment::new(pos(rng), pos(rng)),
+        );
+        match a.intersection(&b) {
+            Some(p) => {
+                close(p.distance(&a.closest_point(&p)), 0.)
+                    && close(p.distance(&b.closest_point(&p)), 0.)
+            }
+            None => true,
+        }
+    });
+}
+
+#[test]
+fn segments_through_a_circle_center_touch_it() {
+    check(|rng| {
+        let c = circle(rng);
+        let from = pos(rng);
+        let through = Segment::new(from, c.center + (c.center - from));
+        let around = Rect::new(&(c.center - Pos(0.5, 0.5)), 1., 1.);
+        through.intersects_circle(&c) && through.intersects_rect(&around)
+    });
+}
+
+fn grid() -> Grid<u32> {
+    Grid::new(Rect::new(&Pos(0., 0.), 16., 16.), 4.)
+}
+
+#[test]
+fn grids_find_what_is_near_and_not_what_is_far() {
+    let mut grid = grid();
+    grid.insert(&Rect::new(&Pos(1., 1.), 1., 1.), 1);
+    grid.insert(&Rect::new(&Pos(13., 13.), 1., 1.), 2);
+    grid.insert(&Rect::new(&Pos(5., 1.), 1., 1.), 3);
+
+    assert_eq!(grid.query(&Rect::new(&Pos(0., 0.), 2., 2.)), [1]);
+    assert_eq!(grid.query(&Rect::new(&Pos(12., 12.), 2., 2.)), [2]);
+    assert_eq!(grid.query(&Rect::new(&Pos(0., 0.), 16., 16.)), [1, 2, 3]);
+
+    grid.clear();
+    assert!(grid.query(&Rect::new(&Pos(0., 0.), 16., 16.)).is_empty());
+}
+
+#[test]
+fn grids_keep_rects_on_cell_edges_in_both_cells() {
+    let mut grid = grid();
+    grid.insert(&Rect::new(&Pos(2., 0.), 2., 1.), 1);
+    grid.insert(&Rect::new(&Pos(4., 0.), 1., 1.), 2);
+
+    assert_eq!(grid.query(&Rect::new(&Pos(0., 0.), 1., 1.)), [1]);
+    assert_eq!(grid.query(&Rect::new(&Pos(6., 0.), 1., 1.)), [1, 2]);
+    assert_eq!(
+        grid.query(&Rect::new(&Pos(8., 0.), 1., 1.)),
+        Vec::<u32>::new()
+    );
+}
+
+#[test]
+fn grids_keep_rects_outside_in_the_cells_along_the_edge() {
+    let mut grid = grid();
+    grid.insert(&Rect::new(&Pos(-10., 2.), 1., 1.), 1);
+    grid.insert(&Rect::new(&Pos(30., 30.), 1., 1.), 2);
+
+    assert_eq!(grid.query(&Rect::new(&Pos(0., 0.), 1., 3.)), [1]);
+    assert_eq!(grid.query(&Rect::new(&Pos(15., 15.), 1., 1.)), [2]);
+}
+
+#[test]
+fn grids_give_each_item_once_in_order() {
+    check(|rng| {
+        let mut grid = grid();
+        let rects: Vec<_> = (0..20)
+            .map(|_| {
+                let pos = Pos(rng.range(-2., 16.), rng.range(-2., 16.));
+                Rect::new(&pos, rng.range(0.5, 9.), rng.range(0.5, 9.))
+            })
+            .collect();
+        for (i, rect) in rects.iter().enumerate() {
+            grid.insert(rect, i as u32);
+        }
+        let area = Rect::new(&Pos(rng.range(0., 12.), rng.range(0., 12.)), 4., 4.);
+        let found = grid.query(&area);
+
+        found.windows(2).all(|pair| pair[0] < pair[1])
+            && rects
+                .iter()
+                .enumerate()
+                .filter(|(_, rect)| rect.intersects(&area))
+                .all(|(i, _)| found.contains(&(i as u32)))
+    });
+}
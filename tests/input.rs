@@ -0,0 +1,91 @@
+#![cfg(feature = "tui")]
+
+use crossterm::event::{Event as TerminalEvent, KeyCode, KeyEvent, KeyModifiers};
+
+use breach::{
+    entity::player::{Ability, HOST_ID},
+    event::{Event, GameEvent, PlayerEvent},
+    input::{read, translate},
+    scene::Scene,
+    settings::{Bind, Keys},
+    Game,
+};
+
+const LEVEL: &str = "\
+##########
+# @      #
+##########";
+
+fn press(code: KeyCode) -> TerminalEvent {
+    TerminalEvent::Key(KeyEvent::new(code, KeyModifiers::NONE))
+}
+
+fn letter(key: char) -> TerminalEvent {
+    press(KeyCode::Char(key))
+}
+
+#[test]
+fn reads_letters_with_the_keys_they_are_bound_to() {
+    let mut keys = Keys::default();
+    let blink = Some(Event::Player(HOST_ID, PlayerEvent::Ability(Ability::Blink)));
+    assert_eq!(translate(&letter('e'), HOST_ID, &keys), blink);
+
+    keys.bind(Bind::Blink, 'w');
+    assert_eq!(translate(&letter('w'), HOST_ID, &keys), blink);
+    assert_eq!(
+        translate(&letter('e'), HOST_ID, &keys),
+        Some(Event::Player(HOST_ID, PlayerEvent::Move(0., -1.))),
+    );
+    assert_eq!(translate(&letter('z'), HOST_ID, &keys), None);
+}
+
+#[test]
+fn keeps_the_arrows_and_escape_whatever_is_bound() {
+    let mut keys = Keys::default();
+    keys.bind(Bind::Up, 'k');
+    keys.bind(Bind::Pause, 'z');
+
+    assert_eq!(
+        translate(&press(KeyCode::Up), HOST_ID, &keys),
+        Some(Event::Player(HOST_ID, PlayerEvent::Move(0., -1.))),
+    );
+    assert_eq!(
+        translate(&press(KeyCode::Esc), HOST_ID, &keys),
+        Some(Event::Game(GameEvent::Back)),
+    );
+    assert_eq!(
+        translate(&letter('z'), HOST_ID, &keys),
+        Some(Event::Game(GameEvent::Back)),
+    );
+    assert_eq!(translate(&letter('p'), HOST_ID, &keys), None);
+}
+
+#[test]
+fn gives_the_rebind_screen_the_next_letter_pressed() {
+    let mut game = Game::new(LEVEL, 0).unwrap();
+    game.settings_mut().keys.bind(Bind::Grenade, 'f');
+    assert_eq!(
+        read(&letter('f'), game.state(), HOST_ID),
+        Some(Event::Player(
+            HOST_ID,
+            PlayerEvent::Ability(Ability::Grenade)
+        )),
+    );
+
+    game.scenes_mut().push(Scene::Rebind(Bind::Blink));
+    let bind = Event::Game(GameEvent::Bind('q'));
+    assert_eq!(
+        read(&letter('q'), game.state(), HOST_ID),
+        Some(bind.clone())
+    );
+    assert_eq!(
+        read(&press(KeyCode::Esc), game.state(), HOST_ID),
+        Some(Event::Game(GameEvent::Back)),
+    );
+
+    game.step([bind]);
+    assert_eq!(
+        read(&letter('q'), game.state(), HOST_ID),
+        Some(Event::Player(HOST_ID, PlayerEvent::Ability(Ability::Blink))),
+    );
+}
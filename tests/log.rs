@@ -0,0 +1,70 @@
+use breach::{
+    geometry::{Pos, Rect},
+    log::{Category, MessageLog, CAPACITY},
+    render::Frame,
+};
+
+fn texts<'a>(entries: impl Iterator<Item = &'a breach::log::Entry>) -> Vec<&'a str> {
+    entries.map(|entry| entry.text.as_str()).collect()
+}
+
+#[test]
+fn drops_the_oldest_entries_past_capacity() {
+    let mut log = MessageLog::default();
+    for i in 0..CAPACITY + 2 {
+        log.push(0, Category::System, i);
+    }
+
+    assert_eq!(log.entries().count(), CAPACITY);
+    assert_eq!(log.entries().next().unwrap().text, "2");
+    assert_eq!(log.latest().unwrap().text, (CAPACITY + 1).to_string());
+}
+
+#[test]
+fn tails_recent_messages_without_debug_ones() {
+    let mut log = MessageLog::default();
+    log.push(0, Category::Combat, "old");
+    log.push(2000, Category::Combat, "a");
+    log.push(2001, Category::Debug, "noise");
+    log.push(2002, Category::Pickup, "b");
+    log.push(2003, Category::System, "c");
+
+    assert_eq!(texts(log.tail(2003)), ["a", "b", "c"]);
+    assert_eq!(texts(log.tail(3502)), ["c"]);
+}
+
+#[test]
+fn stamps_entries_with_the_time_they_were_posted() {
+    let mut log = MessageLog::default();
+    log.push(250, Category::Combat, "goblo was killed");
+
+    assert_eq!(
+        log.latest().unwrap().to_string(),
+        "[   1.0s] combat goblo was killed"
+    );
+}
+
+#[test]
+fn shows_the_full_log_when_open() {
+    let mut log = MessageLog::default();
+    for i in 0..10 {
+        log.push(i, Category::Debug, format!("line {i}"));
+    }
+    log.toggle();
+    let mut frame = Frame::new(40, 4);
+    log.render(10, &Rect::new(&Pos(0., 0.), 40., 4.), &mut frame);
+
+    let row = |y| -> String {
+        (0..40)
+            .filter_map(|x| frame.get(x, y))
+            .map(|c| c.ch)
+            .collect()
+    };
+    assert!(row(0).starts_with("Message log, 10 entries"));
+    assert!(row(3).trim_end().ends_with("line 9"));
+
+    log.scroll(100);
+    assert_eq!(log.scroll, 9);
+    log.scroll(-4);
+    assert_eq!(log.scroll, 5);
+}
@@ -0,0 +1,88 @@
+use std::{
+    net::TcpListener,
+    thread,
+    time::{Duration, Instant},
+};
+
+use breach::{
+    entity::{
+        player::{PlayerId, HOST_ID},
+        EntityKind,
+    },
+    event::PlayerEvent,
+    geometry::Pos,
+    net::{self, client::Client, server::Server},
+};
+
+fn free_port() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.local_addr().unwrap().port()
+}
+
+/// Polls `clients` until `done` holds for them, which fails after a while.
+fn until(clients: &mut [&mut Client], done: impl Fn(&[&mut Client]) -> bool) {
+    let started = Instant::now();
+    while !done(clients) {
+        assert!(started.elapsed() < Duration::from_secs(5), "timed out");
+        for client in clients.iter_mut() {
+            client.poll().unwrap();
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+fn player(client: &Client, id: PlayerId) -> Option<Pos> {
+    client
+        .snapshot()
+        .entities
+        .iter()
+        .find(|entity| entity.kind == EntityKind::Player(id))
+        .map(|entity| entity.pos)
+}
+
+#[test]
+fn hosts_and_joins_one_game_run_by_a_server() {
+    let port = free_port();
+    let mut host = net::host(("127.0.0.1", port)).unwrap();
+    let mut guest = net::join(("127.0.0.1", port)).unwrap();
+    assert_eq!((host.id, guest.id), (HOST_ID, HOST_ID + 1));
+
+    until(&mut [&mut host, &mut guest], |clients| {
+        clients[0].snapshot() == clients[1].snapshot()
+            && clients
+                .iter()
+                .all(|client| player(client, HOST_ID + 1).is_some())
+    });
+
+    let start = player(&host, guest.id).unwrap();
+    guest.send(PlayerEvent::Move(2., 0.)).unwrap();
+    let id = guest.id;
+    until(&mut [&mut host, &mut guest], |clients| {
+        clients
+            .iter()
+            .all(|client| player(client, id) == Some(start + Pos(2., 0.)))
+    });
+}
+
+#[test]
+fn hands_out_the_ids_of_clients_that_left_again() {
+    let server = Server::bind("127.0.0.1:0").unwrap();
+    let addr = server.local_addr().unwrap();
+    thread::spawn(move || server.run());
+
+    let first = Client::connect(addr).unwrap();
+    let second = Client::connect(addr).unwrap();
+    assert_eq!((first.id, second.id), (0, 1));
+    drop(first);
+
+    let started = Instant::now();
+    let reused = loop {
+        let client = Client::connect(addr).unwrap();
+        if client.id == 0 || started.elapsed() > Duration::from_secs(5) {
+            break client.id;
+        }
+        drop(client);
+        thread::sleep(Duration::from_millis(20));
+    };
+    assert_eq!(reused, 0);
+}
@@ -0,0 +1,40 @@
+use breach::{entity::particles::Emitter, geometry::Pos, rng::Rng};
+
+#[test]
+fn bursts_then_dies_down() {
+    let mut rng = Rng::new(0);
+    let mut emitter = Emitter::new("*.", 10, 5);
+
+    emitter.update(Pos(0., 0.), &mut rng);
+    assert_eq!(emitter.particles().len(), 10);
+
+    for _ in 0..5 {
+        emitter.update(Pos(0., 0.), &mut rng);
+    }
+    assert!(emitter.is_done());
+}
+
+#[test]
+fn keeps_emitting_at_its_rate() {
+    let mut rng = Rng::new(0);
+    let mut emitter = Emitter::new("*", 0, 100).rate(0.5, 10);
+
+    for _ in 0..10 {
+        emitter.update(Pos(0., 0.), &mut rng);
+    }
+
+    assert_eq!(emitter.particles().len(), 5);
+    assert!(!emitter.is_done());
+}
+
+#[test]
+fn gravity_pulls_particles_down() {
+    let mut rng = Rng::new(0);
+    let mut emitter = Emitter::new("*", 20, 30).speed(0.1, 0.2).gravity(0.1);
+
+    for _ in 0..20 {
+        emitter.update(Pos(0., 0.), &mut rng);
+    }
+
+    assert!(emitter.particles().iter().all(|p| p.pos.1 > 0.));
+}
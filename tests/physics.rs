@@ -0,0 +1,155 @@
+use std::f32::consts::FRAC_PI_2;
+
+use breach::{
+    entity::{
+        components::{Explosive, Velocity},
+        enemies::goblo,
+        player::{self, HOST_ID},
+        projectiles, EntityId,
+    },
+    geometry::Pos,
+    map::Map,
+    score::Stats,
+    state::State,
+    systems::{
+        collision::{Body, CollisionEvent, CollisionKind},
+        combat, physics,
+    },
+    world::{Commands, World},
+};
+
+const GRENADE: Explosive = Explosive {
+    fuse: 0,
+    radius: 6.,
+    force: 2.,
+    dmg: 0,
+    status: None,
+};
+
+fn close(a: Pos, b: Pos) -> bool {
+    (a - b).length() < 1e-5
+}
+
+fn pushed(world: &World, id: EntityId) -> Pos {
+    world.physics[&id].velocity
+}
+
+fn hit(world: &mut World, source: EntityId, target: EntityId, kind: CollisionKind) {
+    let event = CollisionEvent {
+        source,
+        target: Body::Entity(target),
+        kind,
+        dmg: 0,
+        contact: None,
+    };
+    combat::resolve(
+        world,
+        &[event],
+        &mut Stats::default(),
+        &mut Commands::default(),
+    );
+}
+
+#[test]
+fn bullets_knock_what_they_hit_back_the_way_they_flew() {
+    for (angle, direction) in [(0., Pos(1., 0.)), (FRAC_PI_2, Pos(0., 1.))] {
+        let mut world = World::default();
+        let enemy = world.spawn(goblo(Pos(5., 5.)));
+        let bullet = world.spawn(projectiles::bullet(Pos(6., 6.), angle));
+        let Velocity(flying) = world.velocities[&bullet];
+        assert!(close(flying.normalize(), direction));
+
+        hit(&mut world, bullet, enemy, CollisionKind::Projectile);
+
+        // Knockback 0.3 on a Goblo weighing 1.
+        assert!(close(pushed(&world, enemy), direction * 0.3));
+    }
+}
+
+#[test]
+fn melee_hits_shove_targets_away_from_the_attacker() {
+    let mut world = World::default();
+    let target = world.spawn(player::bundle(HOST_ID, Pos(4., 2.)));
+    // Level with the middle of the player, to its left and then below it.
+    let left = world.spawn(goblo(Pos(2.5, 2.5)));
+    let below = world.spawn(goblo(Pos(4.5, 6.)));
+
+    hit(&mut world, left, target, CollisionKind::Melee);
+    // Knockback 1 stretched into cells, on a player weighing 3.
+    assert!(close(pushed(&world, target), Pos(2. / 3., 0.)));
+
+    hit(&mut world, left, target, CollisionKind::Melee);
+    assert!(
+        close(pushed(&world, target), Pos(2. / 3., 0.)),
+        "cooling down attackers do not shove"
+    );
+
+    hit(&mut world, below, target, CollisionKind::Melee);
+    assert!(close(pushed(&world, target), Pos(2. / 3., -1. / 3.)));
+}
+
+#[test]
+fn explosions_push_straight_away_and_harder_up_close() {
+    let mut world = World::default();
+    let center = Pos(20., 20.);
+    // Offsets of the centers of Goblos from the explosion, in cells.
+    let offsets = [
+        Pos(4., 0.),
+        Pos(-4., 0.),
+        Pos(0., -4.),
+        Pos(6., 3.),
+        Pos(-2., 5.),
+    ];
+    let enemies: Vec<_> = offsets
+        .iter()
+        .map(|offset| world.spawn(goblo(center + *offset - Pos(1., 1.))))
+        .collect();
+    let far = world.spawn(goblo(center + Pos(14., 0.)));
+
+    physics::explode(&mut world, center, &GRENADE, &mut Commands::default());
+
+    for (offset, id) in offsets.iter().zip(&enemies) {
+        let push = pushed(&world, *id);
+        let along = push.dot(*offset);
+        let across = push.0 * offset.1 - push.1 * offset.0;
+        assert!(along > 0., "{offset:?} is pushed away");
+        assert!(across.abs() < 1e-5, "{offset:?} is pushed straight away");
+
+        let distance = offset.from_cells().length();
+        let strength = push.from_cells().length();
+        assert!((strength - GRENADE.force * (1. - distance / GRENADE.radius)).abs() < 1e-5);
+    }
+    let strength = |id: &EntityId| pushed(&world, *id).from_cells().length();
+    assert!(strength(&enemies[0]) > strength(&enemies[2]));
+    assert!(close(pushed(&world, far), Pos(0., 0.)));
+}
+
+#[test]
+fn explosions_do_not_push_anything_into_walls() {
+    let level = "\
+####################
+#                  #
+#                  #
+#                  #
+#                  #
+#                  #
+#                  #
+####################";
+    let map = Map::try_from(level).unwrap();
+    let mut state = State::new(map.clone(), map.bounds.clone(), 0);
+    let corner = state.world.spawn(goblo(Pos(17., 5.)));
+    let before = state.world.hitbox(corner).unwrap();
+
+    // Up and to the left of the Goblo, pushing it into both walls of the corner.
+    let center = before.center() - Pos(2., 1.);
+    physics::explode(&mut state.world, center, &GRENADE, &mut Commands::default());
+    assert!(pushed(&state.world, corner).0 > 0. && pushed(&state.world, corner).1 > 0.);
+    for _ in 0..30 {
+        state.frame();
+    }
+
+    let after = state.world.hitbox(corner).unwrap();
+    assert!(!state.map.blocks(&after));
+    assert_eq!(after.pos, before.pos);
+    assert!(close(pushed(&state.world, corner), Pos(0., 0.)));
+}
@@ -0,0 +1,195 @@
+use breach::{
+    entity::{
+        pickups::{Drops, PickupKind},
+        player::{Ability, Player, HOST_ID},
+        EntityKind,
+    },
+    event::{Event, PlayerEvent},
+    map::{validate::validate, Map},
+    net::protocol::Message,
+    render::Frame,
+    rng::Rng,
+    Game,
+};
+
+const LEVEL: &str = "\
+####################
+#                  #
+# @+               #
+#  =        G      #
+#                  #
+#                  #
+####################";
+
+fn player(game: &Game) -> &Player {
+    let world = &game.state().world;
+    world.players.values().next().unwrap()
+}
+
+fn input(event: PlayerEvent) -> Event {
+    Event::Player(HOST_ID, event)
+}
+
+#[test]
+fn places_pickups_with_map_glyphs() {
+    let map = Map::try_from(LEVEL).unwrap();
+    let kinds: Vec<_> = map.spawns.iter().map(|spawn| spawn.kind).collect();
+
+    assert_eq!(
+        kinds,
+        [
+            EntityKind::Pickup(PickupKind::Health),
+            EntityKind::Pickup(PickupKind::Ammo),
+            EntityKind::Goblo
+        ]
+    );
+    assert_eq!(validate(LEVEL), vec![]);
+}
+
+#[test]
+fn picks_up_what_the_player_walks_over() {
+    let mut game = Game::new(LEVEL, 0).unwrap();
+    game.step([]);
+
+    let inventory = &player(&game).inventory;
+    assert_eq!((inventory.medkits, inventory.magazines), (1, 1));
+    assert!(game.state().world.pickups.is_empty());
+    assert!(game
+        .state()
+        .log
+        .entries()
+        .any(|entry| entry.text == "player 0 picked up the medkit"));
+}
+
+#[test]
+fn leaves_pickups_there_is_no_room_for() {
+    let level = "\
+##########
+#        #
+# @++    #
+#  ++    #
+#        #
+#        #
+##########";
+    let mut game = Game::new(level, 0).unwrap();
+    game.step([]);
+
+    assert_eq!(player(&game).inventory.medkits, 3);
+    assert_eq!(game.state().world.pickups.len(), 1);
+}
+
+#[test]
+fn heals_with_a_medkit_only_when_hurt() {
+    let mut game = Game::new(LEVEL, 0).unwrap();
+    game.step([input(PlayerEvent::UseMedkit)]);
+    game.step([input(PlayerEvent::UseMedkit)]);
+    assert_eq!(player(&game).inventory.medkits, 1);
+
+    let hp = |game: &Game| game.observe().players[0].hp;
+    while hp(&game) == 100 && game.tick() < 5_000 {
+        game.step([]);
+    }
+    let hurt = hp(&game);
+    game.step([input(PlayerEvent::UseMedkit)]);
+
+    assert!(hp(&game) > hurt);
+    assert_eq!(player(&game).inventory.medkits, 0);
+}
+
+#[test]
+fn reloads_at_once_with_a_spare_magazine() {
+    let mut player = Player::new(HOST_ID);
+    player.pick_up(PickupKind::Ammo);
+    for _ in 0..12 {
+        assert!(player.fire());
+    }
+
+    assert_eq!(player.weapon.ammo, 12);
+    assert_eq!(player.weapon.reloading, 0);
+    assert_eq!(player.inventory.magazines, 0);
+
+    for _ in 0..12 {
+        player.fire();
+    }
+    assert!(player.weapon.reloading > 0);
+}
+
+#[test]
+fn spends_charges_on_abilities_that_are_cooling_down() {
+    let mut player = Player::new(HOST_ID);
+    assert!(player.use_ability(Ability::Blink));
+    assert!(!player.use_ability(Ability::Blink));
+
+    player.pick_up(PickupKind::Charge);
+    assert!(player.use_ability(Ability::Blink));
+    assert_eq!(player.inventory.charges, 0);
+}
+
+#[test]
+fn takes_up_weapons_and_switches_between_them() {
+    let mut player = Player::new(HOST_ID);
+
+    assert!(player.pick_up(PickupKind::Weapon));
+    assert_eq!(player.weapon.name, "smg");
+    assert!(!player.pick_up(PickupKind::Weapon));
+
+    assert!(player.switch_weapon());
+    assert_eq!(player.weapon.name, "pistol");
+    assert_eq!(player.inventory.weapons[0].name, "smg");
+}
+
+#[test]
+fn rolls_drops_by_their_chances() {
+    let mut rng = Rng::new(3);
+
+    assert_eq!(
+        Drops(&[(PickupKind::Ammo, 1.)]).roll(&mut rng),
+        Some(PickupKind::Ammo)
+    );
+    assert_eq!(Drops(&[(PickupKind::Ammo, 0.)]).roll(&mut rng), None);
+    assert_eq!(Drops(&[]).roll(&mut rng), None);
+}
+
+#[test]
+fn enemies_drop_pickups_when_killed() {
+    let level = "\
+####################
+#                  #
+# @          G     #
+#                  #
+#                  #
+#                  #
+####################";
+    let dropped = (0..20).any(|seed| {
+        let mut game = Game::new(level, seed).unwrap();
+        while !game.is_cleared() && game.tick() < 5_000 {
+            game.step([input(PlayerEvent::Shoot)]);
+        }
+        !game.state().world.pickups.is_empty()
+    });
+
+    assert!(dropped);
+}
+
+#[test]
+fn shows_the_inventory_in_the_hud() {
+    let mut game = Game::new(LEVEL, 0).unwrap();
+    game.step([Event::Game(breach::event::GameEvent::Resize(120, 20))]);
+    let mut frame = Frame::new(120, 20);
+    game.render(&mut frame);
+
+    let bottom: String = (0..120).map(|x| frame.get(x, 19).unwrap().ch).collect();
+    assert!(bottom.contains("+1 =1 *0"));
+}
+
+#[test]
+fn sends_inventory_inputs_over_the_network() {
+    for event in [PlayerEvent::UseMedkit, PlayerEvent::SwitchWeapon] {
+        let line = Message::Event(input(event.clone())).encode().unwrap();
+
+        assert!(matches!(
+            line.parse(),
+            Ok(Message::Event(Event::Player(HOST_ID, parsed))) if parsed == event
+        ));
+    }
+}
@@ -0,0 +1,70 @@
+use breach::{
+    entity::player::HOST_ID,
+    event::{Event, GameEvent, PlayerEvent},
+    replay::{Replay, ReplayError},
+    Game,
+};
+
+const LEVEL: &str = "\
+####################
+#                  #
+# @          G     #
+#                  #
+####################";
+
+fn inputs(step: u64) -> Vec<Event> {
+    match step % 40 {
+        0 => vec![Event::Player(HOST_ID, PlayerEvent::Shoot)],
+        10 => vec![Event::Player(HOST_ID, PlayerEvent::Move(2., 1.))],
+        20 => vec![Event::Game(GameEvent::ToggleLog)],
+        30 => vec![Event::Player(HOST_ID, PlayerEvent::Move(-2., -1.))],
+        _ => vec![],
+    }
+}
+
+#[test]
+fn plays_back_the_game_it_recorded() {
+    let mut game = Game::new(LEVEL, 3).unwrap();
+    let mut replay = Replay::new(LEVEL, 3);
+    for step in 0..400 {
+        let inputs = inputs(step);
+        replay.record(game.tick(), &inputs);
+        game.step(inputs);
+    }
+
+    let saved: Replay = replay.to_string().parse().unwrap();
+    assert_eq!(saved, replay);
+    assert_eq!(saved.play().unwrap().observe(), game.observe());
+}
+
+#[test]
+fn leaves_out_inputs_that_only_matter_to_the_terminal() {
+    let mut replay = Replay::new(LEVEL, 0);
+    replay.record(20, &inputs(20));
+
+    assert_eq!(replay.inputs(20).count(), 0);
+    assert_eq!(replay.steps, 21);
+}
+
+#[test]
+fn records_the_keys_bound_in_the_settings() {
+    let mut replay = Replay::new(LEVEL, 0);
+    let binds = [' ', 'k'].map(|key| Event::Game(GameEvent::Bind(key)));
+    replay.record(0, &binds);
+
+    let saved: Replay = replay.to_string().parse().unwrap();
+    assert_eq!(saved.inputs(0).collect::<Vec<_>>(), binds);
+}
+
+#[test]
+fn rejects_files_that_are_not_replays() {
+    assert_eq!("level 3".parse::<Replay>(), Err(ReplayError::NotAReplay));
+    assert_eq!(
+        "breach-replay 1\nseed 1\nlevel 1\n#@#\n0 dance\nend 1".parse::<Replay>(),
+        Err(ReplayError::InvalidLine(5))
+    );
+    assert_eq!(
+        "breach-replay 1\nseed 1\nlevel 2\n#@#".parse::<Replay>(),
+        Err(ReplayError::Truncated)
+    );
+}
@@ -0,0 +1,277 @@
+use breach::{
+    entity::player::HOST_ID,
+    event::{Event, GameEvent, PlayerEvent},
+    geometry::Pos,
+    render::Frame,
+    scene::{Action, Scene, Scenes},
+    scoreboard::Scoreboard,
+    settings::{Bind, Settings},
+    theme::Theme,
+    Game,
+};
+
+const LEVEL: &str = "\
+####################
+#                  #
+# @          G     #
+#                  #
+####################";
+
+fn game(scene: Scene) -> Game {
+    let mut game = Game::new(LEVEL, 0).unwrap();
+    game.step([Event::Game(GameEvent::Resize(60, 30))]);
+    game.scenes_mut().reset(scene);
+    game
+}
+
+fn key(event: GameEvent) -> Event {
+    Event::Game(event)
+}
+
+fn player(event: PlayerEvent) -> Event {
+    Event::Player(HOST_ID, event)
+}
+
+#[test]
+fn starts_the_first_level_from_the_main_menu() {
+    let mut game = game(Scene::MainMenu);
+    game.step([key(GameEvent::Select)]);
+
+    assert!(game.state().scenes.is_playing());
+    assert_eq!(game.map().name, "Outpost");
+    assert_eq!(game.observe().players.len(), 1);
+}
+
+#[test]
+fn freezes_the_game_while_paused() {
+    let mut game = game(Scene::Play);
+    game.step([key(GameEvent::Back)]);
+    assert_eq!(game.state().scenes.top(), Scene::Pause);
+
+    let frames = game.state().world.frames;
+    let pos = game.observe().players[0].hitbox.pos;
+    game.step([player(PlayerEvent::Move(2., 0.))]);
+    assert_eq!(game.state().world.frames, frames);
+    assert_eq!(game.observe().players[0].hitbox.pos, pos);
+
+    game.step([key(GameEvent::Back)]);
+    assert!(game.state().scenes.is_playing());
+}
+
+#[test]
+fn keeps_networked_games_going_under_the_pause_menu() {
+    let mut game = Game::lobby(LEVEL, 0).unwrap();
+    game.set_networked(true);
+    game.step([
+        key(GameEvent::Resize(60, 30)),
+        key(GameEvent::Join(HOST_ID)),
+        key(GameEvent::Join(1)),
+    ]);
+    game.step([key(GameEvent::Pause)]);
+    assert_eq!(game.state().scenes.top(), Scene::Pause);
+
+    let frames = game.state().world.frames;
+    let moved = |game: &Game, id| game.observe().player(id).unwrap().hitbox.pos;
+    let (host, guest) = (moved(&game, HOST_ID), moved(&game, 1));
+    game.step([
+        player(PlayerEvent::Move(2., 0.)),
+        Event::Player(1, PlayerEvent::Move(2., 0.)),
+    ]);
+    assert_eq!(game.state().world.frames, frames + 1);
+    assert_eq!(moved(&game, HOST_ID), host + Pos(2., 0.));
+    assert_eq!(moved(&game, 1), guest + Pos(2., 0.));
+
+    game.navigate(&PlayerEvent::Move(0., 1.));
+    game.step([key(GameEvent::Select)]);
+    assert_eq!(game.state().scenes.top(), Scene::Settings);
+    assert_eq!(moved(&game, HOST_ID), host + Pos(2., 0.));
+}
+
+#[test]
+fn changes_settings_from_the_pause_menu() {
+    let mut game = game(Scene::Play);
+    game.step([
+        key(GameEvent::Pause),
+        player(PlayerEvent::Move(0., 1.)),
+        key(GameEvent::Select),
+    ]);
+    assert_eq!(game.state().scenes.top(), Scene::Settings);
+
+    game.step([player(PlayerEvent::Move(2., 0.))]);
+    assert_eq!(game.state().settings.theme, Theme::DUSK);
+    game.step([
+        player(PlayerEvent::Move(-2., 0.)),
+        player(PlayerEvent::Move(-2., 0.)),
+    ]);
+    assert_eq!(game.state().settings.theme, Theme::COLORBLIND);
+
+    game.step([key(GameEvent::Back)]);
+    assert_eq!(game.state().scenes.top(), Scene::Pause);
+}
+
+/// Opens the settings and picks the row of `bind` in them.
+fn rebind(game: &mut Game, bind: Bind) {
+    game.scenes_mut().reset(Scene::Settings);
+    let items = Scene::Settings.items(&game.state().settings, &game.state().scoreboard);
+    let row = items
+        .iter()
+        .position(|item| item.action == Some(Action::Rebind(bind)))
+        .unwrap();
+    game.scenes_mut().select(row);
+    game.step([key(GameEvent::Select)]);
+}
+
+#[test]
+fn rebinds_keys_from_the_settings() {
+    let mut game = game(Scene::Settings);
+    rebind(&mut game, Bind::Blink);
+    assert_eq!(game.state().scenes.top(), Scene::Rebind(Bind::Blink));
+
+    game.step([key(GameEvent::Bind('b'))]);
+    assert_eq!(game.state().scenes.top(), Scene::Settings);
+    assert_eq!(game.state().settings.keys.key(Bind::Blink), 'b');
+    assert_eq!(game.state().settings.keys.bind_of('e'), None);
+
+    rebind(&mut game, Bind::Blink);
+    game.step([key(GameEvent::Bind('w'))]);
+    let keys = game.state().settings.keys;
+    assert_eq!(keys.bind_of('w'), Some(Bind::Blink));
+    assert_eq!(keys.bind_of('b'), Some(Bind::Up), "the keys trade places");
+
+    rebind(&mut game, Bind::Quit);
+    game.step([key(GameEvent::Back)]);
+    assert_eq!(game.state().scenes.top(), Scene::Settings);
+    assert_eq!(game.state().settings.keys.key(Bind::Quit), 'q');
+}
+
+#[test]
+fn ignores_keys_to_bind_outside_the_rebind_screen() {
+    let mut game = game(Scene::Settings);
+    game.step([key(GameEvent::Bind('b'))]);
+
+    assert_eq!(game.state().settings, Settings::default());
+}
+
+#[test]
+fn picks_menu_items_with_the_mouse() {
+    let mut game = game(Scene::MainMenu);
+    let mut frame = Frame::new(60, 30);
+    game.render(&mut frame);
+
+    let quit = (0..30)
+        .find(|y| {
+            (0..60)
+                .filter_map(|x| frame.get(x, *y))
+                .map(|cell| cell.ch)
+                .collect::<String>()
+                .contains("Quit")
+        })
+        .unwrap();
+    game.step([
+        player(PlayerEvent::Aim(Pos(30., f32::from(quit)))),
+        player(PlayerEvent::Shoot),
+    ]);
+
+    assert!(game.state().quit);
+}
+
+#[test]
+fn skips_lines_that_cannot_be_picked() {
+    let settings = Settings::default();
+    let items = Scene::Credits.items(&settings, &Scoreboard::default());
+    let mut scenes = Scenes::new(Scene::Credits);
+
+    scenes.step(&items, 1);
+    assert_eq!(items[scenes.selected()].label, "Back");
+    scenes.step(&items, 1);
+    assert_eq!(items[scenes.selected()].label, "Back");
+}
+
+#[test]
+fn never_closes_the_last_scene() {
+    let mut scenes = Scenes::new(Scene::MainMenu);
+    scenes.push(Scene::Credits);
+    scenes.pop();
+    scenes.pop();
+
+    assert_eq!(scenes.top(), Scene::MainMenu);
+}
+
+#[test]
+fn opens_the_game_over_screen_once_every_player_has_died() {
+    let level = "\
+##########
+#GGG GGG #
+#G  @  GG#
+#GGG GGG #
+##########";
+    let mut game = Game::new(level, 0).unwrap();
+    while !game.is_lost() && game.tick() < 50_000 {
+        game.step([]);
+    }
+
+    assert_eq!(game.state().scenes.top(), Scene::GameOver);
+    game.step([key(GameEvent::Select)]);
+    assert!(game.state().scenes.is_playing());
+    assert!(!game.is_lost());
+}
+
+#[test]
+fn pauses_and_asks_for_room_when_the_terminal_is_too_small() {
+    let mut game = game(Scene::Play);
+    game.step([key(GameEvent::Resize(12, 4))]);
+    assert_eq!(game.state().scenes.top(), Scene::Pause);
+
+    let mut frame = Frame::new(12, 4);
+    game.render(&mut frame);
+    let text: String = (0..4)
+        .flat_map(|y| (0..12).map(move |x| (x, y)))
+        .filter_map(|(x, y)| frame.get(x, y))
+        .map(|cell| cell.ch)
+        .collect();
+    assert!(text.contains("Terminal too"));
+
+    game.step([key(GameEvent::Resize(60, 30)), key(GameEvent::Back)]);
+    assert!(game.state().scenes.is_playing());
+}
+
+fn text(game: &Game, w: u16, h: u16) -> String {
+    let mut frame = Frame::new(w, h);
+    game.render(&mut frame);
+    (0..h)
+        .flat_map(|y| (0..w).map(move |x| (x, y)))
+        .filter_map(|(x, y)| frame.get(x, y))
+        .map(|cell| cell.ch)
+        .collect()
+}
+
+#[test]
+fn plays_on_once_there_is_room_again() {
+    let mut game = game(Scene::Play);
+    for _ in 0..2 {
+        game.step([key(GameEvent::Resize(12, 4))]);
+        assert_eq!(game.state().scenes.top(), Scene::Pause);
+
+        game.step([key(GameEvent::Resize(60, 30)), key(GameEvent::Play)]);
+        let frames = game.state().world.frames;
+        game.step([]);
+        assert!(game.state().scenes.is_playing());
+        assert_eq!(game.state().world.frames, frames + 1);
+        assert!(!text(&game, 60, 30).contains("Terminal too"));
+    }
+}
+
+#[test]
+fn only_asks_for_room_in_networked_games() {
+    let mut game = game(Scene::Play);
+    game.set_networked(true);
+    let frames = game.state().world.frames;
+    game.step([key(GameEvent::Resize(12, 4))]);
+
+    assert!(game.state().scenes.is_playing());
+    assert_eq!(game.state().world.frames, frames + 1);
+    assert!(text(&game, 12, 4).contains("Terminal too"));
+
+    game.step([key(GameEvent::Resize(60, 30))]);
+    assert!(!text(&game, 60, 30).contains("Terminal too"));
+}
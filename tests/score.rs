@@ -0,0 +1,147 @@
+use breach::{
+    entity::player::HOST_ID,
+    event::{Event, GameEvent, PlayerEvent},
+    render::Frame,
+    scene::Scene,
+    score::{Stats, COMBO_WINDOW, MAX_COMBO},
+    Game,
+};
+
+const LEVEL: &str = "\
+####################
+#                  #
+# @          G     #
+#                  #
+#                  #
+#                  #
+####################";
+
+fn shoot() -> Event {
+    Event::Player(HOST_ID, PlayerEvent::Shoot)
+}
+
+fn clear(game: &mut Game) {
+    while !game.is_cleared() && game.tick() < 5_000 {
+        game.step([shoot()]);
+    }
+}
+
+#[test]
+fn multiplies_points_for_kills_in_quick_succession() {
+    let mut stats = Stats::default();
+    stats.kill(100, 0);
+    stats.kill(100, COMBO_WINDOW);
+    assert_eq!(stats.score, 300);
+    assert_eq!(stats.combo_at(COMBO_WINDOW), 2);
+
+    stats.kill(100, COMBO_WINDOW * 3);
+    assert_eq!(stats.score, 400);
+    assert_eq!(stats.combo_at(COMBO_WINDOW * 5), 0);
+    assert_eq!((stats.kills, stats.best_combo), (3, 2));
+}
+
+#[test]
+fn caps_the_combo() {
+    let mut stats = Stats::default();
+    for frame in 0..10 {
+        stats.kill(10, frame);
+    }
+
+    assert_eq!(stats.best_combo, MAX_COMBO);
+    assert_eq!(stats.score, 10 * (1 + 2 + 3 + 4) + 10 * MAX_COMBO * 6);
+}
+
+#[test]
+fn counts_shots_and_the_bullets_that_hit() {
+    let mut game = Game::new(LEVEL, 0).unwrap();
+    clear(&mut game);
+
+    let stats = game.state().stats;
+    assert!(stats.hits > 0);
+    assert!(stats.shots >= stats.hits);
+    assert_eq!(
+        stats.accuracy(),
+        Some(stats.hits as f32 / stats.shots as f32)
+    );
+    assert_eq!(Stats::default().accuracy(), None);
+}
+
+#[test]
+fn shows_a_summary_once_the_level_is_cleared() {
+    let mut game = Game::new(LEVEL, 0).unwrap();
+    clear(&mut game);
+
+    let stats = game.state().stats;
+    assert_eq!((stats.kills, stats.score), (1, 100));
+    assert_eq!(stats.cleared_at, Some(game.state().world.frames));
+    assert_eq!(game.state().scenes.top(), Scene::Cleared(stats));
+
+    let mut frame = Frame::new(20, 7);
+    game.render(&mut frame);
+    let text: String = (0..7)
+        .flat_map(|y| (0..20).map(move |x| (x, y)))
+        .map(|(x, y)| frame.get(x, y).unwrap().ch)
+        .collect();
+    assert!(text.contains("Score"));
+}
+
+#[test]
+fn tallies_the_damage_players_take() {
+    let mut game = Game::new(LEVEL, 0).unwrap();
+    let hp = |game: &Game| game.observe().players[0].hp;
+    while hp(&game) == 100 && game.tick() < 5_000 {
+        game.step([]);
+    }
+
+    assert!(hp(&game) < 100);
+    assert_eq!(game.state().stats.damage_taken, 100 - hp(&game));
+}
+
+#[test]
+fn starts_over_with_the_level() {
+    let mut game = Game::new(LEVEL, 0).unwrap();
+    clear(&mut game);
+    game.step([Event::Game(GameEvent::Select)]);
+
+    assert!(game.state().scenes.is_playing());
+    assert_eq!(game.state().stats, Stats::default());
+}
+
+#[test]
+fn levels_without_enemies_have_nothing_to_clear() {
+    let mut game = Game::new(&LEVEL.replace('G', " "), 0).unwrap();
+    game.step([]);
+
+    assert!(game.state().scenes.is_playing());
+    assert_eq!(game.state().stats.cleared_at, None);
+}
+
+#[test]
+fn shows_the_score_in_the_hud() {
+    let mut game = Game::new(LEVEL, 0).unwrap();
+    game.step([Event::Game(GameEvent::Resize(60, 7))]);
+    let mut frame = Frame::new(60, 7);
+    game.render(&mut frame);
+
+    let top: String = (0..60).map(|x| frame.get(x, 0).unwrap().ch).collect();
+    assert!(top.trim_end().ends_with("0 pts  1 enemy left"));
+}
+
+#[test]
+fn tallies_the_damage_players_take_from_fire() {
+    let level = "\
+####################
+#^@^^^             #
+#^^^^^             #
+#^^^^^             #
+#^^^^^             #
+####################";
+    let mut game = Game::new(level, 0).unwrap();
+    let hp = |game: &Game| game.observe().players[0].hp;
+    while hp(&game) > 80 && game.tick() < 5_000 {
+        game.step([]);
+    }
+
+    assert!(hp(&game) <= 80);
+    assert_eq!(game.state().stats.damage_taken, 100 - hp(&game));
+}
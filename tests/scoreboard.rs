@@ -0,0 +1,129 @@
+use std::path::PathBuf;
+
+use breach::{
+    entity::player::HOST_ID,
+    event::{Event, GameEvent, PlayerEvent},
+    render::Frame,
+    scene::Scene,
+    scoreboard::{date, Run, Scoreboard, ScoreboardError, VERSION},
+    Game,
+};
+
+fn run(level: &str, score: u32, time: u32) -> Run {
+    Run {
+        date: 1_760_832_000,
+        level: level.to_string(),
+        seed: 0,
+        cleared: true,
+        score,
+        time,
+        player: "ada".to_string(),
+        replay: None,
+    }
+}
+
+#[test]
+fn saves_and_reads_back_every_run() {
+    let mut scoreboard = Scoreboard::default();
+    scoreboard.add(run("First contact", 300, 2750));
+    scoreboard.add(Run {
+        cleared: false,
+        replay: Some(PathBuf::from("/home/ada/run.replay")),
+        ..run("Hall", 100, 900)
+    });
+
+    let text = scoreboard.to_string();
+    assert!(text.starts_with(&format!("breach-scores {VERSION}\n")));
+    assert_eq!(text.parse(), Ok(scoreboard));
+}
+
+#[test]
+fn keeps_fields_from_splitting_lines() {
+    let mut scoreboard = Scoreboard::default();
+    scoreboard.add(Run {
+        player: "ada\tlovelace\n".to_string(),
+        ..run("Hall", 100, 900)
+    });
+    let saved: Scoreboard = scoreboard.to_string().parse().unwrap();
+
+    assert_eq!(saved.runs[0].player, "ada lovelace ");
+}
+
+#[test]
+fn reads_scoreboards_of_newer_versions_without_writing_them() {
+    let text = "breach-scores 9\n\
+                1760832000\tHall\t4\tcleared\t500\t1000\tada\t-\tgold\n";
+    let scoreboard: Scoreboard = text.parse().unwrap();
+
+    assert_eq!(scoreboard.runs[0].score, 500);
+    assert_eq!(scoreboard.runs[0].seed, 4);
+    assert!(!scoreboard.is_writable());
+    assert!(Scoreboard::default().is_writable());
+}
+
+#[test]
+fn rejects_what_is_not_a_scoreboard() {
+    assert_eq!(
+        "breach-replay 1".parse::<Scoreboard>(),
+        Err(ScoreboardError::NotAScoreboard)
+    );
+    assert_eq!(
+        "breach-scores 1\n# comment\n\n1\tHall\t0\twon\t5\t5\tada\t-".parse::<Scoreboard>(),
+        Err(ScoreboardError::InvalidLine(4))
+    );
+}
+
+#[test]
+fn ranks_runs_by_score_then_time() {
+    let mut scoreboard = Scoreboard::default();
+    scoreboard.add(run("Hall", 100, 900));
+    scoreboard.add(run("Hall", 300, 2000));
+    scoreboard.add(run("Hall", 300, 1500));
+    scoreboard.add(Run {
+        cleared: false,
+        ..run("Hall", 900, 100)
+    });
+    scoreboard.add(run("Yard", 50, 100));
+
+    let best: Vec<_> = scoreboard
+        .best("Hall")
+        .iter()
+        .map(|run| (run.score, run.time))
+        .collect();
+    assert_eq!(best, [(300, 1500), (300, 2000), (100, 900)]);
+    assert_eq!(scoreboard.levels(), ["Hall", "Yard"]);
+
+    let lines = scoreboard.leaderboard();
+    assert_eq!(lines[0], "Hall");
+    assert!(lines[1].starts_with(" 1.    300     6.0s  ada"));
+    assert!(lines[1].contains("2025-10-19"));
+    assert_eq!(lines[4], "");
+}
+
+#[test]
+fn dates_runs_by_the_day() {
+    assert_eq!(date(0), "1970-01-01");
+    assert_eq!(date(951_782_400), "2000-02-29");
+    assert_eq!(date(1_760_918_399), "2025-10-19");
+}
+
+#[test]
+fn shows_the_leaderboard_from_the_main_menu() {
+    let mut game = Game::new("#####\n#@  #\n#####", 0).unwrap();
+    game.scenes_mut().reset(Scene::MainMenu);
+    game.scoreboard_mut().add(run("Hall", 300, 1500));
+    let down = || Event::Player(HOST_ID, PlayerEvent::Move(0., 1.));
+    game.step([Event::Game(GameEvent::Resize(60, 20)), down()]);
+    game.step([down()]);
+    game.step([Event::Game(GameEvent::Select)]);
+    assert_eq!(game.state().scenes.top(), Scene::HighScores);
+
+    let mut frame = Frame::new(60, 20);
+    game.render(&mut frame);
+    let text: String = (0..20)
+        .flat_map(|y| (0..60).map(move |x| (x, y)))
+        .map(|(x, y)| frame.get(x, y).unwrap().ch)
+        .collect();
+    assert!(text.contains("High scores"));
+    assert!(text.contains("300"));
+}
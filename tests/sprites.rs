@@ -0,0 +1,101 @@
+use breach::{
+    entity::sprites::{self, Facing, Sprite, SpriteError},
+    geometry::Pos,
+    render::{Color, Frame},
+};
+
+const SPRITE: &str = "\
+clip walk 2
+face left
+frame
+ab
+ c
+colors
+r.
+.g
+frame
+de
+face right
+frame
+xy
+";
+
+#[test]
+fn ships_with_sprites_for_every_entity() {
+    assert!(sprites::get("player").is_some());
+    assert!(sprites::get("goblo").is_some());
+}
+
+#[test]
+fn animates_through_the_frames_of_a_clip() {
+    let sprite: Sprite = SPRITE.parse().unwrap();
+    let clip = sprite.clip("walk").unwrap();
+
+    let mut frame = Frame::new(4, 4);
+    clip.frame(Facing::Left, 2)
+        .unwrap()
+        .draw(&mut frame, Pos(0., 0.), None);
+    assert_eq!(frame.get(0, 0).unwrap().ch, 'd');
+
+    clip.frame(Facing::Left, 4)
+        .unwrap()
+        .draw(&mut frame, Pos(0., 0.), None);
+    assert_eq!(frame.get(0, 0).unwrap().ch, 'a');
+}
+
+#[test]
+fn colors_cells_and_leaves_spaces_see_through() {
+    let sprite: Sprite = SPRITE.parse().unwrap();
+    let clip = sprite.clip("walk").unwrap();
+
+    let mut frame = Frame::new(4, 4);
+    frame.print(Pos(0., 1.), "zz");
+    clip.frame(Facing::Left, 0)
+        .unwrap()
+        .draw(&mut frame, Pos(0., 0.), Some(Color::Blue));
+
+    assert_eq!(frame.get(0, 0).unwrap().fg, Some(Color::Red));
+    assert_eq!(frame.get(1, 0).unwrap().fg, Some(Color::Blue));
+    assert_eq!(frame.get(0, 1).unwrap().ch, 'z');
+    assert_eq!(frame.get(1, 1).unwrap().fg, Some(Color::Green));
+}
+
+#[test]
+fn faces_where_it_is_looking() {
+    let sprite: Sprite = SPRITE.parse().unwrap();
+    let clip = sprite.clip("walk").unwrap();
+    let mut frame = Frame::new(4, 4);
+
+    assert_eq!(Facing::from_angle(0.1), Facing::Right);
+    assert_eq!(Facing::from_angle(-3.), Facing::Left);
+    clip.frame(Facing::from_angle(0.1), 0)
+        .unwrap()
+        .draw(&mut frame, Pos(0., 0.), None);
+    assert_eq!(frame.get(0, 0).unwrap().ch, 'x');
+}
+
+#[test]
+fn clips_at_the_edges_of_the_screen() {
+    let sprite: Sprite = SPRITE.parse().unwrap();
+    let clip = sprite.clip("walk").unwrap();
+    let mut frame = Frame::new(2, 2);
+
+    clip.frame(Facing::Left, 0)
+        .unwrap()
+        .draw(&mut frame, Pos(-1., -1.), None);
+
+    assert_eq!(frame.get(0, 0).unwrap().ch, 'c');
+}
+
+#[test]
+fn rejects_broken_sprites() {
+    assert_eq!("".parse::<Sprite>(), Err(SpriteError::Empty));
+    assert_eq!(
+        "clip a 1\nframe\nab\ncolors\nz".parse::<Sprite>(),
+        Err(SpriteError::UnknownColor('z'))
+    );
+    assert_eq!(
+        "clip a 1\nface sideways".parse::<Sprite>(),
+        Err(SpriteError::UnknownFacing("sideways".to_string()))
+    );
+}
@@ -0,0 +1,49 @@
+use breach::entity::status::{Status, StatusKind, Statuses};
+
+#[test]
+fn poison_stacks_up_to_a_limit() {
+    let mut statuses = Statuses::default();
+    for _ in 0..10 {
+        statuses.apply(Status::new(StatusKind::Poison, 50));
+    }
+
+    let poison = statuses.get(StatusKind::Poison).unwrap();
+    assert_eq!(poison.stacks, 5);
+    assert_eq!(poison.dmg(), 5);
+}
+
+#[test]
+fn reapplying_keeps_the_longer_duration_without_stacking() {
+    let mut statuses = Statuses::default();
+    statuses.apply(Status::new(StatusKind::Burning, 100));
+    statuses.apply(Status::new(StatusKind::Burning, 40));
+
+    assert_eq!(
+        statuses.get(StatusKind::Burning),
+        Some(&Status {
+            kind: StatusKind::Burning,
+            ticks: 100,
+            stacks: 1,
+        })
+    );
+}
+
+#[test]
+fn stuns_stop_and_slows_slow_down() {
+    let mut statuses = Statuses::default();
+    statuses.apply(Status::new(StatusKind::Slow, 10));
+    assert!(statuses.speed() > 0. && statuses.speed() < 1.);
+
+    statuses.apply(Status::new(StatusKind::Stun, 10));
+    assert!(statuses.speed() <= 0.);
+    assert_eq!(statuses.color(), Some(StatusKind::Stun.color()));
+}
+
+#[test]
+fn vulnerable_entities_take_more_damage() {
+    let mut statuses = Statuses::default();
+    assert_eq!(statuses.damage(4), 4);
+
+    statuses.apply(Status::new(StatusKind::Vulnerable, 10));
+    assert_eq!(statuses.damage(4), 6);
+}
@@ -0,0 +1,68 @@
+use breach::{
+    render::Color,
+    theme::{ColorDepth, Palette, Role, TermColor, Theme},
+};
+
+#[test]
+fn detects_color_depth_from_the_environment() {
+    let depth = ColorDepth::from_vars;
+
+    assert_eq!(
+        depth(None, Some("truecolor"), Some("xterm")),
+        ColorDepth::TrueColor
+    );
+    assert_eq!(
+        depth(None, None, Some("xterm-256color")),
+        ColorDepth::Ansi256
+    );
+    assert_eq!(depth(None, None, Some("xterm")), ColorDepth::Ansi16);
+    assert_eq!(depth(None, None, Some("dumb")), ColorDepth::None);
+    assert_eq!(depth(Some("1"), Some("truecolor"), None), ColorDepth::None);
+    assert_eq!(depth(Some(""), None, None), ColorDepth::Ansi16);
+}
+
+#[test]
+fn resolves_roles_through_the_theme() {
+    let palette = Palette::new(Theme::CLASSIC, ColorDepth::Ansi16);
+
+    assert_eq!(
+        palette.resolve(Color::Role(Role::Player(0))),
+        palette.resolve(Color::Red)
+    );
+    assert_eq!(
+        palette.resolve(Color::Role(Role::Fov)),
+        Some(TermColor::Ansi(0))
+    );
+}
+
+#[test]
+fn downgrades_true_color() {
+    let color = Color::Rgb(255, 0, 0);
+    let at = |depth| Palette::new(Theme::DUSK, depth).resolve(color);
+
+    assert_eq!(at(ColorDepth::TrueColor), Some(TermColor::Rgb(255, 0, 0)));
+    assert_eq!(at(ColorDepth::Ansi256), Some(TermColor::Ansi(196)));
+    assert_eq!(at(ColorDepth::Ansi16), Some(TermColor::Ansi(9)));
+    assert_eq!(at(ColorDepth::None), None);
+}
+
+#[test]
+fn downgrades_grays_to_the_gray_ramp() {
+    let palette = Palette::new(Theme::CLASSIC, ColorDepth::Ansi256);
+
+    assert_eq!(
+        palette.resolve(Color::Rgb(128, 128, 128)),
+        Some(TermColor::Ansi(244))
+    );
+}
+
+#[test]
+fn ships_a_colorblind_theme() {
+    let theme = Theme::get("colorblind").unwrap();
+
+    assert_ne!(
+        theme.color(Color::Role(Role::Burning)),
+        theme.color(Color::Role(Role::Poison))
+    );
+    assert!(Theme::ALL.iter().any(|t| t.name == "classic"));
+}
@@ -0,0 +1,147 @@
+use breach::{
+    geometry::{Pos, Rect},
+    map::{
+        levels,
+        validate::{analyze, validate, Problem},
+        Map, MapError,
+    },
+};
+
+#[test]
+fn passes_the_built_in_levels() {
+    for (name, text) in levels::LEVELS {
+        assert_eq!(validate(text), vec![], "{name}");
+    }
+}
+
+#[test]
+fn needs_rows_and_a_single_start() {
+    assert_eq!(validate(":name Nothing"), vec![Problem::Empty]);
+    assert_eq!(validate(":name Nothing\n\n"), vec![Problem::Empty]);
+    assert_eq!(validate("#####\n#   #\n#####"), vec![Problem::MissingStart]);
+    assert_eq!(
+        validate("#####\n#@ @#\n#####"),
+        vec![Problem::DuplicateStart(vec![Pos(1., 1.), Pos(3., 1.)])]
+    );
+}
+
+#[test]
+fn finds_ragged_rows_and_unknown_characters() {
+    let problems = validate("#####\n#@ x#\n#x#\n#####");
+
+    assert_eq!(
+        problems,
+        vec![
+            Problem::Ragged {
+                row: 2,
+                width: 3,
+                expected: 5
+            },
+            Problem::UnknownGlyph('x', vec![Pos(3., 1.), Pos(1., 2.)]),
+        ]
+    );
+    assert!(problems.iter().all(Problem::is_error));
+}
+
+#[test]
+fn finds_enemies_in_walls() {
+    let problems = validate(
+        "\
+#######
+#@   G#
+#   G #
+#     #
+#######",
+    );
+
+    assert_eq!(problems, vec![Problem::EnemyInWall(Pos(5., 1.))]);
+}
+
+#[test]
+fn finds_areas_that_cannot_be_reached() {
+    let problems = validate(
+        "\
+##########
+#@  #    #
+#   #  G #
+#   #    #
+##########",
+    );
+
+    assert_eq!(
+        problems,
+        vec![Problem::Unreachable {
+            area: Rect::new(&Pos(5., 1.), 4., 3.),
+            cells: 12,
+            enemies: 1,
+        }]
+    );
+    assert!(problems[0].is_error());
+    assert_eq!(
+        problems[0].to_string(),
+        "12 cells between 5,1 and 8,3 cannot be reached from the start, with an enemy in them"
+    );
+}
+
+#[test]
+fn warns_about_empty_unreachable_areas() {
+    let problems = validate("#######\n#@ #  #\n#######");
+
+    assert_eq!(problems.len(), 1);
+    assert!(!problems[0].is_error());
+}
+
+#[test]
+fn finds_rooms_without_a_way_out() {
+    let map = Map::try_from(
+        "\
+:room Closet 5 0 5 5
+:room Hall 0 0 5 5
+##########
+#@  #    #
+#        #
+#   #    #
+##########",
+    )
+    .unwrap();
+    assert_eq!(analyze(&map), vec![]);
+
+    let map = Map::try_from(
+        "\
+:room Closet 5 0 5 5
+:room Hall 0 0 5 5
+##########
+#@  #    #
+#   ##   #
+#   #    #
+##########",
+    )
+    .unwrap();
+    let problems = analyze(&map);
+
+    assert_eq!(
+        problems[1..],
+        [
+            Problem::NoExit("Closet".to_string()),
+            Problem::NoExit("Hall".to_string())
+        ]
+    );
+    assert!(!problems[1].is_error());
+}
+
+#[test]
+fn will_not_make_a_map_without_cells() {
+    for text in ["", "\n", ":name Nothing\n\n"] {
+        assert_eq!(Map::try_from(text).err(), Some(MapError::Empty), "{text:?}");
+    }
+}
+
+#[test]
+fn maps_are_as_wide_as_their_widest_row() {
+    let map = Map::try_from("\n#####\n#@ G#\n#####").unwrap();
+
+    assert_eq!((map.bounds.w, map.bounds.h), (5., 4.));
+    assert_eq!(map.start_pos, Pos(1., 2.));
+    assert_eq!(map.spawns.len(), 1);
+    assert!(map.is_wall(&Pos(4., 3.)));
+}
@@ -0,0 +1,240 @@
+use std::{
+    io::{Cursor, Read},
+    net::TcpStream,
+    thread,
+};
+
+use breach::{
+    entity::{
+        pickups::PickupKind,
+        player::{Ability, HOST_ID},
+        EntityId, EntityKind,
+    },
+    event::{Event, PlayerEvent},
+    geometry::Pos,
+    net::{
+        server::{Server, KEYFRAME_EVERY, SNAPSHOT_EVERY},
+        snapshot::{Delta, EntityState, Snapshot},
+        wire::{
+            read_frame, write_frame, ClientMessage, ServerMessage, WireError, PROTOCOL_VERSION,
+        },
+    },
+    Game,
+};
+
+const LEVEL: &str = "\
+####################
+#                  #
+# @          G     #
+#                  #
+####################";
+
+fn entity(id: u32, kind: EntityKind, x: f32) -> EntityState {
+    EntityState::new(EntityId(id), kind, Pos(x, 2.), 0.5)
+}
+
+fn snapshot(tick: u64, entities: Vec<EntityState>) -> Snapshot {
+    Snapshot { tick, entities }
+}
+
+fn client_messages() -> Vec<ClientMessage> {
+    let events = [
+        PlayerEvent::Move(1., -1.),
+        PlayerEvent::Aim(Pos(3.5, 4.25)),
+        PlayerEvent::Shoot,
+        PlayerEvent::Ability(Ability::Blink),
+        PlayerEvent::Ability(Ability::Grenade),
+        PlayerEvent::UseMedkit,
+        PlayerEvent::SwitchWeapon,
+    ];
+    std::iter::once(ClientMessage::Hello {
+        version: PROTOCOL_VERSION,
+    })
+    .chain(events.into_iter().map(ClientMessage::Event))
+    .collect()
+}
+
+fn server_messages() -> Vec<ServerMessage> {
+    let kinds = [
+        EntityKind::Player(3),
+        EntityKind::Goblo,
+        EntityKind::Bullet,
+        EntityKind::Effect,
+        EntityKind::Grenade,
+    ]
+    .into_iter()
+    .chain(PickupKind::ALL.map(EntityKind::Pickup));
+    let entities: Vec<_> = kinds
+        .enumerate()
+        .map(|(i, kind)| entity(i as u32, kind, i as f32))
+        .collect();
+
+    vec![
+        ServerMessage::Welcome {
+            version: PROTOCOL_VERSION,
+            id: 2,
+        },
+        ServerMessage::Reject("the server is full".to_string()),
+        ServerMessage::Snapshot(snapshot(64, entities.clone())),
+        ServerMessage::Delta(Delta {
+            base: 64,
+            tick: 72,
+            changed: entities,
+            removed: vec![EntityId(40), EntityId(41)],
+        }),
+    ]
+}
+
+#[test]
+fn reads_back_every_client_message() {
+    for message in client_messages() {
+        assert_eq!(ClientMessage::decode(&message.encode()), Ok(message));
+    }
+}
+
+#[test]
+fn reads_back_every_server_message() {
+    for message in server_messages() {
+        assert_eq!(ServerMessage::decode(&message.encode()), Ok(message));
+    }
+}
+
+#[test]
+fn rejects_truncated_and_unknown_messages_without_panicking() {
+    for bytes in client_messages().iter().map(ClientMessage::encode) {
+        for len in 0..bytes.len() {
+            assert!(ClientMessage::decode(&bytes[..len]).is_err());
+        }
+    }
+    for bytes in server_messages().iter().map(ServerMessage::encode) {
+        for len in 0..bytes.len() {
+            assert!(ServerMessage::decode(&bytes[..len]).is_err());
+        }
+    }
+
+    assert_eq!(
+        ClientMessage::decode(&[9]),
+        Err(WireError::UnknownTag("client message", 9))
+    );
+    assert_eq!(
+        ServerMessage::decode(&[1, 2, 0, 0, 0, 0xff, 0xfe]),
+        Err(WireError::InvalidText)
+    );
+}
+
+#[test]
+fn rejects_numbers_that_are_not_finite() {
+    for v in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+        let moved = ClientMessage::Event(PlayerEvent::Move(v, 0.)).encode();
+        let aimed = ClientMessage::Event(PlayerEvent::Aim(Pos(0., v))).encode();
+
+        assert_eq!(ClientMessage::decode(&moved), Err(WireError::NonFinite));
+        assert_eq!(ClientMessage::decode(&aimed), Err(WireError::NonFinite));
+    }
+}
+
+#[test]
+fn frames_messages_and_rejects_broken_frames() {
+    let mut stream = vec![];
+    write_frame(&mut stream, b"hello").unwrap();
+    assert_eq!(read_frame(&mut Cursor::new(&stream)).unwrap(), b"hello");
+
+    assert!(read_frame(&mut Cursor::new(&stream[..stream.len() - 1])).is_err());
+    assert!(read_frame(&mut Cursor::new(&stream[..2])).is_err());
+    assert!(read_frame(&mut Cursor::new(u32::MAX.to_le_bytes())).is_err());
+}
+
+#[test]
+fn turns_the_base_into_the_next_snapshot_with_a_delta() {
+    let a = snapshot(
+        8,
+        vec![
+            entity(0, EntityKind::Player(0), 1.),
+            entity(1, EntityKind::Goblo, 5.),
+            entity(2, EntityKind::Bullet, 2.),
+        ],
+    );
+    let b = snapshot(
+        16,
+        vec![
+            entity(0, EntityKind::Player(0), 1.),
+            entity(1, EntityKind::Goblo, 4.),
+            entity(3, EntityKind::Bullet, 3.),
+        ],
+    );
+    let delta = b.delta_from(&a);
+
+    assert_eq!(delta.changed.len(), 2);
+    assert_eq!(delta.removed, [EntityId(2)]);
+    assert_eq!(a.apply(&delta), Some(b.clone()));
+    assert_eq!(b.apply(&delta), None);
+}
+
+#[test]
+fn keeps_a_client_in_sync_through_deltas_and_keyframes() {
+    let mut game = Game::new(LEVEL, 3).unwrap();
+    let mut last = Snapshot::default();
+    let mut client = Snapshot::default();
+    let (mut deltas, mut keyframes) = (0, 0);
+
+    while game.tick() < SNAPSHOT_EVERY * KEYFRAME_EVERY * 2 {
+        game.step([Event::Player(HOST_ID, PlayerEvent::Shoot)]);
+        let tick = game.tick();
+        if !tick.is_multiple_of(SNAPSHOT_EVERY) {
+            continue;
+        }
+
+        let snapshot = game.state().snapshot(tick);
+        let message = if (tick / SNAPSHOT_EVERY).is_multiple_of(KEYFRAME_EVERY) {
+            keyframes += 1;
+            ServerMessage::Snapshot(snapshot.clone())
+        } else {
+            deltas += 1;
+            ServerMessage::Delta(snapshot.delta_from(&last))
+        };
+        client = match ServerMessage::decode(&message.encode()).unwrap() {
+            ServerMessage::Snapshot(snapshot) => snapshot,
+            ServerMessage::Delta(delta) => client.apply(&delta).unwrap(),
+            message => panic!("unexpected {message:?}"),
+        };
+
+        assert_eq!(client, snapshot);
+        last = snapshot;
+    }
+    assert_eq!(keyframes, 2);
+    assert!(deltas > keyframes);
+}
+
+#[test]
+fn interpolates_between_snapshots() {
+    let a = snapshot(8, vec![entity(0, EntityKind::Goblo, 2.)]);
+    let b = snapshot(
+        16,
+        vec![
+            entity(0, EntityKind::Goblo, 4.),
+            entity(1, EntityKind::Bullet, 9.),
+        ],
+    );
+
+    let halfway = a.interpolate(&b, 0.5);
+    assert_eq!(halfway[0].pos, Pos(3., 2.));
+    assert_eq!(halfway[1].pos, Pos(9., 2.));
+    assert_eq!(a.interpolate(&b, 7.)[0].pos, Pos(4., 2.));
+}
+
+#[test]
+fn turns_away_clients_of_another_version() {
+    let server = Server::bind("127.0.0.1:0").unwrap();
+    let addr = server.local_addr().unwrap();
+    thread::spawn(move || server.run());
+
+    let mut stream = TcpStream::connect(addr).unwrap();
+    let hello = ClientMessage::Hello {
+        version: PROTOCOL_VERSION + 1,
+    };
+    write_frame(&mut stream, &hello.encode()).unwrap();
+
+    let reply = ServerMessage::decode(&read_frame(&mut stream).unwrap()).unwrap();
+    assert!(matches!(reply, ServerMessage::Reject(reason) if reason.contains("version")));
+    assert_eq!(stream.read(&mut [0]).unwrap(), 0);
+}